@@ -0,0 +1,24 @@
+use eframe::egui::{Response, RichText, Spinner, Ui, WidgetInfo, WidgetType};
+
+/// Names an icon-only button for screen readers, and for sighted users on hover
+pub fn label(response: Response, label: &str) -> Response {
+    response.widget_info(|| WidgetInfo::labeled(WidgetType::Button, label));
+    response.on_hover_text(label)
+}
+
+/// Spinner, or a still hourglass when reduced motion turned the style's animations off
+pub fn spinner(ui: &mut Ui, size: Option<f32>) -> Response {
+    if ui.style().animation_time > 0. {
+        let spinner = Spinner::new();
+        ui.add(match size {
+            Some(size) => spinner.size(size),
+            None => spinner,
+        })
+    } else {
+        let hourglass = RichText::new("⏳");
+        ui.label(match size {
+            Some(size) => hourglass.size(size),
+            None => hourglass,
+        })
+    }
+}
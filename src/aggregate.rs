@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// How many articles each source may contribute to a refresh
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SourceCaps {
+    pub default: usize,
+    pub per_source: BTreeMap<String, usize>,
+}
+
+impl Default for SourceCaps {
+    fn default() -> Self {
+        Self {
+            default: 15,
+            per_source: BTreeMap::new(),
+        }
+    }
+}
+
+impl SourceCaps {
+    pub fn cap(&self, source: &str) -> usize {
+        self.per_source.get(source).copied().unwrap_or(self.default)
+    }
+}
+
+/// Interleaves the articles of every source round-robin, keeping at most the source's cap of each,
+/// so a prolific feed can't push the others out of view
+pub fn merge<T>(batches: Vec<(&str, Vec<T>)>, caps: &SourceCaps) -> Vec<T> {
+    let mut queues: Vec<_> = batches
+        .into_iter()
+        .map(|(source, items)| items.into_iter().take(caps.cap(source)))
+        .collect();
+
+    let mut merged = Vec::new();
+    loop {
+        let len = merged.len();
+        for queue in &mut queues {
+            merged.extend(queue.next());
+        }
+        if merged.len() == len {
+            return merged;
+        }
+    }
+}
@@ -0,0 +1,247 @@
+//! Terminal frontend: the top headlines of the app's countries in a list, opened in the browser
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::execute;
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use headlines::{FetchState, HeadlinesError, NewsCardData, StoredConfig};
+use ratatui::backend::{Backend, CrosstermBackend};
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Span, Spans};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{Frame, Terminal};
+use std::io;
+use std::sync::mpsc::{channel, Receiver, TryRecvError};
+use std::thread;
+use std::time::Duration;
+
+const USAGE: &str = "\
+Usage: headlines-tui [--api-key <key>]
+
+Lists the top headlines of the app's countries, with its stored API key by default.";
+const KEYS: &str = "↑↓ move  enter open  r refresh  q quit";
+/// Wait for a key before checking on the fetch again
+const POLL: Duration = Duration::from_millis(200);
+/// Lines moved by page up and page down
+const PAGE: usize = 10;
+
+type Fetch = (Vec<NewsCardData>, Vec<HeadlinesError>);
+
+struct App {
+    state: FetchState,
+    articles: Vec<NewsCardData>,
+    list: ListState,
+    status: String,
+    /// Fetch under way, if any
+    rx: Option<Receiver<Fetch>>,
+}
+
+impl App {
+    fn new(state: FetchState) -> Self {
+        Self {
+            state,
+            articles: Vec::new(),
+            list: ListState::default(),
+            status: String::new(),
+            rx: None,
+        }
+    }
+
+    /// Fetches the headlines again in the background, the list stays until they're in
+    fn refresh(&mut self) {
+        if self.rx.is_some() {
+            return;
+        }
+        let (tx, rx) = channel();
+        let state = self.state.clone();
+        thread::spawn(move || {
+            // the receiver is gone when the user quit in the meantime
+            let _ = tx.send(headlines::fetch_all(&state));
+        });
+        self.rx = Some(rx);
+        self.status = "Fetching…".to_string();
+    }
+
+    /// Takes the fetch's headlines once it's over
+    fn poll(&mut self) {
+        let fetched = match &self.rx {
+            Some(rx) => rx.try_recv(),
+            None => return,
+        };
+        match fetched {
+            Ok((articles, errors)) => {
+                self.status = match errors.first() {
+                    Some(e) => e.to_string(),
+                    None => format!("{} headlines", articles.len()),
+                };
+                self.list.select((!articles.is_empty()).then_some(0));
+                self.articles = articles;
+                self.rx = None;
+            }
+            Err(TryRecvError::Empty) => {}
+            Err(TryRecvError::Disconnected) => {
+                self.status = "The fetch stopped unexpectedly".to_string();
+                self.rx = None;
+            }
+        }
+    }
+
+    /// Moves the selection by `offset` lines, staying in the list
+    fn select(&mut self, offset: isize) {
+        let last = match self.articles.len() {
+            0 => return,
+            len => len - 1,
+        };
+        let current = self.list.selected().unwrap_or(0);
+        let next = if offset < 0 {
+            current.saturating_sub(offset.unsigned_abs())
+        } else {
+            current.saturating_add(offset as usize).min(last)
+        };
+        self.list.select(Some(next));
+    }
+
+    fn selected(&self) -> Option<&NewsCardData> {
+        self.list.selected().and_then(|i| self.articles.get(i))
+    }
+
+    fn open(&mut self) {
+        let url = match self.selected() {
+            Some(article) => article.url.clone(),
+            None => return,
+        };
+        if let Err(e) = webbrowser::open(&url) {
+            self.status = format!("Failed opening {}: {}", url, e);
+        }
+    }
+}
+
+fn main() {
+    let stored = StoredConfig::load();
+    let mut api_key = stored.api_key.clone();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--api-key" => api_key = args.next().unwrap_or_default(),
+            "--help" | "-h" => {
+                println!("{}", USAGE);
+                return;
+            }
+            other => {
+                eprintln!("headlines-tui: unknown option \"{}\"\n\n{}", other, USAGE);
+                std::process::exit(2);
+            }
+        }
+    }
+    if api_key.is_empty() {
+        eprintln!("headlines-tui: no API key, pass --api-key or set it in the app first");
+        std::process::exit(2);
+    }
+
+    let state = FetchState::new(
+        api_key,
+        stored.countries(),
+        stored.source_caps,
+        stored.retry,
+    );
+    if let Err(e) = run(App::new(state)) {
+        eprintln!("headlines-tui: {}", e);
+        std::process::exit(1);
+    }
+}
+
+/// Runs the app in the terminal's alternate screen, giving the terminal back as it was
+fn run(app: App) -> io::Result<()> {
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+    let result = event_loop(&mut terminal, app);
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+    result
+}
+
+fn event_loop<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io::Result<()> {
+    app.refresh();
+    loop {
+        app.poll();
+        terminal.draw(|f| draw(f, &mut app))?;
+        if !event::poll(POLL)? {
+            continue;
+        }
+        let key = match event::read()? {
+            // Windows also reports releases
+            Event::Key(key) if key.kind == KeyEventKind::Press => key,
+            _ => continue,
+        };
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+            KeyCode::Down | KeyCode::Char('j') => app.select(1),
+            KeyCode::Up | KeyCode::Char('k') => app.select(-1),
+            KeyCode::PageDown => app.select(PAGE as isize),
+            KeyCode::PageUp => app.select(-(PAGE as isize)),
+            KeyCode::Home | KeyCode::Char('g') => app.select(isize::MIN),
+            KeyCode::End | KeyCode::Char('G') => app.select(isize::MAX),
+            KeyCode::Enter | KeyCode::Char('o') => app.open(),
+            KeyCode::Char('r') => app.refresh(),
+            _ => {}
+        }
+    }
+}
+
+/// Headlines on top, the selected one's description under them and the status line at the bottom
+fn draw<B: Backend>(f: &mut Frame<B>, app: &mut App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(3),
+            Constraint::Length(6),
+            Constraint::Length(1),
+        ])
+        .split(f.size());
+
+    let items: Vec<ListItem> = app
+        .articles
+        .iter()
+        .map(|a| {
+            ListItem::new(Spans::from(vec![
+                Span::styled(
+                    a.title.as_str(),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("  "),
+                Span::styled(
+                    a.source.as_str(),
+                    Style::default().add_modifier(Modifier::DIM),
+                ),
+            ]))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title("Headlines"))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+    f.render_stateful_widget(list, chunks[0], &mut app.list);
+
+    let (desc, url) = match app.selected() {
+        Some(a) => (a.desc.as_str(), a.url.as_str()),
+        None => ("", ""),
+    };
+    let details = Paragraph::new(vec![
+        Spans::from(desc),
+        Spans::from(Span::styled(
+            url,
+            Style::default().add_modifier(Modifier::UNDERLINED),
+        )),
+    ])
+    .block(Block::default().borders(Borders::ALL))
+    .wrap(Wrap { trim: true });
+    f.render_widget(details, chunks[1]);
+
+    let status = Paragraph::new(format!("{}  |  {}", app.status, KEYS));
+    f.render_widget(status, chunks[2]);
+}
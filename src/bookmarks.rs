@@ -0,0 +1,40 @@
+use crate::news::NewsCardData;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct Bookmark {
+    pub article: NewsCardData,
+    pub bookmarked_at: DateTime<Utc>,
+}
+
+/// Bookmarked articles, most recent first
+#[derive(Default, Serialize, Deserialize)]
+pub struct Bookmarks {
+    bookmarks: Vec<Bookmark>,
+}
+
+impl Bookmarks {
+    pub fn contains(&self, url: &str) -> bool {
+        self.bookmarks.iter().any(|b| b.article.url == url)
+    }
+
+    /// Bookmarks `article`, or removes its bookmark if it already has one
+    pub fn toggle(&mut self, article: &NewsCardData) {
+        if self.contains(&article.url) {
+            self.bookmarks.retain(|b| b.article.url != article.url);
+        } else {
+            self.bookmarks.insert(
+                0,
+                Bookmark {
+                    article: article.clone(),
+                    bookmarked_at: Utc::now(),
+                },
+            );
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Bookmark> {
+        self.bookmarks.iter()
+    }
+}
@@ -0,0 +1,147 @@
+use crate::news::NewsCardData;
+use eframe::egui::{Color32, FontId, Label, RichText, TextStyle, Ui};
+use serde::{Deserialize, Serialize};
+
+/// Words of a title a similar search looks for
+const SIMILAR_WORDS: usize = 3;
+/// Shorter words are too common to tell articles apart
+const SIMILAR_MIN_LEN: usize = 4;
+
+/// What clicking a card does
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CardAction {
+    Nothing,
+    ShowDetails,
+    OpenReader,
+    OpenBrowser,
+    /// Opens the browser without leaving the app, where the platform allows
+    OpenInBackground,
+    Bookmark,
+    ToggleRead,
+    SaveToPocket,
+    SaveToWallabag,
+    Toot,
+    MuteSource,
+    Hide,
+    SearchSimilar,
+}
+
+impl CardAction {
+    pub const ALL: [CardAction; 13] = [
+        CardAction::Nothing,
+        CardAction::ShowDetails,
+        CardAction::OpenReader,
+        CardAction::OpenBrowser,
+        CardAction::OpenInBackground,
+        CardAction::Bookmark,
+        CardAction::ToggleRead,
+        CardAction::SaveToPocket,
+        CardAction::SaveToWallabag,
+        CardAction::Toot,
+        CardAction::MuteSource,
+        CardAction::Hide,
+        CardAction::SearchSimilar,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            CardAction::Nothing => "Nothing",
+            CardAction::ShowDetails => "Show details",
+            CardAction::OpenReader => "Open in reader",
+            CardAction::OpenBrowser => "Open in browser",
+            CardAction::OpenInBackground => "Open in background",
+            CardAction::Bookmark => "Bookmark",
+            CardAction::ToggleRead => "Mark read / unread",
+            CardAction::SaveToPocket => "Save to Pocket",
+            CardAction::SaveToWallabag => "Save to wallabag",
+            CardAction::Toot => "Toot this",
+            CardAction::MuteSource => "Mute the source",
+            CardAction::Hide => "Hide",
+            CardAction::SearchSimilar => "Search similar",
+        }
+    }
+}
+
+/// Actions bound to the mouse buttons on a card
+///
+/// A double click is also a single click, binding both runs the two actions.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct CardBindings {
+    pub click: CardAction,
+    pub double_click: CardAction,
+    pub middle_click: CardAction,
+}
+
+impl Default for CardBindings {
+    fn default() -> Self {
+        Self {
+            click: CardAction::ShowDetails,
+            double_click: CardAction::OpenBrowser,
+            middle_click: CardAction::Bookmark,
+        }
+    }
+}
+
+/// Text cut to its first `max_rows` rows when laid out in `width`, None if it already fits
+pub fn truncate_rows(
+    ui: &Ui,
+    text: &str,
+    font_id: FontId,
+    width: f32,
+    max_rows: usize,
+) -> Option<String> {
+    let galley = ui
+        .fonts()
+        .layout(text.to_string(), font_id, Color32::WHITE, width);
+    if galley.rows.len() <= max_rows {
+        return None;
+    }
+    let kept: usize = galley.rows[..max_rows]
+        .iter()
+        .map(|row| row.char_count_including_newline())
+        .sum();
+    // leave room for the ellipsis on the last row
+    let text: String = text.chars().take(kept.saturating_sub(2)).collect();
+    Some(format!("{}…", text.trim_end()))
+}
+
+/// Query for articles like the one titled `title`: its longest words, names and topics more
+/// often than not
+pub fn similar_query(title: &str) -> String {
+    let mut words: Vec<&str> = title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() >= SIMILAR_MIN_LEN)
+        .collect();
+    // stable, the title's order breaks ties
+    words.sort_by_key(|word| std::cmp::Reverse(word.chars().count()));
+    let mut picked: Vec<&str> = Vec::new();
+    for word in words {
+        if picked.len() == SIMILAR_WORDS {
+            break;
+        }
+        if !picked.iter().any(|p| p.eq_ignore_ascii_case(word)) {
+            picked.push(word);
+        }
+    }
+    picked.join(" ")
+}
+
+/// Titles linking to the articles over their descriptions cut to `desc_lines`, lighter than cards
+pub fn show_list(ui: &mut Ui, articles: &[NewsCardData], desc_lines: usize) {
+    for a in articles {
+        ui.hyperlink_to(RichText::new(&a.title).strong(), &a.url);
+        let desc = truncate_rows(
+            ui,
+            &a.desc,
+            TextStyle::Button.resolve(ui.style()),
+            ui.available_width(),
+            desc_lines,
+        );
+        let desc = desc.as_deref().unwrap_or(&a.desc);
+        ui.add(Label::new(
+            RichText::new(desc).text_style(TextStyle::Button),
+        ));
+        ui.separator();
+    }
+}
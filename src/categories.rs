@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+/// Categories of newsapi.org's top headlines
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+pub enum Category {
+    Business,
+    Entertainment,
+    Health,
+    Science,
+    Sports,
+    Technology,
+}
+
+impl Category {
+    pub const ALL: [Category; 6] = [
+        Self::Business,
+        Self::Entertainment,
+        Self::Health,
+        Self::Science,
+        Self::Sports,
+        Self::Technology,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Business => "Business",
+            Self::Entertainment => "Entertainment",
+            Self::Health => "Health",
+            Self::Science => "Science",
+            Self::Sports => "Sports",
+            Self::Technology => "Tech",
+        }
+    }
+
+    /// Category of its name in newsapi.org's API
+    pub fn from_api_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|category| category.api_name() == name)
+    }
+
+    /// Name of the category in newsapi.org's API
+    pub fn api_name(&self) -> &'static str {
+        match self {
+            Self::Business => "business",
+            Self::Entertainment => "entertainment",
+            Self::Health => "health",
+            Self::Science => "science",
+            Self::Sports => "sports",
+            Self::Technology => "technology",
+        }
+    }
+}
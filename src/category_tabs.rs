@@ -0,0 +1,109 @@
+use crate::accessibility;
+use crate::cards;
+use crate::categories::Category;
+use crate::news::NewsCardData;
+use crate::worker::Fetched;
+use eframe::egui::{ScrollArea, Ui};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::mpsc::Receiver;
+
+#[derive(Default)]
+struct Feed {
+    articles: Vec<NewsCardData>,
+    /// Fetch under way, if any
+    rx: Option<Receiver<Fetched>>,
+    errors: Vec<String>,
+}
+
+/// Tabs above the feed, the top headlines stay the main feed and every category has its own list,
+/// fetched the first time its tab is opened and kept until it's refreshed
+#[derive(Default)]
+pub struct CategoryTabs {
+    /// None for the top headlines
+    active: Option<Category>,
+    feeds: BTreeMap<Category, Feed>,
+}
+
+impl CategoryTabs {
+    pub fn active(&self) -> Option<Category> {
+        self.active
+    }
+
+    /// Switches to the tab of the category, or to the top headlines
+    pub fn open(&mut self, category: Option<Category>) {
+        self.active = category;
+    }
+
+    /// Replaces the articles of the category with those of a new fetch
+    pub fn start(&mut self, category: Category, rx: Receiver<Fetched>) {
+        let feed = self.feeds.entry(category).or_default();
+        feed.articles.clear();
+        feed.errors.clear();
+        feed.rx = Some(rx);
+    }
+
+    /// Collects what the fetches under way sent
+    pub fn poll(&mut self) {
+        for feed in self.feeds.values_mut() {
+            let mut done = false;
+            if let Some(rx) = &feed.rx {
+                for fetched in rx.try_iter() {
+                    match fetched {
+                        Fetched::Progress => {}
+                        Fetched::Articles(articles) => feed.articles.extend(articles),
+                        Fetched::Failed(error) => feed.errors.push(error.to_string()),
+                        Fetched::Done => done = true,
+                    }
+                }
+            }
+            if done {
+                feed.rx = None;
+            }
+        }
+    }
+
+    /// Draws the tab bar with the tabs of the `shown` categories, every one when empty, returns the
+    /// category to fetch when its tab is opened for the first time
+    pub fn show_bar(&mut self, ui: &mut Ui, shown: &BTreeSet<Category>) -> Option<Category> {
+        let mut opened = None;
+        ui.horizontal_wrapped(|ui| {
+            ui.selectable_value(&mut self.active, None, "Top");
+            for category in Category::ALL {
+                if !shown.is_empty() && !shown.contains(&category) {
+                    continue;
+                }
+                let tab = ui.selectable_value(&mut self.active, Some(category), category.name());
+                if tab.clicked() && !self.feeds.contains_key(&category) {
+                    opened = Some(category);
+                }
+            }
+        });
+        ui.separator();
+        opened
+    }
+
+    /// Draws the list of the active category, returns it when a refresh is asked for
+    pub fn show_feed(&mut self, ui: &mut Ui, desc_lines: usize) -> Option<Category> {
+        let category = self.active?;
+        let feed = self.feeds.entry(category).or_default();
+        let mut refresh = false;
+        ui.horizontal(|ui| {
+            refresh = ui.button("🔄 Refresh").clicked();
+            if feed.rx.is_some() {
+                accessibility::spinner(ui, None);
+            }
+        });
+        for error in &feed.errors {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+        ScrollArea::vertical()
+            .id_source(("category", category))
+            .show(ui, |ui| {
+                if feed.articles.is_empty() && feed.rx.is_none() {
+                    ui.weak("No articles");
+                }
+                cards::show_list(ui, &feed.articles, desc_lines);
+            });
+        refresh.then_some(category)
+    }
+}
@@ -0,0 +1,40 @@
+const CHANGELOG: &str = include_str!("../CHANGELOG.md");
+
+pub const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+pub struct Release {
+    pub version: &'static str,
+    pub notes: Vec<&'static str>,
+}
+
+/// Released versions of the embedded changelog newer than `since`, most recent first.
+/// Unparsable versions (e.g. an empty string) are treated as older than every release.
+pub fn releases_since(since: &str) -> Vec<Release> {
+    let since = parse_version(since).unwrap_or_default();
+    let mut releases: Vec<Release> = Vec::new();
+    let mut current: Option<Release> = None;
+
+    for line in CHANGELOG.lines() {
+        if let Some(heading) = line.strip_prefix("## ") {
+            releases.extend(current.take());
+            let version = heading.trim().trim_start_matches('[').trim_end_matches(']');
+            // skips the unreleased section as well as anything already seen
+            if parse_version(version).map_or(false, |v| v > since) {
+                current = Some(Release {
+                    version,
+                    notes: Vec::new(),
+                });
+            }
+        } else if let (Some(release), Some(note)) = (&mut current, line.strip_prefix("- ")) {
+            release.notes.push(note);
+        }
+    }
+    releases.extend(current);
+
+    releases
+}
+
+fn parse_version(version: &str) -> Option<(u32, u32, u32)> {
+    let mut parts = version.split('.').map(|p| p.parse().ok());
+    Some((parts.next()??, parts.next()??, parts.next()??))
+}
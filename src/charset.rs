@@ -0,0 +1,48 @@
+use encoding_rs::{Encoding, UTF_8, WINDOWS_1252};
+use std::borrow::Cow;
+
+/// Bytes at the start of a page searched for a `<meta>` charset, which has to come early
+const META_SCAN_LENGTH: usize = 1024;
+
+/// Decodes a fetched page with the charset its `Content-Type` header or `<meta>` tags declare,
+/// telling UTF-8 from windows-1252 by itself when none is declared
+pub fn decode<'a>(bytes: &'a [u8], content_type: Option<&str>) -> Cow<'a, str> {
+    let declared = content_type
+        .and_then(header_charset)
+        .or_else(|| meta_charset(bytes))
+        .and_then(|label| Encoding::for_label(label.as_bytes()));
+    let encoding = match declared {
+        Some(encoding) => encoding,
+        None if std::str::from_utf8(bytes).is_ok() => UTF_8,
+        // latin-1 is decoded as its windows-1252 superset, like browsers do
+        None => WINDOWS_1252,
+    };
+    // a byte order mark wins over what's declared
+    let (text, _, _) = encoding.decode(bytes);
+    text
+}
+
+/// Charset parameter of a `Content-Type` header, e.g. `text/html; charset=ISO-8859-1`
+fn header_charset(content_type: &str) -> Option<String> {
+    content_type.split(';').skip(1).find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+        name.trim().eq_ignore_ascii_case("charset").then(|| {
+            value
+                .trim()
+                .trim_matches(|c| c == '"' || c == '\'')
+                .to_string()
+        })
+    })
+}
+
+/// Charset of a `<meta charset>` or `<meta http-equiv="Content-Type">` tag
+fn meta_charset(bytes: &[u8]) -> Option<String> {
+    let head = String::from_utf8_lossy(&bytes[..bytes.len().min(META_SCAN_LENGTH)]).to_lowercase();
+    let start = head.find("charset=")? + "charset=".len();
+    let label: String = head[start..]
+        .trim_start_matches(|c| c == '"' || c == '\'')
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':' | '.'))
+        .collect();
+    (!label.is_empty()).then_some(label)
+}
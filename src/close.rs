@@ -0,0 +1,162 @@
+use eframe::egui::Context;
+use eframe::Frame;
+use serde::{Deserialize, Serialize};
+#[cfg(any(windows, target_os = "macos"))]
+use std::time::Duration;
+
+/// Whether the window can be hidden to a tray icon on this platform, winit runs no GTK loop for
+/// one on Linux
+pub const TRAY_SUPPORTED: bool = cfg!(any(windows, target_os = "macos"));
+
+/// How often the tray icon's menu is checked while the window is hidden
+#[cfg(any(windows, target_os = "macos"))]
+const TRAY_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum CloseAction {
+    Quit,
+    Minimize,
+    /// Hides the window, a tray icon brings it back, minimizes where there's no tray
+    HideToTray,
+}
+
+impl CloseAction {
+    pub const ALL: [CloseAction; 3] = [Self::Quit, Self::Minimize, Self::HideToTray];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Quit => "Quit",
+            Self::Minimize => "Minimize",
+            Self::HideToTray => "Hide to the tray",
+        }
+    }
+}
+
+/// Applies the close action to the ❌ button and to the window's own close button
+#[derive(Default)]
+pub struct CloseHandler {
+    /// Set once the app is really quitting, the window may close then
+    quitting: bool,
+    /// Whether the window asked to close and the action is still to be applied
+    requested: bool,
+    #[cfg(any(windows, target_os = "macos"))]
+    tray: Option<tray::Tray>,
+}
+
+impl CloseHandler {
+    /// Whether the window may close, otherwise the action is applied on the next frame as it
+    /// needs the `Frame`
+    pub fn on_close_event(&mut self, action: CloseAction) -> bool {
+        if self.quitting || action == CloseAction::Quit {
+            return true;
+        }
+        self.requested = true;
+        false
+    }
+
+    pub fn close(&mut self, frame: &mut Frame, action: CloseAction) -> Result<(), String> {
+        match action {
+            CloseAction::Quit => self.quit(frame),
+            CloseAction::Minimize => frame.set_minimized(true),
+            #[cfg(any(windows, target_os = "macos"))]
+            CloseAction::HideToTray => {
+                if self.tray.is_none() {
+                    self.tray = Some(tray::Tray::new()?);
+                }
+                frame.set_visible(false);
+            }
+            #[cfg(not(any(windows, target_os = "macos")))]
+            CloseAction::HideToTray => frame.set_minimized(true),
+        }
+        Ok(())
+    }
+
+    /// Applies a close the window asked for, and the commands of the tray icon
+    pub fn update(
+        &mut self,
+        _ctx: &Context,
+        frame: &mut Frame,
+        action: CloseAction,
+    ) -> Result<(), String> {
+        if std::mem::take(&mut self.requested) {
+            self.close(frame, action)?;
+        }
+
+        #[cfg(any(windows, target_os = "macos"))]
+        if let Some(tray) = &self.tray {
+            _ctx.request_repaint_after(TRAY_POLL_INTERVAL);
+            match tray.poll() {
+                Some(tray::Command::Show) => {
+                    self.tray = None;
+                    frame.set_visible(true);
+                }
+                Some(tray::Command::Quit) => self.quit(frame),
+                None => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn quit(&mut self, frame: &mut Frame) {
+        self.quitting = true;
+        frame.close();
+    }
+}
+
+#[cfg(any(windows, target_os = "macos"))]
+mod tray {
+    use tray_icon::icon::Icon;
+    use tray_icon::menu::{Menu, MenuEvent, MenuItem};
+    use tray_icon::{TrayIcon, TrayIconBuilder};
+
+    const ICON_SIZE: u32 = 16;
+    /// The app's red, for the icon
+    const ICON_COLOR: [u8; 4] = [255, 0, 0, 255];
+
+    pub enum Command {
+        Show,
+        Quit,
+    }
+
+    /// Icon standing for the hidden window, removed when it's dropped
+    pub struct Tray {
+        _icon: TrayIcon,
+        show: u32,
+        quit: u32,
+    }
+
+    impl Tray {
+        pub fn new() -> Result<Self, String> {
+            let show = MenuItem::new("Show headlines", true, None);
+            let quit = MenuItem::new("Quit", true, None);
+            let menu = Menu::new();
+            menu.append_items(&[&show, &quit]);
+
+            let pixels = (ICON_SIZE * ICON_SIZE) as usize;
+            let rgba = ICON_COLOR.repeat(pixels);
+            let icon = Icon::from_rgba(rgba, ICON_SIZE, ICON_SIZE).map_err(|e| e.to_string())?;
+            let tray_icon = TrayIconBuilder::new()
+                .with_menu(Box::new(menu))
+                .with_tooltip("headlines")
+                .with_icon(icon)
+                .build()
+                .map_err(|e| e.to_string())?;
+            Ok(Self {
+                _icon: tray_icon,
+                show: show.id(),
+                quit: quit.id(),
+            })
+        }
+
+        pub fn poll(&self) -> Option<Command> {
+            let event = MenuEvent::receiver().try_recv().ok()?;
+            if event.id == self.show {
+                Some(Command::Show)
+            } else if event.id == self.quit {
+                Some(Command::Quit)
+            } else {
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use crate::news::NewsCardData;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Share of their title words two articles need in common to cover the same story
+const SIMILARITY_THRESHOLD: f32 = 0.4;
+/// Title words two articles need in common at least, short titles alike by chance aren't grouped
+const MIN_SHARED_WORDS: usize = 2;
+/// Words shorter than this say nothing about the story
+const MIN_WORD_LEN: usize = 3;
+/// Frequent words that would make unrelated titles look alike
+const STOPWORDS: &[&str] = &[
+    "after", "and", "are", "but", "for", "from", "has", "have", "into", "its", "new", "not", "off",
+    "out", "over", "says", "than", "that", "the", "this", "was", "were", "what", "who", "will",
+    "with",
+];
+
+/// Groups the articles covering the same story. Returns the article leading each story, in the
+/// order of the feed, and the others of each story under the url of its lead.
+pub fn group(
+    articles: Vec<NewsCardData>,
+) -> (Vec<NewsCardData>, BTreeMap<String, Vec<NewsCardData>>) {
+    let mut leads: Vec<(NewsCardData, BTreeSet<String>)> = Vec::new();
+    let mut related: BTreeMap<String, Vec<NewsCardData>> = BTreeMap::new();
+    for article in articles {
+        let words = title_words(&article.title);
+        let story = leads
+            .iter()
+            .find(|(_, lead_words)| similarity(&words, lead_words) >= SIMILARITY_THRESHOLD);
+        match story {
+            Some((lead, _)) => related.entry(lead.url.clone()).or_default().push(article),
+            None => leads.push((article, words)),
+        }
+    }
+    (leads.into_iter().map(|(lead, _)| lead).collect(), related)
+}
+
+/// Name of the publisher of the article, newsapi appends it to the titles after a dash, the host
+/// of the url otherwise
+pub fn publisher(article: &NewsCardData) -> String {
+    match article.title.rsplit_once(" - ") {
+        Some((_, name)) if !name.trim().is_empty() => name.trim().to_string(),
+        _ => article
+            .url
+            .split("://")
+            .nth(1)
+            .and_then(|rest| rest.split('/').next())
+            .map(|host| host.trim_start_matches("www.").to_string())
+            .unwrap_or_else(|| article.url.clone()),
+    }
+}
+
+/// Distinctive words of a title, without the publisher newsapi appends to it
+fn title_words(title: &str) -> BTreeSet<String> {
+    let title = title.rsplit_once(" - ").map_or(title, |(title, _)| title);
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|word| word.chars().count() >= MIN_WORD_LEN && !STOPWORDS.contains(&word.as_str()))
+        .collect()
+}
+
+/// Jaccard index of the two sets of words, 0 when they share too few
+fn similarity(a: &BTreeSet<String>, b: &BTreeSet<String>) -> f32 {
+    let shared = a.intersection(b).count();
+    if shared < MIN_SHARED_WORDS {
+        return 0.;
+    }
+    shared as f32 / a.union(b).count() as f32
+}
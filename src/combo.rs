@@ -0,0 +1,74 @@
+use eframe::egui::{ComboBox, Id, ScrollArea, TextEdit, Ui};
+use std::hash::Hash;
+
+const MAX_LIST_HEIGHT: f32 = 300.;
+
+/// Entry of a searchable combo box
+pub struct Entry {
+    pub label: String,
+    /// Other words the entry is found by, besides its label
+    pub keywords: String,
+    /// Header the entry is listed under, entries of a group must be next to each other
+    pub group: &'static str,
+}
+
+impl Entry {
+    fn matches(&self, query: &str) -> bool {
+        query.is_empty()
+            || self.label.to_lowercase().contains(query)
+            || self.keywords.to_lowercase().contains(query)
+    }
+}
+
+/// Combo box with a search field above its entries, which are listed by group
+///
+/// Returns the index of the entry picked this frame, if any.
+pub fn searchable(
+    ui: &mut Ui,
+    id_source: impl Hash,
+    entries: &[Entry],
+    selected: Option<usize>,
+) -> Option<usize> {
+    let id = Id::new(id_source);
+    let selected_text = selected.map_or("", |i| entries[i].label.as_str());
+    let mut picked = None;
+    ComboBox::from_id_source(id)
+        .selected_text(selected_text)
+        .show_ui(ui, |ui| {
+            let mut query = ui.data().get_temp::<String>(id).unwrap_or_default();
+            let search = ui.add(TextEdit::singleline(&mut query).hint_text("🔍 Search"));
+            search.request_focus();
+            let lower = query.to_lowercase();
+
+            ScrollArea::vertical()
+                .max_height(MAX_LIST_HEIGHT)
+                .show(ui, |ui| {
+                    let mut group = None;
+                    for (i, entry) in entries.iter().enumerate() {
+                        if !entry.matches(&lower) {
+                            continue;
+                        }
+                        if group != Some(entry.group) {
+                            group = Some(entry.group);
+                            ui.add_space(4.);
+                            ui.weak(entry.group);
+                        }
+                        if ui
+                            .selectable_label(selected == Some(i), &entry.label)
+                            .clicked()
+                        {
+                            picked = Some(i);
+                        }
+                    }
+                    if group.is_none() {
+                        ui.label("No matches");
+                    }
+                });
+
+            if picked.is_some() {
+                query.clear();
+            }
+            ui.data().insert_temp(id, query);
+        });
+    picked
+}
@@ -0,0 +1,253 @@
+use crate::accessibility;
+use crate::cards;
+use crate::combo;
+use crate::countries;
+use crate::news::NewsCardData;
+use crate::worker::Fetched;
+use eframe::egui::{Button, Key, ScrollArea, TextEdit, Ui};
+use newsapi::Country;
+use serde::{Deserialize, Serialize};
+use std::sync::mpsc::Receiver;
+
+/// Most columns the layout can be split into
+const MAX_COLUMNS: usize = 4;
+
+/// Search and country a column is bound to
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PaneQuery {
+    /// Shown above the column when set
+    #[serde(default)]
+    pub name: String,
+    pub query: String,
+    pub country: Country,
+}
+
+impl PaneQuery {
+    fn new(country: Country) -> Self {
+        Self {
+            name: String::new(),
+            query: String::new(),
+            country,
+        }
+    }
+}
+
+/// Columns of the split layout, kept between sessions
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct ComparisonSettings {
+    /// From left to right
+    pub columns: Vec<PaneQuery>,
+    pub sync_scroll: bool,
+}
+
+impl Default for ComparisonSettings {
+    fn default() -> Self {
+        Self {
+            columns: vec![PaneQuery::new(Country::FR), PaneQuery::new(Country::US)],
+            sync_scroll: false,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Pane {
+    articles: Vec<NewsCardData>,
+    /// Fetch under way, if any
+    rx: Option<Receiver<Fetched>>,
+    errors: Vec<String>,
+    /// Scroll offset on the last frame
+    offset: f32,
+    scroll_to: Option<f32>,
+}
+
+/// Feeds side by side in columns, each with its own search and country
+#[derive(Default)]
+pub struct Comparison {
+    /// As many as the settings have columns, resized when drawn
+    panes: Vec<Pane>,
+    /// Whether the layout editor is open
+    editing: bool,
+}
+
+impl Comparison {
+    pub fn is_empty(&self) -> bool {
+        self.panes
+            .iter()
+            .all(|pane| pane.articles.is_empty() && pane.rx.is_none())
+    }
+
+    /// Replaces the articles of the `i`th pane with those of a new fetch
+    pub fn start(&mut self, i: usize, rx: Receiver<Fetched>) {
+        if self.panes.len() <= i {
+            self.panes.resize_with(i + 1, Pane::default);
+        }
+        let pane = &mut self.panes[i];
+        pane.articles.clear();
+        pane.errors.clear();
+        pane.rx = Some(rx);
+        pane.scroll_to = Some(0.);
+    }
+
+    /// Collects what the fetches under way sent
+    pub fn poll(&mut self) {
+        for pane in &mut self.panes {
+            let mut done = false;
+            if let Some(rx) = &pane.rx {
+                for fetched in rx.try_iter() {
+                    match fetched {
+                        Fetched::Progress => {}
+                        Fetched::Articles(articles) => pane.articles.extend(articles),
+                        Fetched::Failed(error) => pane.errors.push(error.to_string()),
+                        Fetched::Done => done = true,
+                    }
+                }
+            }
+            if done {
+                pane.rx = None;
+            }
+        }
+    }
+
+    /// Draws the panes, returns those whose query should be fetched
+    pub fn show(
+        &mut self,
+        ui: &mut Ui,
+        settings: &mut ComparisonSettings,
+        desc_lines: usize,
+    ) -> Vec<usize> {
+        let mut fetch = Vec::new();
+        ui.horizontal(|ui| {
+            if ui.button("Fetch all").clicked() {
+                fetch = (0..settings.columns.len()).collect();
+            }
+            ui.checkbox(&mut settings.sync_scroll, "Sync scrolling");
+            ui.toggle_value(&mut self.editing, "✏ Edit layout");
+            if self.editing {
+                let can_add = settings.columns.len() < MAX_COLUMNS;
+                if ui.add_enabled(can_add, Button::new("➕ Column")).clicked() {
+                    let country = settings.columns.last().map_or(Country::US, |c| c.country);
+                    settings.columns.push(PaneQuery::new(country));
+                    fetch.push(settings.columns.len() - 1);
+                }
+            }
+        });
+        ui.separator();
+        if settings.columns.is_empty() {
+            settings.columns.push(PaneQuery::new(Country::US));
+        }
+        self.panes
+            .resize_with(settings.columns.len(), Pane::default);
+
+        let count = settings.columns.len();
+        let mut offsets = vec![0.; count];
+        let mut hovered = vec![false; count];
+        let mut moved = None;
+        let mut removed = None;
+        ui.columns(count, |columns| {
+            for (i, ui) in columns.iter_mut().enumerate() {
+                let query = &mut settings.columns[i];
+                let pane = &mut self.panes[i];
+                if self.editing {
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            TextEdit::singleline(&mut query.name)
+                                .hint_text("Column name")
+                                .desired_width(100.),
+                        );
+                        if ui.add_enabled(i > 0, Button::new("◀").small()).clicked() {
+                            moved = Some((i, i - 1));
+                        }
+                        if ui
+                            .add_enabled(i + 1 < count, Button::new("▶").small())
+                            .clicked()
+                        {
+                            moved = Some((i, i + 1));
+                        }
+                        if ui
+                            .add_enabled(count > 1, Button::new("✖").small())
+                            .clicked()
+                        {
+                            removed = Some(i);
+                        }
+                    });
+                } else if !query.name.trim().is_empty() {
+                    ui.strong(query.name.trim());
+                }
+                ui.horizontal(|ui| {
+                    let search = ui.add(
+                        TextEdit::singleline(&mut query.query)
+                            .hint_text("🔍 Search")
+                            .desired_width(120.),
+                    );
+                    let selected = countries::index(query.country);
+                    let picked = combo::searchable(
+                        ui,
+                        ("compare_country", i),
+                        &countries::entries(),
+                        Some(selected),
+                    );
+                    if let Some(picked) = picked.filter(|picked| *picked != selected) {
+                        query.country = countries::COUNTRIES[picked].country;
+                        fetch.push(i);
+                    }
+                    let entered = search.lost_focus() && ui.input().key_pressed(Key::Enter);
+                    if entered || ui.button("Fetch").clicked() {
+                        fetch.push(i);
+                    }
+                    if pane.rx.is_some() {
+                        accessibility::spinner(ui, None);
+                    }
+                });
+                for error in &pane.errors {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+
+                let mut area = ScrollArea::vertical().id_source(("compare", i));
+                if let Some(offset) = pane.scroll_to.take() {
+                    area = area.vertical_scroll_offset(offset);
+                }
+                let output = area.show(ui, |ui| {
+                    if pane.articles.is_empty() && pane.rx.is_none() {
+                        ui.weak("No articles");
+                    }
+                    cards::show_list(ui, &pane.articles, desc_lines);
+                });
+                offsets[i] = output.state.offset.y;
+                hovered[i] = ui.rect_contains_pointer(output.inner_rect);
+            }
+        });
+
+        if settings.sync_scroll {
+            // the pane scrolled under the pointer leads, the others follow on the next frame
+            let leader =
+                (0..count).find(|&i| hovered[i] && (offsets[i] - self.panes[i].offset).abs() > 0.5);
+            if let Some(leader) = leader {
+                for (i, pane) in self.panes.iter_mut().enumerate() {
+                    if i != leader {
+                        pane.scroll_to = Some(offsets[leader]);
+                    }
+                }
+                ui.ctx().request_repaint();
+            }
+        }
+        for (pane, offset) in self.panes.iter_mut().zip(offsets) {
+            pane.offset = offset;
+        }
+
+        // the fetches under way follow their columns
+        if let Some((from, to)) = moved {
+            settings.columns.swap(from, to);
+            self.panes.swap(from, to);
+            fetch.clear();
+        }
+        if let Some(i) = removed {
+            settings.columns.remove(i);
+            self.panes.remove(i);
+            fetch.clear();
+        }
+
+        fetch.dedup();
+        fetch
+    }
+}
@@ -0,0 +1,59 @@
+#[cfg(feature = "gui")]
+use crate::combo::Entry;
+use newsapi::Country;
+
+pub struct CountryInfo {
+    pub country: Country,
+    /// ISO 3166-1 alpha-2 code
+    pub code: &'static str,
+    pub flag: &'static str,
+    pub name: &'static str,
+    /// Name in the country's own language
+    pub native_name: &'static str,
+    pub region: &'static str,
+}
+
+/// Countries newsapi has headlines for, sorted by region then name
+pub const COUNTRIES: [CountryInfo; 2] = [
+    CountryInfo {
+        country: Country::US,
+        code: "US",
+        flag: "🇺🇸",
+        name: "United States",
+        native_name: "United States",
+        region: "Americas",
+    },
+    CountryInfo {
+        country: Country::FR,
+        code: "FR",
+        flag: "🇫🇷",
+        name: "France",
+        native_name: "France",
+        region: "Europe",
+    },
+];
+
+/// Index of the country in `COUNTRIES`
+pub fn index(country: Country) -> usize {
+    match country {
+        Country::US => 0,
+        Country::FR => 1,
+    }
+}
+
+pub fn by_code(code: &str) -> Option<&'static CountryInfo> {
+    COUNTRIES.iter().find(|c| c.code == code)
+}
+
+/// Entries of the country combo box, found by name, native name or code
+#[cfg(feature = "gui")]
+pub fn entries() -> Vec<Entry> {
+    COUNTRIES
+        .iter()
+        .map(|c| Entry {
+            label: format!("{} {}", c.flag, c.name),
+            keywords: format!("{} {}", c.native_name, c.code),
+            group: c.region,
+        })
+        .collect()
+}
@@ -0,0 +1,321 @@
+use crate::bookmarks::Bookmarks;
+use crate::news::NewsCardData;
+use crate::weather::{self, fetch_weather, WeatherCache, WeatherState, WEATHER_REFRESH};
+use eframe::egui::{
+    pos2, Align, CursorIcon, DragValue, Frame, Hyperlink, Label, Layout, ScrollArea, Sense, Shape,
+    Stroke, TextEdit, Ui,
+};
+use serde::{Deserialize, Serialize};
+
+/// Articles listed by the saved search and bookmarks widgets
+const LISTED_ARTICLES: usize = 5;
+const SPARKLINE_HEIGHT: f32 = 40.;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub enum Widget {
+    TopStories {
+        count: usize,
+    },
+    SavedSearch {
+        query: String,
+    },
+    Bookmarks,
+    /// Articles fetched per day over the last week
+    Sparkline,
+    Weather {
+        latitude: f64,
+        longitude: f64,
+    },
+}
+
+impl Widget {
+    /// One of each kind, as offered by the add menu
+    fn all() -> [Widget; 5] {
+        [
+            Widget::TopStories { count: 5 },
+            Widget::SavedSearch {
+                query: String::new(),
+            },
+            Widget::Bookmarks,
+            Widget::Sparkline,
+            Widget::Weather {
+                latitude: 48.85,
+                longitude: 2.35,
+            },
+        ]
+    }
+
+    fn title(&self) -> &'static str {
+        match self {
+            Widget::TopStories { .. } => "📰 Top stories",
+            Widget::SavedSearch { .. } => "🔍 Saved search",
+            Widget::Bookmarks => "★ Bookmarks",
+            Widget::Sparkline => "📈 Articles this week",
+            Widget::Weather { .. } => "🌦 Weather",
+        }
+    }
+}
+
+/// Widgets of the home dashboard, in the order they're shown
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct DashboardSettings {
+    /// Whether the dashboard is the home instead of the feed
+    pub enabled: bool,
+    pub widgets: Vec<Widget>,
+}
+
+impl Default for DashboardSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            widgets: vec![
+                Widget::TopStories { count: 5 },
+                Widget::Bookmarks,
+                Widget::Sparkline,
+            ],
+        }
+    }
+}
+
+/// What the widgets show
+pub struct DashboardData<'a> {
+    pub articles: &'a [NewsCardData],
+    pub bookmarks: &'a Bookmarks,
+    /// Articles fetched per day, the oldest day first
+    pub daily_counts: Vec<usize>,
+}
+
+/// Home made of widgets, rearranged by dragging their handle while editing
+#[derive(Default)]
+pub struct Dashboard {
+    editing: bool,
+    /// Index of the widget being dragged
+    dragging: Option<usize>,
+    weather: WeatherCache,
+}
+
+impl Dashboard {
+    /// Draws the widgets, returns true when the full feed is asked for
+    pub fn show(
+        &mut self,
+        ui: &mut Ui,
+        settings: &mut DashboardSettings,
+        data: &DashboardData,
+    ) -> bool {
+        let mut show_feed = false;
+        ui.horizontal(|ui| {
+            ui.toggle_value(&mut self.editing, "✏ Edit");
+            if self.editing {
+                ui.menu_button("➕ Add widget", |ui| {
+                    for widget in Widget::all() {
+                        if ui.button(widget.title()).clicked() {
+                            settings.widgets.push(widget);
+                            ui.close_menu();
+                        }
+                    }
+                });
+            }
+        });
+        ui.add_space(4.);
+
+        let mut removed = None;
+        let mut rects = Vec::new();
+        let output = ScrollArea::vertical().show(ui, |ui| {
+            for (i, widget) in settings.widgets.iter_mut().enumerate() {
+                let frame = Frame::group(ui.style()).show(ui, |ui| {
+                    ui.set_width(ui.available_width());
+                    ui.push_id(i, |ui| {
+                        ui.horizontal(|ui| {
+                            if self.editing {
+                                let handle = ui
+                                    .add(Label::new("☰").sense(Sense::drag()))
+                                    .on_hover_cursor(CursorIcon::Grab);
+                                if handle.drag_started() {
+                                    self.dragging = Some(i);
+                                }
+                            }
+                            ui.strong(widget.title());
+                            if self.editing {
+                                ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                                    if ui.small_button("✖").on_hover_text("Remove").clicked() {
+                                        removed = Some(i);
+                                    }
+                                });
+                            }
+                        });
+                        show_feed |= self.show_widget(ui, widget, data);
+                    });
+                });
+                rects.push(frame.response.rect);
+            }
+            if settings.widgets.is_empty() {
+                ui.weak("No widgets, add some with ✏ Edit");
+            }
+        });
+
+        if let Some(from) = self.dragging {
+            let pointer = ui.input().pointer.hover_pos();
+            // the widget is dropped before the first one whose middle is below the pointer
+            let to = pointer.map(|pointer| {
+                rects
+                    .iter()
+                    .take_while(|rect| rect.center().y < pointer.y)
+                    .count()
+            });
+            if let (Some(to), Some(last)) = (to, rects.last()) {
+                let y = rects
+                    .get(to)
+                    .map_or(last.bottom() + 2., |rect| rect.top() - 2.);
+                let stroke = Stroke::new(2., ui.visuals().selection.bg_fill);
+                ui.painter()
+                    .with_clip_rect(output.inner_rect)
+                    .hline(last.x_range(), y, stroke);
+            }
+            if !ui.input().pointer.any_down() {
+                if let Some(to) = to {
+                    let widget = settings.widgets.remove(from);
+                    let to = if to > from { to - 1 } else { to };
+                    settings.widgets.insert(to, widget);
+                }
+                self.dragging = None;
+            }
+        }
+        if let Some(i) = removed {
+            settings.widgets.remove(i);
+        }
+        show_feed
+    }
+
+    /// Draws a widget's content, returns true when the full feed is asked for
+    fn show_widget(&mut self, ui: &mut Ui, widget: &mut Widget, data: &DashboardData) -> bool {
+        match widget {
+            Widget::TopStories { count } => {
+                if self.editing {
+                    ui.horizontal(|ui| {
+                        ui.label("Stories");
+                        ui.add(DragValue::new(count).clamp_range(1..=20));
+                    });
+                }
+                article_links(ui, data.articles.iter().take(*count));
+                return ui.link("See all").clicked();
+            }
+            Widget::SavedSearch { query } => {
+                ui.add(TextEdit::singleline(query).hint_text("🔍 Search the feed"));
+                let lower = query.to_lowercase();
+                if !lower.is_empty() {
+                    let matching = data.articles.iter().filter(|a| {
+                        a.title.to_lowercase().contains(&lower)
+                            || a.desc.to_lowercase().contains(&lower)
+                    });
+                    article_links(ui, matching.take(LISTED_ARTICLES));
+                }
+            }
+            Widget::Bookmarks => {
+                let bookmarked = data.bookmarks.iter().map(|b| &b.article);
+                article_links(ui, bookmarked.take(LISTED_ARTICLES));
+            }
+            Widget::Sparkline => {
+                let total: usize = data.daily_counts.iter().sum();
+                sparkline(ui, &data.daily_counts);
+                ui.weak(format!("{} articles over 7 days", total));
+            }
+            Widget::Weather {
+                latitude,
+                longitude,
+            } => {
+                if self.editing {
+                    ui.horizontal(|ui| {
+                        ui.label("Latitude");
+                        ui.add(
+                            DragValue::new(latitude)
+                                .clamp_range(-90.0..=90.0)
+                                .speed(0.1),
+                        );
+                        ui.label("Longitude");
+                        ui.add(
+                            DragValue::new(longitude)
+                                .clamp_range(-180.0..=180.0)
+                                .speed(0.1),
+                        );
+                    });
+                }
+                self.show_weather(ui, *latitude, *longitude);
+            }
+        }
+        false
+    }
+
+    fn show_weather(&mut self, ui: &mut Ui, latitude: f64, longitude: f64) {
+        let key = format!("{:.2},{:.2}", latitude, longitude);
+        let now = ui.input().time;
+        let mut cache = self.weather.lock().unwrap();
+        let stale = cache
+            .get(&key)
+            .map_or(true, |(fetched_at, _)| now - fetched_at > WEATHER_REFRESH);
+        if stale {
+            cache.insert(key.clone(), (now, WeatherState::Loading));
+            fetch_weather(
+                &key,
+                latitude,
+                longitude,
+                self.weather.clone(),
+                ui.ctx().clone(),
+            );
+        }
+        match cache.get(&key).map(|(_, state)| state) {
+            Some(WeatherState::Loaded(weather)) => {
+                let (icon, description) = weather::describe(weather.code);
+                ui.label(format!(
+                    "{} {:.0} °C, {}, wind {:.0} km/h",
+                    icon, weather.temperature, description, weather.windspeed
+                ));
+            }
+            Some(WeatherState::Failed(e)) => {
+                ui.colored_label(ui.visuals().error_fg_color, e);
+            }
+            _ => {
+                ui.weak("Loading…");
+            }
+        }
+        // the link the forecast comes from, as Open-Meteo asks
+        ui.add(Hyperlink::from_label_and_url(
+            "Open-Meteo",
+            "https://open-meteo.com",
+        ));
+    }
+}
+
+fn article_links<'a>(ui: &mut Ui, articles: impl Iterator<Item = &'a NewsCardData>) {
+    let mut any = false;
+    for article in articles {
+        any = true;
+        ui.add(Hyperlink::from_label_and_url(&article.title, &article.url));
+    }
+    if !any {
+        ui.weak("Nothing yet");
+    }
+}
+
+/// Line of the values over the available width
+fn sparkline(ui: &mut Ui, values: &[usize]) {
+    let (rect, _) = ui.allocate_exact_size(
+        [ui.available_width(), SPARKLINE_HEIGHT].into(),
+        Sense::hover(),
+    );
+    let max = values.iter().copied().max().unwrap_or(0).max(1) as f32;
+    let step = rect.width() / (values.len().max(2) - 1) as f32;
+    let points = values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| {
+            let x = rect.left() + step * i as f32;
+            let y = rect.bottom() - rect.height() * *value as f32 / max;
+            pos2(x, y)
+        })
+        .collect();
+    let stroke = Stroke::new(1.5, ui.visuals().hyperlink_color);
+    ui.painter().add(Shape::line(points, stroke));
+    let baseline = ui.visuals().widgets.noninteractive.bg_stroke;
+    ui.painter().hline(rect.x_range(), rect.bottom(), baseline);
+}
@@ -0,0 +1,72 @@
+use crate::categories::Category;
+use crate::countries;
+use eframe::wasm_bindgen::JsValue;
+use newsapi::Country;
+use web_sys::UrlSearchParams;
+
+/// Part of the view kept in the page's query string, like `?country=us&category=technology&q=rust`,
+/// so that it can be bookmarked and shared
+#[derive(Clone, Default, PartialEq)]
+pub struct View {
+    /// Lowercase ISO code of the feed's country
+    pub country: String,
+    pub category: Option<Category>,
+    pub query: String,
+}
+
+impl View {
+    /// View of the page's url, what it doesn't say is left empty
+    pub fn current() -> Self {
+        let search = web_sys::window()
+            .and_then(|w| w.location().search().ok())
+            .unwrap_or_default();
+        let params = match UrlSearchParams::new_with_str(&search) {
+            Ok(params) => params,
+            Err(e) => {
+                tracing::error!("Failed reading the page's query string: {:?}", e);
+                return Self::default();
+            }
+        };
+        Self {
+            country: params.get("country").unwrap_or_default().to_lowercase(),
+            category: params
+                .get("category")
+                .and_then(|name| Category::from_api_name(&name.to_lowercase())),
+            query: params.get("q").unwrap_or_default(),
+        }
+    }
+
+    pub fn country(&self) -> Option<Country> {
+        countries::by_code(&self.country.to_uppercase()).map(|info| info.country)
+    }
+
+    /// Puts the view in the page's url, replacing the history entry so that Back still leaves
+    pub fn show(&self) -> Result<(), String> {
+        let js_error = |e: JsValue| format!("{:?}", e);
+
+        let params = UrlSearchParams::new().map_err(js_error)?;
+        if !self.country.is_empty() {
+            params.append("country", &self.country);
+        }
+        if let Some(category) = self.category {
+            params.append("category", category.api_name());
+        }
+        if !self.query.is_empty() {
+            params.append("q", &self.query);
+        }
+        let search = String::from(params.to_string());
+
+        let window = web_sys::window().ok_or("No window to show the url in")?;
+        let path = window.location().pathname().map_err(js_error)?;
+        let url = if search.is_empty() {
+            path
+        } else {
+            format!("{}?{}", path, search)
+        };
+        window
+            .history()
+            .map_err(js_error)?
+            .replace_state_with_url(&JsValue::NULL, "", Some(&url))
+            .map_err(js_error)
+    }
+}
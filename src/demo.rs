@@ -0,0 +1,75 @@
+use crate::news::NewsCardData;
+use chrono::Utc;
+use eframe::egui::{Align2, Color32, Context, FontId, Id, LayerId, Order, RichText, Ui};
+use serde::Deserialize;
+
+const ARTICLES: &str = include_str!("../demo_articles.json");
+const WATERMARK: &str = "DEMO";
+
+/// Article of the bundled sample, made up
+#[derive(Deserialize)]
+struct SampleArticle {
+    title: String,
+    desc: String,
+    url: String,
+    source: String,
+    country: String,
+    language: String,
+}
+
+/// Sample articles for trying the app without an API key
+pub fn articles() -> Vec<NewsCardData> {
+    let sample: Vec<SampleArticle> = match serde_json::from_str(ARTICLES) {
+        Ok(sample) => sample,
+        Err(e) => {
+            tracing::error!("Failed reading the sample articles: {}", e);
+            return Vec::new();
+        }
+    };
+    let fetched_at = Utc::now();
+    sample
+        .into_iter()
+        .map(|a| NewsCardData {
+            title: a.title,
+            desc: a.desc,
+            url: a.url,
+            source: a.source,
+            fetched_at,
+            country: a.country,
+            language: a.language,
+            discussion: None,
+            thumbnail: None,
+            author: None,
+            published_at: None,
+        })
+        .collect()
+}
+
+/// Tells the articles are samples, returns true when the user asks to enter an API key instead
+pub fn show_banner(ui: &mut Ui) -> bool {
+    let mut leave = false;
+    ui.horizontal(|ui| {
+        ui.label(
+            RichText::new("Demo mode")
+                .strong()
+                .color(ui.visuals().warn_fg_color),
+        );
+        ui.label("These articles are made up samples, not real news.");
+        leave = ui.button("Enter an API key").clicked();
+    });
+    leave
+}
+
+/// Faint word across the window, over everything else without catching clicks
+pub fn paint_watermark(ctx: &Context) {
+    let painter = ctx.layer_painter(LayerId::new(Order::Foreground, Id::new("demo_watermark")));
+    let rect = ctx.screen_rect();
+    let color = Color32::from_rgba_unmultiplied(128, 128, 128, 40);
+    painter.text(
+        rect.center(),
+        Align2::CENTER_CENTER,
+        WATERMARK,
+        FontId::proportional(rect.width().min(rect.height()) / 4.),
+        color,
+    );
+}
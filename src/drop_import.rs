@@ -0,0 +1,111 @@
+use crate::feeds;
+use eframe::egui::{
+    Align2, Color32, Context, FontId, Id, Key, Label, LayerId, Order, RichText, Window,
+};
+
+/// File dropped on the window, waiting for the user to confirm importing it
+pub enum Dropped {
+    /// Settings exported by this app
+    Settings { name: String, contents: Vec<u8> },
+    /// Feeds of an OPML subscription list
+    Feeds { name: String, urls: Vec<String> },
+}
+
+impl Dropped {
+    /// Asks whether to import the file, returns the answer once there's one
+    pub fn confirm(&self, ctx: &Context, profile: &str) -> Option<bool> {
+        let (name, question) = match self {
+            Dropped::Settings { name, .. } => (
+                name,
+                format!(
+                    "Replace the settings and bookmarks of the {} profile with those of the file?",
+                    profile
+                ),
+            ),
+            Dropped::Feeds { name, urls } => (
+                name,
+                format!(
+                    "Add the {} feeds of the file to the feeds source?",
+                    urls.len()
+                ),
+            ),
+        };
+        let mut confirmed = None;
+        Window::new("Import file?")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, [0., 0.])
+            .show(ctx, |ui| {
+                ui.add(Label::new(RichText::new(name).monospace()).wrap(true));
+                ui.add(Label::new(question).wrap(true));
+                ui.horizontal(|ui| {
+                    if ui.button("Import").clicked() {
+                        confirmed = Some(true);
+                    }
+                    if ui.button("Cancel").clicked() || ui.input().key_pressed(Key::Escape) {
+                        confirmed = Some(false);
+                    }
+                });
+            });
+        confirmed
+    }
+}
+
+/// Reads the file dropped on the window this frame, if any, the others dropped with it are ignored
+pub fn take(ctx: &Context) -> Option<Result<Dropped, String>> {
+    let file = ctx.input().raw.dropped_files.first().cloned()?;
+    let name = match (&file.path, file.name.is_empty()) {
+        (Some(path), true) => path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        _ => file.name.clone(),
+    };
+    let contents = match (file.bytes, &file.path) {
+        (Some(bytes), _) => bytes.to_vec(),
+        #[cfg(not(target_arch = "wasm32"))]
+        (None, Some(path)) => match std::fs::read(path) {
+            Ok(contents) => contents,
+            Err(e) => return Some(Err(format!("{}: {}", name, e))),
+        },
+        _ => return Some(Err(format!("{}: no contents", name))),
+    };
+    Some(read(name, contents))
+}
+
+/// Tells settings from subscription lists by their extension
+fn read(name: String, contents: Vec<u8>) -> Result<Dropped, String> {
+    let extension = name
+        .rsplit_once('.')
+        .map(|(_, extension)| extension.to_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "json" => Ok(Dropped::Settings { name, contents }),
+        "opml" | "xml" => match feeds::opml_urls(&contents) {
+            Ok(urls) if urls.is_empty() => Err(format!("{}: no feeds in it", name)),
+            Ok(urls) => Ok(Dropped::Feeds { name, urls }),
+            Err(e) => Err(format!("{}: {}", name, e)),
+        },
+        _ => Err(format!(
+            "{}: only settings (.json) and OPML (.opml) files can be imported",
+            name
+        )),
+    }
+}
+
+/// Dims the window while files are dragged over it, telling what dropping them does
+pub fn paint_hover(ctx: &Context) {
+    if ctx.input().raw.hovered_files.is_empty() {
+        return;
+    }
+    let painter = ctx.layer_painter(LayerId::new(Order::Foreground, Id::new("drop_import")));
+    let rect = ctx.screen_rect();
+    painter.rect_filled(rect, 0., Color32::from_black_alpha(192));
+    painter.text(
+        rect.center(),
+        Align2::CENTER_CENTER,
+        "Drop a settings or OPML file to import it",
+        FontId::proportional(20.),
+        Color32::WHITE,
+    );
+}
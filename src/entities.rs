@@ -0,0 +1,247 @@
+use crate::news::NewsCardData;
+use eframe::egui::{Hyperlink, RichText, ScrollArea, Ui};
+use serde::{Deserialize, Serialize};
+
+/// Words that make a name an organization's
+const ORGANIZATION_WORDS: [&str; 24] = [
+    "Inc",
+    "Corp",
+    "Ltd",
+    "Co",
+    "Group",
+    "Party",
+    "Ministry",
+    "University",
+    "Bank",
+    "Council",
+    "Court",
+    "Company",
+    "Association",
+    "Agency",
+    "Department",
+    "Parliament",
+    "Senate",
+    "Congress",
+    "Commission",
+    "Union",
+    "Institute",
+    "Foundation",
+    "Club",
+    "FC",
+];
+
+/// Titles introducing a person's name, left out of it
+const PERSON_TITLES: [&str; 14] = [
+    "Mr",
+    "Mrs",
+    "Ms",
+    "Dr",
+    "President",
+    "Senator",
+    "Sen",
+    "Gov",
+    "Governor",
+    "Minister",
+    "Chancellor",
+    "King",
+    "Queen",
+    "Pope",
+];
+
+/// Places recognized on their own, other ones are found after "in", "at" or "from"
+const PLACES: [&str; 32] = [
+    "Africa",
+    "America",
+    "Asia",
+    "Australia",
+    "Beijing",
+    "Berlin",
+    "Brazil",
+    "Brussels",
+    "California",
+    "Canada",
+    "China",
+    "Europe",
+    "France",
+    "Gaza",
+    "Germany",
+    "India",
+    "Iran",
+    "Israel",
+    "Italy",
+    "Japan",
+    "London",
+    "Mexico",
+    "Moscow",
+    "New York",
+    "Paris",
+    "Russia",
+    "Spain",
+    "Texas",
+    "Ukraine",
+    "United Kingdom",
+    "United States",
+    "Washington",
+];
+
+/// Capitalized words starting a sentence that aren't part of a name
+const SENTENCE_STARTS: [&str; 20] = [
+    "A", "An", "The", "In", "On", "At", "As", "After", "Before", "How", "Why", "What", "When",
+    "Who", "This", "That", "Is", "Are", "Le", "La",
+];
+
+/// Lowercase words a name may contain, like "Bank of England"
+const CONNECTORS: [&str; 4] = ["of", "de", "du", "des"];
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum EntityKind {
+    Person,
+    Organization,
+    Place,
+}
+
+impl EntityKind {
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Self::Person => "👤",
+            Self::Organization => "🏢",
+            Self::Place => "📍",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct Entity {
+    pub name: String,
+    pub kind: EntityKind,
+}
+
+/// People, organizations and places named in `text`, found from capitalization and word lists
+/// rather than a language model, so expect misses and oddities
+pub fn extract(text: &str) -> Vec<Entity> {
+    let mut entities: Vec<Entity> = Vec::new();
+    let mut run: Vec<&str> = Vec::new();
+    let mut run_starts_sentence = false;
+    let mut sentence_start = true;
+    let mut previous = "";
+
+    // the empty token at the end closes the last name
+    for token in text.split_whitespace().chain([""]) {
+        let word = token.trim_matches(|c: char| !c.is_alphanumeric());
+        let word = word
+            .strip_suffix("'s")
+            .or_else(|| word.strip_suffix("’s"))
+            .unwrap_or(word);
+        let capitalized = word.chars().next().map_or(false, char::is_uppercase);
+        let connects = !run.is_empty() && CONNECTORS.contains(&word);
+
+        if capitalized || connects {
+            if run.is_empty() {
+                run_starts_sentence = sentence_start;
+            }
+            run.push(word);
+        }
+        // punctuation after a word, or a lowercase word, ends the name
+        let ends = !(capitalized || connects) || token.ends_with(|c: char| !c.is_alphanumeric());
+        if ends && !run.is_empty() {
+            let before = if run_starts_sentence { "" } else { previous };
+            if let Some(entity) = classify(&run, run_starts_sentence, before) {
+                if !entities.iter().any(|e| e.name == entity.name) {
+                    entities.push(entity);
+                }
+            }
+            run.clear();
+        }
+        if run.is_empty() {
+            previous = word;
+        }
+        sentence_start = token.ends_with(['.', '!', '?', ':']);
+    }
+    entities
+}
+
+/// Entity named by a run of capitalized words, if it looks like one. `before` is the word
+/// preceding the run.
+fn classify(run: &[&str], starts_sentence: bool, before: &str) -> Option<Entity> {
+    let mut words = run;
+    while let [.., last] = words {
+        if CONNECTORS.contains(last) {
+            words = &words[..words.len() - 1];
+        } else {
+            break;
+        }
+    }
+    if starts_sentence && words.first().map_or(false, |w| SENTENCE_STARTS.contains(w)) {
+        words = &words[1..];
+    }
+    if words.is_empty() {
+        return None;
+    }
+
+    let entity = |words: &[&str], kind| {
+        Some(Entity {
+            name: words.join(" "),
+            kind,
+        })
+    };
+    let name = words.join(" ");
+    if words.iter().any(|w| ORGANIZATION_WORDS.contains(w)) {
+        return entity(words, EntityKind::Organization);
+    }
+    if PLACES.contains(&name.as_str()) || ["in", "at", "from"].contains(&before) {
+        return entity(words, EntityKind::Place);
+    }
+    if PERSON_TITLES.contains(&words[0]) && words.len() > 1 {
+        return entity(&words[1..], EntityKind::Person);
+    }
+    let acronym = words.len() == 1
+        && (2..=6).contains(&name.len())
+        && name.chars().all(|c| c.is_ascii_uppercase());
+    if acronym {
+        return entity(words, EntityKind::Organization);
+    }
+    // lone capitalized words are too often something else, longer runs are title case
+    if (2..=3).contains(&words.len()) {
+        return entity(words, EntityKind::Person);
+    }
+    None
+}
+
+/// Whether the article's title or description names the entity
+pub fn mentions(article: &NewsCardData, name: &str) -> bool {
+    article.title.contains(name) || article.desc.contains(name)
+}
+
+/// Page of an entity listing the articles naming it, returns true when it's closed
+pub fn show_page<'a>(
+    ui: &mut Ui,
+    entity: &Entity,
+    articles: impl Iterator<Item = &'a NewsCardData>,
+    followed: &mut bool,
+) -> bool {
+    let mut closed = false;
+    ui.horizontal(|ui| {
+        closed = ui.button("⬅ Back").clicked();
+        ui.toggle_value(followed, "🔔 Follow")
+            .on_hover_text("Queue new articles naming it for review");
+    });
+    ui.heading(format!("{} {}", entity.kind.icon(), entity.name));
+    ui.separator();
+
+    ScrollArea::vertical().show(ui, |ui| {
+        let mut any = false;
+        for article in articles {
+            any = true;
+            ui.add(Hyperlink::from_label_and_url(
+                RichText::new(&article.title).strong(),
+                &article.url,
+            ));
+            ui.weak(&article.source);
+            ui.add_space(4.);
+        }
+        if !any {
+            ui.weak("No article names it anymore");
+        }
+    });
+    closed
+}
@@ -0,0 +1,115 @@
+use crate::errors::HeadlinesError;
+use chrono::{DateTime, Local};
+use eframe::egui::{self, vec2, Align2, Area, Context, Grid, ScrollArea, Ui};
+use std::time::Duration;
+
+/// Seconds an error stays on screen as a toast
+const TOAST_DURATION: f64 = 6.;
+
+pub struct LoggedError {
+    pub error: HeadlinesError,
+    pub at: DateTime<Local>,
+}
+
+/// What a toast shows
+enum Toast {
+    /// Index of the error in the log's entries
+    Error(usize),
+    /// Something done in the background, it isn't logged
+    Notice(String),
+}
+
+/// Errors of the session, the recent ones are also shown as toasts along with notices
+#[derive(Default)]
+pub struct ErrorLog {
+    entries: Vec<LoggedError>,
+    /// Toasts and when they expire
+    toasts: Vec<(Toast, f64)>,
+}
+
+impl ErrorLog {
+    pub fn push(&mut self, error: HeadlinesError, now: f64) {
+        tracing::error!("{}", error);
+        self.toasts
+            .push((Toast::Error(self.entries.len()), now + TOAST_DURATION));
+        self.entries.push(LoggedError {
+            error,
+            at: Local::now(),
+        });
+    }
+
+    /// Tells something went well, in a toast of its own
+    pub fn notify(&mut self, message: String, now: f64) {
+        tracing::info!("{}", message);
+        self.toasts
+            .push((Toast::Notice(message), now + TOAST_DURATION));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Shows the recent errors and notices in the bottom right corner until they expire or are
+    /// dismissed
+    pub fn show_toasts(&mut self, ctx: &Context) {
+        let now = ctx.input().time;
+        self.toasts.retain(|(_, until)| *until > now);
+        if let Some(next) = self.toasts.iter().map(|(_, until)| *until).reduce(f64::min) {
+            ctx.request_repaint_after(Duration::from_secs_f64(next - now));
+        }
+
+        let mut dismissed = None;
+        Area::new("error_toasts")
+            .anchor(Align2::RIGHT_BOTTOM, vec2(-10., -10.))
+            .show(ctx, |ui| {
+                for (n, (toast, _)) in self.toasts.iter().enumerate() {
+                    egui::Frame::popup(ui.style()).show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            match toast {
+                                Toast::Error(i) => {
+                                    ui.colored_label(ui.visuals().error_fg_color, "⚠");
+                                    ui.label(self.entries[*i].error.to_string());
+                                }
+                                Toast::Notice(message) => {
+                                    ui.label("✔");
+                                    ui.label(message);
+                                }
+                            }
+                            if ui.small_button("✖").clicked() {
+                                dismissed = Some(n);
+                            }
+                        });
+                    });
+                }
+            });
+        if let Some(n) = dismissed {
+            self.toasts.remove(n);
+        }
+    }
+
+    pub fn show_log(&mut self, ui: &mut Ui) {
+        if self.entries.is_empty() {
+            ui.label("No errors so far");
+            return;
+        }
+        if ui.button("Clear").clicked() {
+            self.entries.clear();
+            self.toasts.clear();
+            return;
+        }
+        ScrollArea::vertical().show(ui, |ui| {
+            Grid::new("error_log").striped(true).show(ui, |ui| {
+                for entry in self.entries.iter().rev() {
+                    ui.label(entry.at.format("%H:%M:%S").to_string());
+                    ui.strong(entry.error.kind());
+                    ui.label(entry.error.message());
+                    ui.end_row();
+                }
+            });
+        });
+    }
+}
@@ -0,0 +1,83 @@
+use std::fmt::{self, Display};
+
+#[derive(Clone, Debug)]
+pub enum HeadlinesError {
+    Network(String),
+    /// A request that got no answer in time
+    Timeout(String),
+    Auth(String),
+    RateLimited(String),
+    Parse(String),
+    Storage(String),
+    /// A part of the app that failed unexpectedly
+    Internal(String),
+}
+
+impl HeadlinesError {
+    /// Classifies a failed fetch, newsapi only describes its errors as text
+    pub fn from_fetch(error: impl Display) -> Self {
+        let message = error.to_string();
+        let lower = message.to_lowercase();
+        if lower.contains("api key") || lower.contains("apikey") || lower.contains("401") {
+            HeadlinesError::Auth(message)
+        } else if lower.contains("ratelimit")
+            || lower.contains("rate limit")
+            || lower.contains("too many requests")
+            || lower.contains("429")
+        {
+            HeadlinesError::RateLimited(message)
+        } else if lower.contains("pars") {
+            HeadlinesError::Parse(message)
+        } else {
+            HeadlinesError::Network(message)
+        }
+    }
+
+    /// Whether trying again later may succeed
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            HeadlinesError::Network(_) | HeadlinesError::Timeout(_)
+        )
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            HeadlinesError::Network(_) => "Network",
+            HeadlinesError::Timeout(_) => "Timeout",
+            HeadlinesError::Auth(_) => "API key",
+            HeadlinesError::RateLimited(_) => "Rate limited",
+            HeadlinesError::Parse(_) => "Parse",
+            HeadlinesError::Storage(_) => "Storage",
+            HeadlinesError::Internal(_) => "Internal",
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            HeadlinesError::Network(message)
+            | HeadlinesError::Timeout(message)
+            | HeadlinesError::Auth(message)
+            | HeadlinesError::RateLimited(message)
+            | HeadlinesError::Parse(message)
+            | HeadlinesError::Storage(message)
+            | HeadlinesError::Internal(message) => message,
+        }
+    }
+}
+
+impl Display for HeadlinesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HeadlinesError::Network(message) => write!(f, "Network error: {}", message),
+            HeadlinesError::Timeout(message) => write!(f, "Timed out: {}", message),
+            HeadlinesError::Auth(message) => write!(f, "The API key was refused: {}", message),
+            HeadlinesError::RateLimited(message) => {
+                write!(f, "Too many requests, try again later: {}", message)
+            }
+            HeadlinesError::Parse(message) => write!(f, "Unreadable response: {}", message),
+            HeadlinesError::Storage(message) => write!(f, "Storage error: {}", message),
+            HeadlinesError::Internal(message) => write!(f, "Internal error: {}", message),
+        }
+    }
+}
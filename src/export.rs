@@ -0,0 +1,124 @@
+use crate::bookmarks::Bookmarks;
+use crate::news::NewsCardData;
+use crate::trends::TopicCoverage;
+use chrono::{DateTime, Local, SecondsFormat, Utc};
+
+/// Renders `articles` as a standalone HTML page, styles included
+pub fn to_html(articles: &[NewsCardData], exported_at: DateTime<Local>) -> String {
+    let mut html = String::from(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <title>headlines</title>
+    <style>
+        body { max-width: 48em; margin: 2em auto; padding: 0 1em; font-family: sans-serif; background: #1b1b1b; color: #dcdcdc; }
+        article { padding: 0.5em 0; border-bottom: 1px solid #3c3c3c; }
+        h2 { font-size: 1.1em; color: #ffffff; }
+        a { color: #00ffff; }
+        .meta { font-size: 0.8em; color: #8c8c8c; }
+    </style>
+</head>
+<body>
+"#,
+    );
+
+    html.push_str(&format!(
+        "    <h1>headlines</h1>\n    <p class=\"meta\">Exported on {}</p>\n",
+        exported_at.format("%Y-%m-%d %H:%M")
+    ));
+    for article in articles {
+        html.push_str(&format!(
+            r#"    <article>
+        <h2>▶ {}</h2>
+        <p>{}</p>
+        <p class="meta">{} · fetched {} · <a href="{}">read more ⤴</a></p>
+    </article>
+"#,
+            escape(&article.title),
+            escape(&article.desc),
+            escape(&article.source),
+            article
+                .fetched_at
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M"),
+            escape(&article.url),
+        ));
+    }
+    html.push_str("</body>\n</html>\n");
+
+    html
+}
+
+/// Renders `bookmarks` as an Atom feed, so feed readers can subscribe to them
+pub fn to_atom(bookmarks: &Bookmarks, updated_at: DateTime<Utc>) -> String {
+    let mut atom = format!(
+        r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+    <title>headlines bookmarks</title>
+    <id>urn:headlines:bookmarks</id>
+    <updated>{}</updated>
+    <generator uri="https://github.com/c-leri/headlines">headlines</generator>
+"#,
+        updated_at.to_rfc3339_opts(SecondsFormat::Secs, true)
+    );
+    for bookmark in bookmarks.iter() {
+        let article = &bookmark.article;
+        atom.push_str(&format!(
+            r#"    <entry>
+        <title>{}</title>
+        <id>{}</id>
+        <link href="{}"/>
+        <updated>{}</updated>
+        <author><name>{}</name></author>
+        <summary>{}</summary>
+    </entry>
+"#,
+            escape(&article.title),
+            escape(&article.url),
+            escape(&article.url),
+            bookmark
+                .bookmarked_at
+                .to_rfc3339_opts(SecondsFormat::Secs, true),
+            escape(&article.source),
+            escape(&article.desc),
+        ));
+    }
+    atom.push_str("</feed>\n");
+
+    atom
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Renders the weekly topic report as CSV, one row per topic and source
+pub fn trends_to_csv(report: &[TopicCoverage]) -> String {
+    let mut csv = String::from("topic,source,mentions,articles,share\n");
+    for coverage in report {
+        for source in &coverage.sources {
+            csv.push_str(&format!(
+                "{},{},{},{},{:.3}\n",
+                csv_field(&coverage.topic),
+                csv_field(&source.source),
+                source.mentions,
+                source.articles,
+                source.share(),
+            ));
+        }
+    }
+    csv
+}
+
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
@@ -0,0 +1,138 @@
+use crate::http;
+use crate::thumbnails;
+use eframe::egui::{vec2, Color32, Context, Image, TextureHandle, TextureOptions, Ui};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Side of the icons on the cards
+const FAVICON_SIZE: f32 = 14.;
+/// Seconds an icon saved on disk is used before it's fetched again
+#[cfg(not(target_arch = "wasm32"))]
+const MAX_AGE: u64 = 30 * 24 * 60 * 60;
+
+enum Favicon {
+    Loading,
+    Loaded(TextureHandle),
+    /// The site has none, or it couldn't be read
+    Missing,
+}
+
+type FaviconCache = Arc<Mutex<HashMap<String, Favicon>>>;
+
+/// Icons of the articles' sites, kept in memory for the session and on disk between runs on native
+#[derive(Default)]
+pub struct Favicons {
+    cache: FaviconCache,
+}
+
+impl Favicons {
+    /// Shows the icon of the site, nothing until it's loaded or when it has none
+    pub fn show(&self, ui: &mut Ui, domain: &str, tint: Color32) {
+        let mut cache = self.cache.lock().unwrap();
+        match cache.get(domain) {
+            Some(Favicon::Loaded(texture)) => {
+                ui.add(Image::new(texture, vec2(FAVICON_SIZE, FAVICON_SIZE)).tint(tint));
+            }
+            Some(_) => {}
+            None => {
+                cache.insert(domain.to_string(), Favicon::Loading);
+                drop(cache);
+                load(domain.to_string(), self.cache.clone(), ui.ctx().clone());
+            }
+        }
+    }
+}
+
+fn load(domain: String, cache: FaviconCache, ctx: Context) {
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Some(bytes) = disk::read(&domain) {
+        let favicon = texture(&ctx, &domain, &bytes);
+        cache.lock().unwrap().insert(domain, favicon);
+        ctx.request_repaint();
+        return;
+    }
+
+    let url = format!("https://{}/favicon.ico", domain);
+    http::fetch(ehttp::Request::get(url), move |response| {
+        let bytes = match response {
+            Ok(response) if response.ok => response.bytes,
+            // an empty file remembers that the site has no icon
+            Ok(_) => Vec::new(),
+            // tried again on the next run
+            Err(e) => {
+                tracing::error!("Failed fetching the icon of {}: {}", domain, e);
+                cache.lock().unwrap().insert(domain, Favicon::Missing);
+                return;
+            }
+        };
+        #[cfg(not(target_arch = "wasm32"))]
+        disk::write(&domain, &bytes);
+        let favicon = texture(&ctx, &domain, &bytes);
+        cache.lock().unwrap().insert(domain, favicon);
+        ctx.request_repaint();
+    });
+}
+
+fn texture(ctx: &Context, domain: &str, bytes: &[u8]) -> Favicon {
+    if bytes.is_empty() {
+        return Favicon::Missing;
+    }
+    match thumbnails::decode(bytes) {
+        Ok(image) => Favicon::Loaded(ctx.load_texture(
+            format!("favicon-{}", domain),
+            image,
+            TextureOptions::LINEAR,
+        )),
+        Err(e) => {
+            tracing::error!("Failed reading the icon of {}: {}", domain, e);
+            Favicon::Missing
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod disk {
+    use super::MAX_AGE;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    fn path(domain: &str) -> Option<PathBuf> {
+        let name: String = domain
+            .chars()
+            .map(|c| match c {
+                'a'..='z' | 'A'..='Z' | '0'..='9' | '.' | '-' => c,
+                _ => '_',
+            })
+            .collect();
+        directories_next::ProjectDirs::from("", "", "headlines").map(|dirs| {
+            dirs.cache_dir()
+                .join("favicons")
+                .join(format!("{}.ico", name))
+        })
+    }
+
+    /// The icon saved for the site unless it's too old, empty when the site has none
+    pub fn read(domain: &str) -> Option<Vec<u8>> {
+        let path = path(domain)?;
+        let age = fs::metadata(&path).ok()?.modified().ok()?.elapsed().ok()?;
+        if age > Duration::from_secs(MAX_AGE) {
+            return None;
+        }
+        fs::read(path).ok()
+    }
+
+    pub fn write(domain: &str, bytes: &[u8]) {
+        let path = match path(domain) {
+            Some(path) => path,
+            None => return,
+        };
+        let result = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&path, bytes));
+        if let Err(e) = result {
+            tracing::error!("Failed saving the icon of {}: {}", domain, e);
+        }
+    }
+}
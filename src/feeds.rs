@@ -0,0 +1,143 @@
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+/// An item of an RSS feed or an entry of an Atom feed
+#[derive(Default)]
+pub struct FeedItem {
+    pub title: String,
+    pub link: String,
+    pub summary: String,
+    /// Url of the item's image, from Media RSS or an image enclosure
+    pub thumbnail: Option<String>,
+}
+
+/// Reads the items of an RSS 2.0 or Atom feed. Elements are matched by their local name, so the
+/// namespaced ones like `media:description` are read as well.
+pub fn parse(xml: &[u8]) -> Result<Vec<FeedItem>, String> {
+    let mut reader = Reader::from_reader(xml);
+    reader.trim_text(true);
+    let mut buf = Vec::new();
+    let mut items = Vec::new();
+    let mut item: Option<FeedItem> = None;
+    // element whose text is being read
+    let mut field: Option<Vec<u8>> = None;
+    loop {
+        match reader
+            .read_event_into(&mut buf)
+            .map_err(|e| e.to_string())?
+        {
+            Event::Start(e) => {
+                let name = e.local_name().as_ref().to_vec();
+                if matches!(name.as_slice(), b"item" | b"entry") {
+                    item = Some(FeedItem::default());
+                } else if let Some(item) = &mut item {
+                    read_attributes(item, &e)?;
+                }
+                field = Some(name);
+            }
+            Event::Empty(e) => {
+                if let Some(item) = &mut item {
+                    read_attributes(item, &e)?;
+                }
+            }
+            Event::Text(e) => {
+                if let (Some(item), Some(field)) = (&mut item, &field) {
+                    read_text(item, field, &e.unescape().map_err(|e| e.to_string())?);
+                }
+            }
+            Event::CData(e) => {
+                if let (Some(item), Some(field)) = (&mut item, &field) {
+                    read_text(item, field, &String::from_utf8_lossy(&e.into_inner()));
+                }
+            }
+            Event::End(e) => {
+                if matches!(e.local_name().as_ref(), b"item" | b"entry") {
+                    items.extend(item.take());
+                }
+                field = None;
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+    Ok(items)
+}
+
+/// The first of each field is kept, YouTube repeats the title in its `media:group`
+fn read_text(item: &mut FeedItem, field: &[u8], text: &str) {
+    let target = match field {
+        b"title" => &mut item.title,
+        // RSS links are text, Atom ones are attributes
+        b"link" => &mut item.link,
+        b"description" | b"summary" => &mut item.summary,
+        _ => return,
+    };
+    if target.is_empty() {
+        *target = text.trim().to_string();
+    }
+}
+
+fn read_attributes(item: &mut FeedItem, element: &BytesStart) -> Result<(), String> {
+    let attribute = |name: &str| -> Result<Option<String>, String> {
+        match element.try_get_attribute(name).map_err(|e| e.to_string())? {
+            Some(attribute) => attribute
+                .unescape_value()
+                .map(|value| Some(value.into_owned()))
+                .map_err(|e| e.to_string()),
+            None => Ok(None),
+        }
+    };
+    match element.local_name().as_ref() {
+        b"link" => {
+            // the alternate link is the item's page, the others are comments, enclosures…
+            let alternate = attribute("rel")?.map_or(true, |rel| rel == "alternate");
+            if item.link.is_empty() && alternate {
+                item.link = attribute("href")?.unwrap_or_default();
+            }
+        }
+        b"thumbnail" if item.thumbnail.is_none() => item.thumbnail = attribute("url")?,
+        b"enclosure" | b"content" if item.thumbnail.is_none() => {
+            let is_image = attribute("type")?.map_or(false, |t| t.starts_with("image/"))
+                || attribute("medium")?.map_or(false, |m| m == "image");
+            if is_image {
+                item.thumbnail = attribute("url")?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Feed urls of the outlines of an OPML subscription list, the format feed readers export
+#[cfg(feature = "gui")]
+pub fn opml_urls(xml: &[u8]) -> Result<Vec<String>, String> {
+    let mut reader = Reader::from_reader(xml);
+    let mut buf = Vec::new();
+    let mut urls = Vec::new();
+    let mut opml = false;
+    loop {
+        match reader
+            .read_event_into(&mut buf)
+            .map_err(|e| e.to_string())?
+        {
+            Event::Start(e) | Event::Empty(e) => match e.local_name().as_ref() {
+                b"opml" => opml = true,
+                b"outline" => {
+                    if let Some(url) = e.try_get_attribute("xmlUrl").map_err(|e| e.to_string())? {
+                        let url = url.unescape_value().map_err(|e| e.to_string())?;
+                        urls.push(url.into_owned());
+                    }
+                }
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+    if !opml {
+        return Err("not an OPML file".to_string());
+    }
+    Ok(urls)
+}
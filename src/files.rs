@@ -0,0 +1,100 @@
+/// Lets the user save `contents` as `file_name`, through a save dialog on native.
+/// Returns `Ok(false)` if the user cancelled.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save_file(file_name: &str, _mime_type: &str, contents: &[u8]) -> Result<bool, String> {
+    match rfd::FileDialog::new().set_file_name(file_name).save_file() {
+        Some(path) => std::fs::write(path, contents)
+            .map(|_| true)
+            .map_err(|e| e.to_string()),
+        None => Ok(false),
+    }
+}
+
+/// Lets the user save `contents` as `file_name`, as a browser download on the web.
+/// Returns `Ok(false)` if the user cancelled.
+#[cfg(target_arch = "wasm32")]
+pub fn save_file(file_name: &str, mime_type: &str, contents: &[u8]) -> Result<bool, String> {
+    use eframe::wasm_bindgen::{JsCast, JsValue};
+    use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Url};
+
+    let js_error = |e: JsValue| format!("{:?}", e);
+
+    let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(contents));
+    let blob = Blob::new_with_u8_array_sequence_and_options(
+        &parts,
+        BlobPropertyBag::new().type_(mime_type),
+    )
+    .map_err(js_error)?;
+    let url = Url::create_object_url_with_blob(&blob).map_err(js_error)?;
+
+    let document = web_sys::window()
+        .and_then(|w| w.document())
+        .ok_or("No document to download from")?;
+    let anchor: HtmlAnchorElement = document
+        .create_element("a")
+        .map_err(js_error)?
+        .dyn_into()
+        .map_err(|e| js_error(e.into()))?;
+    anchor.set_href(&url);
+    anchor.set_download(file_name);
+    anchor.click();
+
+    Url::revoke_object_url(&url).map_err(js_error)?;
+    Ok(true)
+}
+
+/// Lets the user pick a file with the given extension through an open dialog on native, and
+/// passes its contents to `on_open`. It isn't called if the user cancelled.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn open_file(extension: &str, on_open: impl FnOnce(Result<Vec<u8>, String>) + 'static) {
+    let picked = rfd::FileDialog::new()
+        .add_filter(extension, &[extension])
+        .pick_file();
+    if let Some(path) = picked {
+        on_open(std::fs::read(path).map_err(|e| e.to_string()));
+    }
+}
+
+/// Lets the user pick a file with the given extension through the browser's file input, and
+/// passes its contents to `on_open` once they're read. It isn't called if the user cancelled.
+#[cfg(target_arch = "wasm32")]
+pub fn open_file(extension: &str, on_open: impl FnOnce(Result<Vec<u8>, String>) + 'static) {
+    use eframe::wasm_bindgen::{closure::Closure, JsCast, JsValue};
+    use wasm_bindgen_futures::JsFuture;
+    use web_sys::HtmlInputElement;
+
+    let js_error = |e: JsValue| format!("{:?}", e);
+
+    let input = web_sys::window()
+        .and_then(|w| w.document())
+        .ok_or_else(|| "No document to pick a file from".to_string())
+        .and_then(|document| document.create_element("input").map_err(js_error))
+        .and_then(|element| {
+            element
+                .dyn_into::<HtmlInputElement>()
+                .map_err(|e| js_error(e.into()))
+        });
+    let input = match input {
+        Ok(input) => input,
+        Err(e) => return on_open(Err(e)),
+    };
+    input.set_type("file");
+    input.set_accept(&format!(".{}", extension));
+
+    let picker = input.clone();
+    let on_change = Closure::once(move || {
+        if let Some(file) = picker.files().and_then(|files| files.get(0)) {
+            wasm_bindgen_futures::spawn_local(async move {
+                let contents = JsFuture::from(file.array_buffer())
+                    .await
+                    .map(|buffer| js_sys::Uint8Array::new(&buffer).to_vec())
+                    .map_err(js_error);
+                on_open(contents);
+            });
+        }
+    });
+    input.set_onchange(Some(on_change.as_ref().unchecked_ref()));
+    // the input outlives this call, the closure has to as well
+    on_change.forget();
+    input.click();
+}
@@ -0,0 +1,159 @@
+use eframe::egui::{Context, FontData, FontDefinitions, FontFamily};
+use serde::{Deserialize, Serialize};
+#[cfg(not(target_arch = "wasm32"))]
+use std::fs;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::Path;
+use std::path::PathBuf;
+
+const MESLO: &str = "MesloLGS";
+/// Family of the reader's serif setting, the proportional fonts stand in when no serif font is found
+pub const SERIF: &str = "serif";
+
+/// Common serif fonts, the first one installed is used by the reader
+#[cfg(not(target_arch = "wasm32"))]
+const SERIF_CANDIDATES: [&str; 8] = [
+    "/usr/share/fonts/truetype/dejavu/DejaVuSerif.ttf",
+    "/usr/share/fonts/dejavu-serif-fonts/DejaVuSerif.ttf",
+    "/usr/share/fonts/TTF/DejaVuSerif.ttf",
+    "/usr/share/fonts/truetype/noto/NotoSerif-Regular.ttf",
+    "/usr/share/fonts/noto/NotoSerif-Regular.ttf",
+    "/usr/share/fonts/truetype/liberation/LiberationSerif-Regular.ttf",
+    "/System/Library/Fonts/Supplemental/Georgia.ttf",
+    "C:\\Windows\\Fonts\\georgia.ttf",
+];
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum FontChoice {
+    MesloLGS,
+    /// egui's default proportional font
+    UbuntuLight,
+    /// egui's default monospace font
+    Hack,
+    /// A font file installed on the system, only available on native
+    System { name: String, path: PathBuf },
+}
+
+impl Default for FontChoice {
+    fn default() -> Self {
+        Self::MesloLGS
+    }
+}
+
+impl FontChoice {
+    pub fn bundled() -> [FontChoice; 3] {
+        [Self::MesloLGS, Self::UbuntuLight, Self::Hack]
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Self::MesloLGS => "MesloLGS",
+            Self::UbuntuLight => "Ubuntu Light",
+            Self::Hack => "Hack",
+            Self::System { name, .. } => name,
+        }
+    }
+}
+
+pub fn configure_fonts(ctx: &Context, choice: &FontChoice) {
+    let mut font_def = FontDefinitions::default();
+
+    let font_name = match choice {
+        FontChoice::MesloLGS => {
+            font_def.font_data.insert(
+                MESLO.to_string(),
+                FontData::from_static(include_bytes!("../MesloLGS-NF-Regular.ttf")),
+            );
+            MESLO.to_string()
+        }
+        FontChoice::UbuntuLight => "Ubuntu-Light".to_string(),
+        FontChoice::Hack => "Hack".to_string(),
+        FontChoice::System { name, path } => match std::fs::read(path) {
+            Ok(bytes) => {
+                font_def
+                    .font_data
+                    .insert(name.to_string(), FontData::from_owned(bytes));
+                name.to_string()
+            }
+            Err(e) => {
+                tracing::error!("Failed loading font {}: {}", path.display(), e);
+                return configure_fonts(ctx, &FontChoice::default());
+            }
+        },
+    };
+
+    let family = font_def
+        .families
+        .get_mut(&FontFamily::Proportional)
+        .unwrap();
+    family.retain(|f| *f != font_name);
+    family.insert(0, font_name);
+
+    let mut serif = Vec::new();
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Some(bytes) = SERIF_CANDIDATES.iter().find_map(|path| fs::read(path).ok()) {
+        font_def
+            .font_data
+            .insert(SERIF.to_string(), FontData::from_owned(bytes));
+        serif.push(SERIF.to_string());
+    }
+    serif.extend(font_def.families[&FontFamily::Proportional].iter().cloned());
+    font_def
+        .families
+        .insert(FontFamily::Name(SERIF.into()), serif);
+
+    ctx.set_fonts(font_def);
+}
+
+/// Fonts found in the usual system and user font directories, sorted by name
+#[cfg(not(target_arch = "wasm32"))]
+pub fn system_fonts() -> Vec<FontChoice> {
+    let mut dirs = vec![
+        PathBuf::from("/usr/share/fonts"),
+        PathBuf::from("/usr/local/share/fonts"),
+        PathBuf::from("/Library/Fonts"),
+        PathBuf::from("/System/Library/Fonts"),
+        PathBuf::from("C:\\Windows\\Fonts"),
+    ];
+    if let Some(home) = std::env::var_os("HOME").map(PathBuf::from) {
+        dirs.push(home.join(".fonts"));
+        dirs.push(home.join(".local/share/fonts"));
+        dirs.push(home.join("Library/Fonts"));
+    }
+
+    let mut fonts = Vec::new();
+    for dir in dirs {
+        collect_fonts(&dir, &mut fonts, 0);
+    }
+    fonts.sort_by(|a, b| a.name().cmp(b.name()));
+    fonts.dedup_by(|a, b| a.name() == b.name());
+
+    fonts
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn collect_fonts(dir: &Path, fonts: &mut Vec<FontChoice>, depth: usize) {
+    if depth > 4 {
+        return;
+    }
+    if let Ok(entries) = fs::read_dir(dir) {
+        for path in entries.flatten().map(|e| e.path()) {
+            if path.is_dir() {
+                collect_fonts(&path, fonts, depth + 1);
+                continue;
+            }
+            let is_font = path
+                .extension()
+                .and_then(|e| e.to_str())
+                .map_or(false, |e| {
+                    e.eq_ignore_ascii_case("ttf") || e.eq_ignore_ascii_case("otf")
+                });
+            if !is_font {
+                continue;
+            }
+            if let Some(name) = path.file_stem().map(|n| n.to_string_lossy().to_string()) {
+                fonts.push(FontChoice::System { name, path });
+            }
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use crate::countries;
+use crate::news::NewsCardData;
+use crate::news_source;
+use crate::share;
+use crate::stored_config::StoredConfig;
+use crate::worker::FetchState;
+
+const USAGE: &str = "\
+Usage: headlines --headless [options]
+
+Fetches the headlines once, prints them and exits.
+
+Options:
+    --format <plain|json|markdown>  Output format, plain by default
+    --api-key <key>                 newsapi.org key, the app's stored key by default
+    --country <code>                Country code, can be repeated, the app's countries by default
+    --query <text>                  Only the headlines containing the text";
+
+#[derive(Clone, Copy)]
+enum Format {
+    Plain,
+    Json,
+    Markdown,
+}
+
+/// Runs the `--headless` mode with the command line arguments, returns the exit code
+pub fn run_headless(args: &[String]) -> i32 {
+    let stored = StoredConfig::load();
+    let mut format = Format::Plain;
+    let mut api_key = stored.api_key.clone();
+    let mut countries = Vec::new();
+    let mut query = String::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().cloned().unwrap_or_default();
+        match arg.as_str() {
+            "--headless" => {}
+            "--format" => {
+                format = match value().as_str() {
+                    "plain" => Format::Plain,
+                    "json" => Format::Json,
+                    "markdown" => Format::Markdown,
+                    other => return usage_error(&format!("unknown format \"{}\"", other)),
+                }
+            }
+            "--api-key" => api_key = value(),
+            "--country" => {
+                let code = value().to_uppercase();
+                match countries::by_code(&code) {
+                    Some(info) => countries.push(info.country),
+                    None => return usage_error(&format!("unknown country \"{}\"", code)),
+                }
+            }
+            "--query" => query = value(),
+            "--help" | "-h" => {
+                println!("{}", USAGE);
+                return 0;
+            }
+            other => return usage_error(&format!("unknown option \"{}\"", other)),
+        }
+    }
+
+    if api_key.is_empty() {
+        return usage_error("no API key, pass --api-key or set it in the app first");
+    }
+    if countries.is_empty() {
+        countries = stored.countries();
+    }
+
+    let mut state = FetchState::new(api_key, countries, stored.source_caps, stored.retry);
+    state.query = query.trim().to_string();
+
+    let (articles, errors) = news_source::fetch_all(&state);
+    for e in &errors {
+        eprintln!("{}", e);
+    }
+
+    match print(&articles, format) {
+        Ok(()) if errors.is_empty() => 0,
+        Ok(()) => 1,
+        Err(e) => {
+            eprintln!("Failed printing the headlines: {}", e);
+            1
+        }
+    }
+}
+
+fn print(articles: &[NewsCardData], format: Format) -> Result<(), String> {
+    match format {
+        Format::Plain => {
+            for a in articles {
+                println!("{}\n    {}\n    {}\n", a.title, a.desc, a.url);
+            }
+        }
+        Format::Json => {
+            let json = serde_json::to_string_pretty(articles).map_err(|e| e.to_string())?;
+            println!("{}", json);
+        }
+        Format::Markdown => {
+            for a in articles {
+                println!("- {}: {}", share::markdown_link(&a.title, &a.url), a.desc);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn usage_error(message: &str) -> i32 {
+    eprintln!("headlines: {}\n\n{}", message, USAGE);
+    2
+}
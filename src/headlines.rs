@@ -1,234 +1,3104 @@
+use crate::accessibility;
+use crate::aggregate::{self, SourceCaps};
+use crate::bookmarks::Bookmarks;
+use crate::cards::{self, CardAction, CardBindings};
+use crate::categories::Category;
+use crate::category_tabs::CategoryTabs;
+use crate::changelog::{self, Release, CURRENT_VERSION};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::close::{self, CloseAction, CloseHandler};
+use crate::cluster;
+use crate::combo;
+use crate::compare::{Comparison, ComparisonSettings};
+use crate::countries;
+use crate::dashboard::{Dashboard, DashboardData, DashboardSettings};
+#[cfg(target_arch = "wasm32")]
+use crate::deep_link;
+use crate::demo;
+use crate::drop_import::{self, Dropped};
+use crate::entities;
+use crate::error_log::ErrorLog;
+use crate::errors::HeadlinesError;
+use crate::export;
+use crate::favicons::Favicons;
+use crate::files;
+use crate::fonts::{self, FontChoice};
+use crate::history::History;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::hotkey::{self, GlobalHotkey};
+use crate::http::{self, ProxySettings};
+use crate::http_cache::SharedHttpCache;
+use crate::language;
+use crate::marquee::Marquee;
+use crate::mastodon::{Composer, MastodonSettings};
+use crate::news::{self, NewsCardData};
+use crate::news_source::{NewsApi, NewsSource, NEWSAPI_PAGES, NEWSAPI_SOURCE};
+use crate::onboarding::{Choices, Finish, Wizard};
+use crate::outbox::{OutboundRequest, Outbox};
+use crate::pocket::{PocketLogin, PocketSettings};
+use crate::privacy;
+use crate::profiles::Profiles;
+use crate::qr;
+use crate::quotes::{QuoteSettings, Quotes};
+use crate::reader::{PageTitles, Reader, ReaderAction, ReaderSettings};
+use crate::retention::{Cleanup, RetentionSettings};
+use crate::review::ReviewQueue;
+use crate::schedule::NightSchedule;
+use crate::screens::{Navigation, Screen};
+use crate::search::LocalSearch;
+use crate::sentiment::{Scoring, SentimentSettings};
+use crate::session::Session;
+use crate::share;
+use crate::sources::{self, SourceSettings};
+use crate::speech::Speech;
+use crate::stats::ReadingStats;
+use crate::store::{self, Store};
+use crate::sync::SettingsSync;
+use crate::theme::{self, Theme, ThemeMode};
+use crate::thumbnails::Thumbnails;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::ticker::{Ticker, TickerSettings};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::title_bar::TitleBar;
+use crate::trends::{self, TopicTrends};
+use crate::visits::Visits;
+use crate::wallabag::{Wallabag, WallabagSettings};
+use crate::weather::{CityWeather, WeatherSettings};
+use crate::webhook::{Webhook, WebhookSettings};
+use crate::worker::{FetchState, Fetched, Msg, RetryPolicy, Step, WorkerQueue};
+use chrono::{DateTime, Local, Utc};
+use eframe::egui::output::OpenUrl;
 use eframe::egui::{
-    menu, Align, Button, CentralPanel, Color32, Context, FontData, FontDefinitions, FontFamily,
-    Hyperlink, Key, Label, Layout, RichText, ScrollArea, Separator, TextStyle, TopBottomPanel, Ui,
-    Visuals, Window,
+    self, menu, Align, Align2, Button, CentralPanel, Checkbox, CollapsingHeader, ComboBox, Context,
+    DragValue, Grid, Hyperlink, Key, Label, Layout, Modifiers, Rect, RichText, ScrollArea, Sense,
+    Separator, Slider, Stroke, Style, TextEdit, TextStyle, TopBottomPanel, Ui, WidgetInfo,
+    WidgetType, Window,
 };
 use eframe::{App, CreationContext, Frame, Storage};
-use newsapi::{NewsAPI, NewsAPIResponse, Country};
+use newsapi::Country;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use std::sync::mpsc::{channel, sync_channel, Receiver, Sender, SyncSender};
+use std::collections::{BTreeMap, BTreeSet};
+#[cfg(not(target_arch = "wasm32"))]
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{channel, sync_channel, Receiver, Sender, SyncSender, TryRecvError};
+use std::sync::{Arc, Mutex};
 #[cfg(not(target_arch = "wasm32"))]
 use std::thread;
+use std::time::Duration;
 
 const PADDING: f32 = 5.;
-const WHITE: Color32 = Color32::from_rgb(255, 255, 255);
-const BLACK: Color32 = Color32::from_rgb(0, 0, 0);
-const CYAN: Color32 = Color32::from_rgb(0, 255, 255);
-const RED: Color32 = Color32::from_rgb(255, 0, 0);
+/// Widest a card's image is shown, smaller ones keep their size
+const THUMBNAIL_WIDTH: f32 = 240.;
+/// Share of the feed scrolled through when its next page starts being fetched
+const NEXT_PAGE_AT: f32 = 0.7;
+/// Screens below the feed's viewport whose cards' images are fetched before they're shown
+const IMAGE_LOOKAHEAD: f32 = 0.5;
 
 const APP_NAME: &str = "headlines";
+const OUTBOX_KEY: &str = "outbox";
+const STATS_KEY: &str = "stats";
+const BOOKMARKS_KEY: &str = "bookmarks";
+const REVIEW_KEY: &str = "review";
+const HTTP_CACHE_KEY: &str = "http_cache";
+const TRENDS_KEY: &str = "trends";
+const READ_KEY: &str = "read";
+const HIDDEN_KEY: &str = "hidden";
+const READER_KEY: &str = "reader";
+const READER_POSITIONS_KEY: &str = "reader_positions";
+const VISITS_KEY: &str = "visits";
+const SESSION_KEY: &str = "session";
+const PROFILES_KEY: &str = "profiles";
+const SYNC_KEY: &str = "sync";
+const WEBHOOK_KEY: &str = "webhook";
+/// Articles of the last refresh, shown until the next one comes through, offline as well
+const FEED_KEY: &str = "feed";
+#[cfg(not(target_arch = "wasm32"))]
+const HISTORY_KEY: &str = "history";
+/// Keys whose value was last saved in the database rather than in eframe's storage
+const IN_DATABASE_KEY: &str = "in_database";
 
-enum Msg {
-    APIKeySet(String),
-    Refresh(Country),
-}
+/// Seconds a refresh may take before the feed admits the source is slow
+const FETCH_BUDGET: f64 = 8.;
+
+/// How often the scheduled theme mode checks whether night has come
+const SCHEDULE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+/// Seconds a fetch worker has to stay up for the next one that stops to be replaced right away
+const WORKER_RESTART_BACKOFF: f64 = 10.;
+/// Wait before handing the fetch worker a message again when its channel was full
+const WORKER_QUEUE_RETRY: Duration = Duration::from_millis(200);
+
+/// How far the feed must be pulled down past its top to refresh it
+const PULL_DISTANCE: f32 = 80.;
+
+/// Height of the cards that were never laid out
+const CARD_HEIGHT_ESTIMATE: f32 = 100.;
+
+/// Width of the first run wizard's column
+const ONBOARDING_WIDTH: f32 = 420.;
+
+const UI_SCALE_MIN: f32 = 0.5;
+const UI_SCALE_MAX: f32 = 2.5;
+/// UI scale added or removed by Ctrl+Plus and Ctrl+Minus
+const ZOOM_STEP: f32 = 0.1;
+/// Version of the settings file's layout, bumped when it changes incompatibly
+const SETTINGS_FILE_VERSION: u32 = 1;
 
 #[derive(Serialize, Deserialize)]
+#[serde(default)]
 struct HeadlinesConfig {
-    dark_mode: bool,
+    theme_mode: ThemeMode,
+    /// The dark mode switch theme_mode replaced, only ever read from an older config
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dark_mode: Option<bool>,
+    night_schedule: NightSchedule,
     api_key: String,
-    country: Country
+    country: Country,
+    /// Countries whose headlines are merged with the main country's
+    more_countries: Vec<Country>,
+    ui_scale: f32,
+    last_seen_version: String,
+    font: FontChoice,
+    privacy_mode: bool,
+    dark_theme: Theme,
+    light_theme: Theme,
+    source_caps: SourceCaps,
+    sources: SourceSettings,
+    retry: RetryPolicy,
+    proxy: ProxySettings,
+    /// Card borders tinted by how positive or negative the headline is
+    sentiment: SentimentSettings,
+    pocket: PocketSettings,
+    wallabag: WallabagSettings,
+    mastodon: MastodonSettings,
+    webhook: WebhookSettings,
+    weather: WeatherSettings,
+    quotes: QuoteSettings,
+    muted_sources: BTreeSet<String>,
+    /// Whether articles covering the same story share a card
+    group_stories: bool,
+    /// How many refreshes the history keeps
+    history_size: usize,
+    /// Whether the titles scroll by in a strip above the footer
+    marquee: bool,
+    /// Points a second the strip scrolls by
+    marquee_speed: f32,
+    #[cfg(not(target_arch = "wasm32"))]
+    ticker: TickerSettings,
+    /// What closing the window does
+    #[cfg(not(target_arch = "wasm32"))]
+    close_action: CloseAction,
+    /// Whether the app draws its own title bar instead of the system's
+    #[cfg(not(target_arch = "wasm32"))]
+    custom_title_bar: bool,
+    /// Hotkey showing and hiding the window from anywhere, like `Ctrl+Shift+H`, none when empty
+    #[cfg(not(target_arch = "wasm32"))]
+    global_hotkey: String,
+    /// ISO 639-3 codes of the languages the feed keeps, every language when empty
+    languages: BTreeSet<String>,
+    /// Categories with a tab next to the top headlines, every category when empty
+    categories: BTreeSet<Category>,
+    card_bindings: CardBindings,
+    confirm_links: bool,
+    /// How much images are dimmed in dark mode, from 0 to 1
+    image_dimming: f32,
+    /// Rows of a description shown before it's collapsed
+    desc_lines: usize,
+    comparison: ComparisonSettings,
+    /// Speed of the speech, as a multiple of the engine's normal speed
+    speech_rate: f32,
+    /// Program, and its arguments, opening links instead of the default browser (native only)
+    browser_command: String,
+    /// Stronger text and outlines, with titles and links kept to WCAG's AAA contrast ratio
+    high_contrast: bool,
+    /// No animations, and still indicators instead of spinners
+    reduced_motion: bool,
+    /// Comma separated words whose articles land in the review queue
+    alert_keywords: String,
+    /// Url the articles approved in the review queue are POSTed to as JSON, none when empty
+    forward_url: String,
+    /// Comma separated topics whose coverage by each source the weekly report counts
+    topics: String,
+    /// Names of the followed entities, whose new articles are queued for review
+    followed_entities: BTreeSet<String>,
+    dashboard: DashboardSettings,
+    /// Whether the data growing with use is kept in a SQLite database, read on launch
+    database: bool,
+    retention: RetentionSettings,
 }
 
 impl Default for HeadlinesConfig {
     fn default() -> Self {
         Self {
-            dark_mode: true,
+            theme_mode: ThemeMode::Dark,
+            dark_mode: None,
+            night_schedule: NightSchedule::default(),
             api_key: String::new(),
-            country: Country::FR
+            country: Country::FR,
+            more_countries: Vec::new(),
+            ui_scale: 1.,
+            last_seen_version: String::new(),
+            font: FontChoice::default(),
+            privacy_mode: false,
+            dark_theme: Theme::dark(),
+            light_theme: Theme::light(),
+            source_caps: SourceCaps::default(),
+            sources: SourceSettings::default(),
+            retry: RetryPolicy::default(),
+            proxy: ProxySettings::default(),
+            sentiment: SentimentSettings::default(),
+            pocket: PocketSettings::default(),
+            wallabag: WallabagSettings::default(),
+            mastodon: MastodonSettings::default(),
+            webhook: WebhookSettings::default(),
+            weather: WeatherSettings::default(),
+            quotes: QuoteSettings::default(),
+            muted_sources: BTreeSet::new(),
+            group_stories: true,
+            history_size: 10,
+            marquee: false,
+            marquee_speed: 40.,
+            #[cfg(not(target_arch = "wasm32"))]
+            ticker: TickerSettings::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            close_action: CloseAction::Quit,
+            #[cfg(not(target_arch = "wasm32"))]
+            custom_title_bar: false,
+            #[cfg(not(target_arch = "wasm32"))]
+            global_hotkey: String::new(),
+            languages: BTreeSet::new(),
+            categories: BTreeSet::new(),
+            card_bindings: CardBindings::default(),
+            confirm_links: false,
+            image_dimming: 0.3,
+            desc_lines: 4,
+            comparison: ComparisonSettings::default(),
+            speech_rate: 1.,
+            browser_command: String::new(),
+            high_contrast: false,
+            reduced_motion: false,
+            alert_keywords: String::new(),
+            forward_url: String::new(),
+            topics: String::new(),
+            followed_entities: BTreeSet::new(),
+            dashboard: DashboardSettings::default(),
+            database: false,
+            retention: RetentionSettings::default(),
+        }
+    }
+}
+
+impl HeadlinesConfig {
+    /// Carries an older config's dark mode switch over to the theme mode, which it had none of
+    fn migrate_dark_mode(&mut self) {
+        if let Some(dark) = self.dark_mode.take() {
+            self.theme_mode = if dark {
+                ThemeMode::Dark
+            } else {
+                ThemeMode::Light
+            };
+        }
+    }
+
+    /// Alert keywords and followed entities, comma separated, for the review queue
+    fn alert_triggers(&self) -> String {
+        let mut triggers = self.alert_keywords.clone();
+        for name in &self.followed_entities {
+            triggers.push(',');
+            triggers.push_str(name);
+        }
+        triggers
+    }
+
+    /// Countries to fetch, the main one first
+    fn countries(&self) -> Vec<Country> {
+        let main = countries::index(self.country);
+        let mut all = vec![self.country];
+        all.extend(
+            self.more_countries
+                .iter()
+                .filter(|c| countries::index(**c) != main),
+        );
+        all
+    }
+
+    /// Key telling the views of the feed apart, the codes of its countries
+    fn view_key(&self) -> String {
+        let codes: Vec<_> = self
+            .countries()
+            .into_iter()
+            .map(|c| countries::COUNTRIES[countries::index(c)].code)
+            .collect();
+        codes.join("+")
+    }
+}
+
+/// What "Export settings" writes, to carry a setup over to another machine or build
+#[derive(Serialize)]
+struct ExportedSettings<'a> {
+    version: u32,
+    config: &'a HeadlinesConfig,
+    bookmarks: &'a Bookmarks,
+}
+
+impl<'a> ExportedSettings<'a> {
+    fn new(config: &'a HeadlinesConfig, bookmarks: &'a Bookmarks) -> Self {
+        Self {
+            version: SETTINGS_FILE_VERSION,
+            config,
+            bookmarks,
         }
     }
 }
 
-struct NewsCardData {
-    title: String,
-    desc: String,
-    url: String,
+#[derive(Deserialize)]
+struct ImportedSettings {
+    version: u32,
+    config: HeadlinesConfig,
+    #[serde(default)]
+    bookmarks: Bookmarks,
 }
 
 pub struct Headlines {
     articles: Vec<NewsCardData>,
     config: HeadlinesConfig,
-    api_key_initialized: bool,
-    news_rx: Option<Receiver<NewsCardData>>,
+    /// Where the countries' headlines are fetched from
+    source: Arc<dyn NewsSource>,
+    dark_mode: bool,
+    /// Screens opened over the feed, going back closes the last one
+    nav: Navigation,
+    news_rx: Option<Receiver<Fetched>>,
     app_tx: Option<SyncSender<Msg>>,
+    worker_queue: WorkerQueue,
+    whats_new: Vec<Release>,
+    refresh_started: f64,
+    /// Whether a fetch is under way, the feed keeps showing the previous articles meanwhile
+    refreshing: bool,
+    /// Articles of the fetch under way
+    incoming: Vec<NewsCardData>,
+    /// Page of the feed the next prefetch asks for, none once the last one came or one failed
+    next_page: Option<u32>,
+    /// Prefetch of the next page under way
+    page_rx: Option<Receiver<Fetched>>,
+    next_page_articles: Vec<NewsCardData>,
+    /// Other articles covering the story of an article of the feed, by the url of the latter
+    related: BTreeMap<String, Vec<NewsCardData>>,
+    visits: Visits,
+    /// How many articles at the top of the feed came since the last visit
+    new_until: usize,
+    /// Whether the new articles haven't been scrolled through yet
+    unseen: bool,
+    /// When the fetch under way last sent something, for the watchdog
+    last_progress: f64,
+    /// When the fetch worker in use was started
+    worker_started: f64,
+    /// Requests of the fetch under way that are over, successful or not
+    answered: usize,
+    refresh_failed: bool,
+    /// Url of the first visible card and how far the viewport is scrolled into it
+    anchor: Option<(String, f32)>,
+    /// Scroll offset to apply to the feed on the next frame
+    scroll_to: Option<f32>,
+    /// How far the feed is pulled down past its top, in points
+    pull: f32,
+    outbox: Outbox,
+    pocket_login: PocketLogin,
+    wallabag: Wallabag,
+    webhook: Webhook,
+    city_weather: CityWeather,
+    quotes: Quotes,
+    /// Toot being written about an article
+    composer: Composer,
+    stats: ReadingStats,
+    trends: TopicTrends,
+    show_stats: bool,
+    /// The headlines of the last refreshes, only kept across sessions on native
+    history: History,
+    show_history: bool,
+    show_local_search: bool,
+    local_search: LocalSearch,
+    /// Where the bookmarks, read articles, history and cached responses are kept
+    store: Store,
+    cleanup: Cleanup,
+    marquee: Marquee,
+    /// Each profile's config and bookmarks are stored under keys of its own
+    profiles: Profiles,
+    /// Profile to switch to on the next frame, where the storage can be reached
+    pending_profile: Option<String>,
+    onboarding: Wizard,
+    /// Whether the feed shows the bundled sample articles, for trying the app without an API key
+    demo: bool,
+    /// Whether the profile to use is being asked for at startup
+    choosing_profile: bool,
+    /// Syncs the profile's config and bookmarks with the other instances of the app
+    sync: SettingsSync,
+    /// Contents of the settings file being imported, once it's read
+    imported: Arc<Mutex<Option<Result<Vec<u8>, String>>>>,
+    /// Width of the top panel's controls on the last frame
+    controls_width: f32,
+    comparison: Comparison,
+    categories: CategoryTabs,
+    dashboard: Dashboard,
+    review: ReviewQueue,
+    speech: Speech,
+    card_heights: Vec<f32>,
+    bookmarks: Bookmarks,
+    /// Urls of the articles marked read
+    read: BTreeSet<String>,
+    /// Urls of the articles hidden from the card's menu, left out of the next refreshes too, and
+    /// when they were hidden for them to expire with the history
+    hidden: BTreeMap<String, DateTime<Utc>>,
+    /// Languages detected in the fetched articles, filtered out ones included
+    seen_languages: BTreeSet<String>,
+    /// Urls of the cards showing their whole description
+    expanded: BTreeSet<String>,
+    query: String,
+    errors: ErrorLog,
+    /// Link shown as a QR code, if any
+    qr_url: Option<String>,
+    thumbnails: Thumbnails,
+    favicons: Favicons,
+    /// Article shown in the details window, if any
+    details: Option<NewsCardData>,
+    reader: Option<Reader>,
+    /// Readers left by following a link, the last one is shown again on going back
+    reader_back: Vec<Reader>,
+    reader_settings: ReaderSettings,
+    page_titles: PageTitles,
+    http_cache: SharedHttpCache,
+    /// Reading progress of the articles left unfinished in the reader, by url
+    reader_positions: BTreeMap<String, f32>,
+    /// Link waiting for the user to confirm opening it
+    pending_link: Option<OpenUrl>,
+    /// File dropped on the window, waiting for the user to confirm importing it
+    pending_import: Option<Dropped>,
+    #[cfg(not(target_arch = "wasm32"))]
+    system_fonts: Option<Vec<FontChoice>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    ticker: Ticker,
+    #[cfg(not(target_arch = "wasm32"))]
+    close: CloseHandler,
+    #[cfg(not(target_arch = "wasm32"))]
+    title_bar: TitleBar,
+    #[cfg(not(target_arch = "wasm32"))]
+    hotkey: GlobalHotkey,
+    /// Polls the worker messages, dropping it cancels the polling
+    #[cfg(target_arch = "wasm32")]
+    worker: Option<gloo_timers::callback::Interval>,
+    /// View last put in the page's url
+    #[cfg(target_arch = "wasm32")]
+    link: deep_link::View,
 }
 
 impl Headlines {
     pub fn new() -> Self {
         Self {
             articles: Vec::new(),
-            api_key_initialized: Default::default(),
+            nav: Navigation::default(),
             config: Default::default(),
+            source: Arc::new(NewsApi),
+            dark_mode: true,
             news_rx: None,
             app_tx: None,
+            worker_queue: WorkerQueue::default(),
+            whats_new: Vec::new(),
+            refresh_started: 0.,
+            refreshing: true,
+            incoming: Vec::new(),
+            next_page: None,
+            page_rx: None,
+            next_page_articles: Vec::new(),
+            related: BTreeMap::new(),
+            visits: Visits::default(),
+            new_until: 0,
+            unseen: false,
+            last_progress: 0.,
+            worker_started: f64::NEG_INFINITY,
+            answered: 0,
+            refresh_failed: false,
+            anchor: None,
+            scroll_to: None,
+            pull: 0.,
+            outbox: Outbox::default(),
+            pocket_login: PocketLogin::default(),
+            wallabag: Wallabag::default(),
+            webhook: Webhook::default(),
+            city_weather: CityWeather::default(),
+            quotes: Quotes::default(),
+            composer: Composer::default(),
+            stats: ReadingStats::default(),
+            trends: TopicTrends::default(),
+            show_stats: false,
+            history: History::default(),
+            show_history: false,
+            show_local_search: false,
+            local_search: LocalSearch::default(),
+            store: Store::default(),
+            cleanup: Cleanup::default(),
+            marquee: Marquee::default(),
+            profiles: Profiles::default(),
+            pending_profile: None,
+            onboarding: Wizard::default(),
+            demo: false,
+            choosing_profile: false,
+            sync: SettingsSync::default(),
+            imported: Arc::default(),
+            controls_width: 0.,
+            comparison: Comparison::default(),
+            categories: CategoryTabs::default(),
+            dashboard: Dashboard::default(),
+            review: ReviewQueue::default(),
+            speech: Speech::new(),
+            card_heights: Vec::new(),
+            bookmarks: Bookmarks::default(),
+            read: BTreeSet::new(),
+            hidden: BTreeMap::new(),
+            expanded: BTreeSet::new(),
+            seen_languages: BTreeSet::new(),
+            query: String::new(),
+            errors: ErrorLog::default(),
+            qr_url: None,
+            thumbnails: Thumbnails::default(),
+            favicons: Favicons::default(),
+            details: None,
+            reader: None,
+            reader_back: Vec::new(),
+            reader_settings: ReaderSettings::default(),
+            page_titles: PageTitles::default(),
+            http_cache: SharedHttpCache::default(),
+            reader_positions: BTreeMap::new(),
+            pending_link: None,
+            pending_import: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            system_fonts: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            ticker: Ticker::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            close: CloseHandler::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            title_bar: TitleBar::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            hotkey: GlobalHotkey::default(),
+            #[cfg(target_arch = "wasm32")]
+            worker: None,
+            #[cfg(target_arch = "wasm32")]
+            link: deep_link::View::default(),
         }
     }
 
-    fn configure_fonts(&self, ctx: &Context) {
-        let mut font_def = FontDefinitions::default();
+    /// App fetching the countries' headlines from `source` instead of newsapi.org
+    pub fn with_source(source: Box<dyn NewsSource>) -> Self {
+        let mut headlines = Self::new();
+        headlines.source = source.into();
+        headlines
+    }
 
-        font_def.font_data.insert(
-            "MesloLGS".to_string(),
-            FontData::from_static(include_bytes!("../MesloLGS-NF-Regular.ttf")),
-        );
+    /// Starts on the feed with the API key, as if it was entered in the onboarding
+    pub fn with_api_key(mut self, api_key: &str) -> Self {
+        self.config.api_key = api_key.to_string();
+        // a fresh install has no releases to catch up on
+        self.config.last_seen_version = CURRENT_VERSION.to_string();
+        self.nav.home();
+        self
+    }
 
-        font_def
-            .families
-            .get_mut(&FontFamily::Proportional)
-            .unwrap()
-            .insert(0, "MesloLGS".to_string());
+    /// Offers the ticker mode, for a window created transparent and small enough for it
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_ticker(mut self) -> Self {
+        self.ticker = Ticker::available();
+        self
+    }
 
-        ctx.set_fonts(font_def);
+    fn theme(&self) -> Theme {
+        let theme = if self.dark_mode {
+            &self.config.dark_theme
+        } else {
+            &self.config.light_theme
+        };
+        if self.config.high_contrast {
+            theme.high_contrast(self.dark_mode)
+        } else {
+            theme.clone()
+        }
     }
 
-    fn render_news_cards(&self, ui: &mut Ui) {
-        if self.articles.is_empty() {
+    /// Lays out the cards intersecting `viewport`, the others only take the room of their last
+    /// known height. Returns the index of the card whose link was opened, if any.
+    fn render_news_cards(&mut self, ui: &mut Ui, viewport: Rect) -> Option<usize> {
+        let mut opened = None;
+        let mut action = None;
+        if self.articles.is_empty() && !self.refreshing {
+            ui.vertical_centered(|ui| ui.label("No articles found"));
+        } else if self.articles.is_empty() {
             ui.vertical_centered(|ui| {
-                ui.label("Loading ⌛");
+                let elapsed = ui.input().time - self.refresh_started;
+                accessibility::spinner(ui, Some(32.));
+                ui.label(self.refresh_progress(elapsed));
+                if elapsed > FETCH_BUDGET {
+                    ui.weak("The news source is slow to answer");
+                }
             });
         } else {
-            for a in &self.articles {
-                // title
+            self.card_heights
+                .resize(self.articles.len(), CARD_HEIGHT_ESTIMATE);
+            ui.set_min_height(self.card_heights.iter().sum());
+
+            let mut top = 0.;
+            let mut first = self.articles.len();
+            for (i, height) in self.card_heights.iter().enumerate() {
+                if top + height >= viewport.min.y {
+                    first = i;
+                    break;
+                }
+                top += height;
+            }
+            ui.add_space(top);
+            self.anchor = self
+                .articles
+                .get(first)
+                .map(|a| (a.url.clone(), viewport.min.y - top));
+
+            let theme = self.theme();
+            let image_tint = theme::image_tint(self.dark_mode, self.config.image_dimming);
+            let mut scrolled_through_new = false;
+            let mut below = self.articles.len();
+            for i in first..self.articles.len() {
+                if top > viewport.max.y {
+                    below = i;
+                    break;
+                }
+                let a = &self.articles[i];
+                let card_top = ui.cursor().top();
+                if i == self.new_until && i > 0 {
+                    ui.add_space(PADDING);
+                    ui.vertical_centered(|ui| ui.weak("— new since last visit —"));
+                }
+
                 ui.add_space(PADDING);
-                let title = format!("▶ {}", a.title);
-                if self.config.dark_mode {
-                    ui.colored_label(WHITE, title);
+                let mut inner_clicked = false;
+                let mut toggle_expanded = false;
+                let tint = if self.config.sentiment.enabled {
+                    self.config.sentiment.score(&a.title).stroke()
                 } else {
-                    ui.colored_label(BLACK, title);
+                    Stroke::default()
+                };
+                let card = egui::Frame::none()
+                    .fill(theme.card_background)
+                    .stroke(tint)
+                    .inner_margin(PADDING)
+                    .rounding(PADDING)
+                    .show(ui, |ui| {
+                        // title
+                        let title_color = if self.read.contains(&a.url) {
+                            ui.visuals().weak_text_color()
+                        } else {
+                            theme.title
+                        };
+                        ui.colored_label(title_color, format!("▶ {}", a.title));
+
+                        if let Some(thumbnail) = &a.thumbnail {
+                            ui.add_space(PADDING);
+                            self.thumbnails
+                                .show(ui, thumbnail, THUMBNAIL_WIDTH, image_tint);
+                        }
+
+                        // desc
+                        ui.add_space(PADDING);
+                        let expanded = self.expanded.contains(&a.url);
+                        let truncated = if expanded {
+                            None
+                        } else {
+                            cards::truncate_rows(
+                                ui,
+                                &a.desc,
+                                TextStyle::Button.resolve(ui.style()),
+                                ui.available_width(),
+                                self.config.desc_lines,
+                            )
+                        };
+                        let desc = truncated.as_deref().unwrap_or(&a.desc);
+                        let desc = Label::new(RichText::new(desc).text_style(TextStyle::Button));
+                        ui.add(desc);
+                        if expanded || truncated.is_some() {
+                            let toggle = if expanded { "show less" } else { "show more" };
+                            if ui.link(toggle).clicked() {
+                                inner_clicked = true;
+                                toggle_expanded = true;
+                            }
+                        }
+
+                        // the other articles covering the story
+                        if let Some(related) = self.related.get(&a.url) {
+                            let covering = format!("{} sources covering this", related.len() + 1);
+                            CollapsingHeader::new(covering)
+                                .id_source(("related", &a.url))
+                                .show(ui, |ui| {
+                                    for article in std::iter::once(a).chain(related) {
+                                        let link = Hyperlink::from_label_and_url(
+                                            cluster::publisher(article),
+                                            &article.url,
+                                        );
+                                        if ui.add(link).clicked() {
+                                            inner_clicked = true;
+                                        }
+                                    }
+                                });
+                        }
+
+                        // people, organizations and places named in the title
+                        let named = entities::extract(&a.title);
+                        if !named.is_empty() {
+                            ui.horizontal_wrapped(|ui| {
+                                for entity in named {
+                                    let text = format!("{} {}", entity.kind.icon(), entity.name);
+                                    if ui.link(RichText::new(text).small()).clicked() {
+                                        inner_clicked = true;
+                                        self.nav.open(Screen::Entity(entity));
+                                    }
+                                }
+                            });
+                        }
+
+                        // links
+                        ui.add_space(PADDING);
+                        ui.with_layout(Layout::right_to_left(Align::Min), |ui| {
+                            let link = Hyperlink::from_label_and_url("read more ⤴", &a.url);
+                            if ui.add(link).clicked() {
+                                inner_clicked = true;
+                                opened = Some(i);
+                            }
+
+                            let bookmarked = self.bookmarks.contains(&a.url);
+                            let bookmark_btn = accessibility::label(
+                                ui.add(
+                                    Button::new(if bookmarked { "★" } else { "☆" }).frame(false),
+                                ),
+                                if bookmarked {
+                                    "Remove bookmark"
+                                } else {
+                                    "Bookmark"
+                                },
+                            );
+                            if bookmark_btn.clicked() {
+                                inner_clicked = true;
+                                action = Some((i, CardAction::Bookmark));
+                            }
+
+                            if let Some(discussion) = &a.discussion {
+                                let comments = Hyperlink::from_label_and_url(
+                                    format!("💬 {}", discussion.comments),
+                                    &discussion.url,
+                                );
+                                if ui.add(comments).on_hover_text("Discussion").clicked() {
+                                    inner_clicked = true;
+                                }
+                                ui.weak(format!("▲ {}", discussion.score));
+                            }
+
+                            if !a.language.is_empty() {
+                                ui.label(
+                                    RichText::new(a.language.to_uppercase())
+                                        .small()
+                                        .background_color(ui.visuals().faint_bg_color),
+                                )
+                                .on_hover_text(language::name(&a.language));
+                            }
+
+                            // only worth telling apart when the feed mixes countries
+                            if !self.config.more_countries.is_empty() {
+                                if let Some(country) = countries::by_code(&a.country) {
+                                    ui.label(
+                                        RichText::new(format!("{} {}", country.flag, country.code))
+                                            .small()
+                                            .background_color(ui.visuals().faint_bg_color),
+                                    )
+                                    .on_hover_text(country.name);
+                                }
+                            }
+
+                            let domain = news::domain(&a.url);
+                            ui.weak(domain);
+                            self.favicons.show(ui, domain, image_tint);
+                        });
+                    })
+                    .response;
+
+                if toggle_expanded && !self.expanded.remove(&a.url) {
+                    self.expanded.insert(a.url.clone());
+                }
+
+                // the card's own buttons take precedence over the card
+                let card = ui.interact(card.rect, ui.id().with(("card", i)), Sense::click());
+                card.widget_info(|| WidgetInfo::labeled(WidgetType::Button, &a.title));
+                if !inner_clicked {
+                    let bindings = &self.config.card_bindings;
+                    if card.double_clicked() {
+                        action = Some((i, bindings.double_click));
+                    } else if card.clicked() {
+                        action = Some((i, bindings.click));
+                    } else if card.middle_clicked() {
+                        action = Some((i, bindings.middle_click));
+                    }
+                }
+                // actions on the card not worth a button of their own
+                let can_mute = self.can_mute(&a.source);
+                card.context_menu(|ui| {
+                    for menu_action in [
+                        CardAction::OpenBrowser,
+                        CardAction::OpenInBackground,
+                        CardAction::OpenReader,
+                    ] {
+                        if ui.button(menu_action.name()).clicked() {
+                            ui.close_menu();
+                            action = Some((i, menu_action));
+                        }
+                    }
+                    ui.separator();
+                    if ui.button("Copy link").clicked() {
+                        ui.close_menu();
+                        ui.output().copied_text = a.url.clone();
+                    }
+                    if ui.button("Copy Markdown link").clicked() {
+                        ui.close_menu();
+                        ui.output().copied_text = share::markdown_link(&a.title, &a.url);
+                    }
+                    if ui.button("Show QR code").clicked() {
+                        ui.close_menu();
+                        self.qr_url = Some(a.url.clone());
+                    }
+                    if self.speech.is_available() && ui.button("Read aloud").clicked() {
+                        ui.close_menu();
+                        self.speech.read(vec![speech_text(a)]);
+                    }
+                    ui.separator();
+                    let bookmark = if self.bookmarks.contains(&a.url) {
+                        "Remove bookmark"
+                    } else {
+                        "Bookmark"
+                    };
+                    if ui.button(bookmark).clicked() {
+                        ui.close_menu();
+                        action = Some((i, CardAction::Bookmark));
+                    }
+                    let mute_btn = Button::new(format!("Mute {}", a.source));
+                    if ui
+                        .add_enabled(can_mute, mute_btn)
+                        .on_disabled_hover_text("It's the only source left in the feed")
+                        .clicked()
+                    {
+                        ui.close_menu();
+                        action = Some((i, CardAction::MuteSource));
+                    }
+                    for menu_action in [CardAction::Hide, CardAction::SearchSimilar] {
+                        if ui.button(menu_action.name()).clicked() {
+                            ui.close_menu();
+                            action = Some((i, menu_action));
+                        }
+                    }
+                    ui.separator();
+                    let save_btn = Button::new("Save to Pocket");
+                    if ui
+                        .add_enabled(self.config.pocket.is_connected(), save_btn)
+                        .on_disabled_hover_text("Connect Pocket in the settings first")
+                        .clicked()
+                    {
+                        ui.close_menu();
+                        action = Some((i, CardAction::SaveToPocket));
+                    }
+                    let save_btn = Button::new("Save to wallabag");
+                    if ui
+                        .add_enabled(self.config.wallabag.is_configured(), save_btn)
+                        .on_disabled_hover_text("Set up wallabag in the settings first")
+                        .clicked()
+                    {
+                        ui.close_menu();
+                        action = Some((i, CardAction::SaveToWallabag));
+                    }
+                    let toot_btn = Button::new("Toot this…");
+                    if ui
+                        .add_enabled(self.config.mastodon.is_configured(), toot_btn)
+                        .on_disabled_hover_text("Set up Mastodon in the settings first")
+                        .clicked()
+                    {
+                        ui.close_menu();
+                        action = Some((i, CardAction::Toot));
+                    }
+                    if share::SUPPORTED && ui.button("Share…").clicked() {
+                        ui.close_menu();
+                        if let Err(e) = share::share(&a.title, &a.url) {
+                            tracing::error!("Failed sharing {}: {}", a.url, e);
+                        }
+                    }
+                });
+
+                ui.add_space(PADDING);
+                theme.add_separator(ui);
+
+                let height = ui.cursor().top() - card_top;
+                self.card_heights[i] = height;
+                top += height;
+                if i + 1 == self.new_until && top <= viewport.max.y {
+                    scrolled_through_new = true;
+                }
+            }
+            let loaded: f32 = self.card_heights.iter().sum();
+            if viewport.max.y >= loaded * NEXT_PAGE_AT {
+                self.prefetch_next_page(ui.ctx());
+            }
+            // the images of the cards about to scroll into view are fetched ahead
+            let lookahead = viewport.max.y + viewport.height() * IMAGE_LOOKAHEAD;
+            for i in below..self.articles.len() {
+                if top > lookahead {
+                    break;
+                }
+                if let Some(thumbnail) = &self.articles[i].thumbnail {
+                    self.thumbnails.prefetch(ui.ctx(), thumbnail);
+                }
+                top += self.card_heights[i];
+            }
+            if scrolled_through_new && self.unseen {
+                self.unseen = false;
+                let view = self.config.view_key();
+                self.visits.visit(&view, &self.articles);
+            }
+        }
+        if let Some((i, action)) = action {
+            let article = self.articles[i].clone();
+            match action {
+                CardAction::Nothing => {}
+                CardAction::ShowDetails => self.details = Some(article),
+                CardAction::OpenReader => {
+                    self.open_reader(article, ui.ctx());
+                    opened = Some(i);
+                }
+                CardAction::OpenBrowser => {
+                    ui.ctx().output().open_url(&article.url);
+                    opened = Some(i);
+                }
+                CardAction::OpenInBackground => {
+                    ui.ctx().output().open_url = Some(OpenUrl::new_tab(&article.url));
+                    opened = Some(i);
+                }
+                CardAction::Bookmark => self.bookmarks.toggle(&article),
+                CardAction::ToggleRead => {
+                    if !self.read.remove(&article.url) {
+                        self.read.insert(article.url);
+                    }
+                }
+                CardAction::SaveToPocket => self.save_to_pocket(&article),
+                CardAction::SaveToWallabag => self.save_to_wallabag(article),
+                CardAction::Toot => {
+                    if self.config.mastodon.is_configured() {
+                        self.composer.open(&article);
+                    } else {
+                        tracing::error!("Can't toot {}, Mastodon isn't set up", article.url);
+                    }
+                }
+                CardAction::MuteSource if !self.can_mute(&article.source) => {
+                    let notice =
+                        format!("{} is the only source left, not muting it", article.source);
+                    self.errors.notify(notice, ui.input().time);
+                }
+                CardAction::MuteSource => {
+                    let notice = format!("{} muted, unmute it in the statistics", article.source);
+                    self.errors.notify(notice, ui.input().time);
+                    self.mute_source(article.source);
+                }
+                CardAction::Hide => {
+                    self.remove_articles(|a| a.url == article.url);
+                    self.hidden.insert(article.url, Utc::now());
+                }
+                CardAction::SearchSimilar => {
+                    self.query = cards::similar_query(&article.title);
+                    self.search(ui.ctx());
+                }
+            }
+            // the card it was on may be gone
+            opened = opened.filter(|_| i < self.articles.len());
+        }
+        opened
+    }
+
+    fn render_stats(&mut self, ctx: &Context) {
+        let suggestions: Vec<String> = self
+            .stats
+            .mute_suggestions(&self.config.muted_sources)
+            .filter(|source| self.can_mute(source))
+            .map(str::to_string)
+            .collect();
+        let mut muted = None;
+        Window::new("Statistics")
+            .open(&mut self.show_stats)
+            .show(ctx, |ui| {
+                Grid::new("source_stats").striped(true).show(ui, |ui| {
+                    ui.strong("Source");
+                    ui.strong("Fetched");
+                    ui.strong("Opened");
+                    ui.strong("Read-through");
+                    ui.end_row();
+                    for (source, stats) in self.stats.iter() {
+                        ui.label(source);
+                        ui.label(stats.fetched.to_string());
+                        ui.label(stats.opened.to_string());
+                        ui.label(format!("{:.0} %", stats.read_through() * 100.));
+                        ui.end_row();
+                    }
+                });
+
+                for source in suggestions {
+                    ui.horizontal(|ui| {
+                        ui.label(format!(
+                            "You never read anything from {} — mute it?",
+                            source
+                        ));
+                        if ui.button("Mute").clicked() {
+                            muted = Some(source.to_string());
+                        }
+                    });
+                }
+
+                let mut unmuted = None;
+                for source in &self.config.muted_sources {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("🔇 {}", source));
+                        if ui.button("Unmute").clicked() {
+                            unmuted = Some(source.clone());
+                        }
+                    });
+                }
+                if let Some(source) = unmuted {
+                    self.config.muted_sources.remove(&source);
+                }
+
+                CollapsingHeader::new("This week's topics").show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Topics");
+                        ui.add(
+                            TextEdit::singleline(&mut self.config.topics)
+                                .hint_text("climate, elections"),
+                        );
+                    });
+                    let report = self.trends.report(&self.config.topics, Utc::now());
+                    if report.is_empty() {
+                        ui.weak("Add comma separated topics to see how each source covers them");
+                        return;
+                    }
+                    trends::show_report(ui, &report);
+                    if ui.button("Export as CSV").clicked() {
+                        let csv = export::trends_to_csv(&report);
+                        if let Err(e) = files::save_file("topics.csv", "text/csv", csv.as_bytes()) {
+                            let error = format!("Failed exporting the topic report: {}", e);
+                            self.errors
+                                .push(HeadlinesError::Storage(error), ctx.input().time);
+                        }
+                    }
+                });
+            });
+        if let Some(source) = muted {
+            self.mute_source(source);
+        }
+    }
+
+    fn render_qr_code(&mut self, ctx: &Context) {
+        let mut open = true;
+        if let Some(url) = &self.qr_url {
+            Window::new("QR code")
+                .open(&mut open)
+                .collapsible(false)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    ui.vertical_centered(|ui| {
+                        qr::show(ui, url);
+                        ui.add_space(PADDING);
+                        ui.add(Label::new(RichText::new(url).small()).wrap(true));
+                    });
+                });
+        }
+        if !open {
+            self.qr_url = None;
+        }
+    }
+
+    fn render_details(&mut self, ctx: &Context) {
+        let mut open = true;
+        let mut action = None;
+        if let Some(article) = &self.details {
+            let theme = self.theme();
+            let image_tint = theme::image_tint(self.dark_mode, self.config.image_dimming);
+            Window::new("Details")
+                .open(&mut open)
+                .collapsible(false)
+                .default_width(400.)
+                .show(ctx, |ui| {
+                    ui.add(
+                        Label::new(RichText::new(&article.title).heading().color(theme.title))
+                            .wrap(true),
+                    );
+                    if let Some(thumbnail) = &article.thumbnail {
+                        ui.add_space(PADDING);
+                        let width = ui.available_width();
+                        self.thumbnails.show(ui, thumbnail, width, image_tint);
+                    }
+                    ui.add_space(PADDING);
+                    ui.add(Label::new(&article.desc).wrap(true));
+                    ui.add_space(PADDING);
+                    Grid::new("article_details").show(ui, |ui| {
+                        ui.strong("Source");
+                        ui.label(&article.source);
+                        ui.end_row();
+                        if let Some(author) = &article.author {
+                            ui.strong("Author");
+                            ui.label(author);
+                            ui.end_row();
+                        }
+                        if let Some(published_at) = article.published_at {
+                            ui.strong("Published");
+                            let published_at = published_at.with_timezone(&Local);
+                            ui.label(published_at.format("%Y-%m-%d %H:%M").to_string());
+                            ui.end_row();
+                        }
+                        ui.strong("Fetched");
+                        let fetched_at = article.fetched_at.with_timezone(&Local);
+                        ui.label(fetched_at.format("%Y-%m-%d %H:%M").to_string());
+                        ui.end_row();
+                    });
+                    ui.add_space(PADDING);
+                    ui.horizontal(|ui| {
+                        if ui.button("Open in browser ⤴").clicked() {
+                            action = Some(CardAction::OpenBrowser);
+                        }
+                        if ui.button("Open in reader 📖").clicked() {
+                            action = Some(CardAction::OpenReader);
+                        }
+                        let bookmark = if self.bookmarks.contains(&article.url) {
+                            "★ Remove bookmark"
+                        } else {
+                            "☆ Bookmark"
+                        };
+                        if ui.button(bookmark).clicked() {
+                            action = Some(CardAction::Bookmark);
+                        }
+                    });
+                });
+        }
+
+        if let Some(article) = self.details.clone() {
+            match action {
+                Some(CardAction::OpenBrowser) => {
+                    ctx.output().open_url(&article.url);
+                    self.record_opened(&article);
+                    open = false;
+                }
+                Some(CardAction::OpenReader) => {
+                    self.open_reader(article.clone(), ctx);
+                    self.record_opened(&article);
+                    open = false;
+                }
+                Some(CardAction::Bookmark) => self.bookmarks.toggle(&article),
+                _ => {}
+            }
+        }
+        if !open {
+            self.details = None;
+        }
+    }
+
+    fn open_reader(&mut self, article: NewsCardData, ctx: &Context) {
+        let progress = self.reader_positions.get(&article.url).copied();
+        self.reader = Some(Reader::open(
+            article,
+            progress,
+            self.page_titles.clone(),
+            self.http_cache.clone(),
+            ctx,
+        ));
+        self.nav.open(Screen::Reader);
+    }
+
+    /// Closes the article in the reader, showing the one it was opened from if any
+    fn close_reader(&mut self) {
+        if let Some(reader) = self.reader.take() {
+            let url = reader.article.url.clone();
+            let progress = reader.progress();
+            self.remember_position(url, progress);
+        }
+        self.reader = self.reader_back.pop();
+        if self.reader.is_none() {
+            self.nav.back();
+        }
+    }
+
+    /// Closes the current screen
+    fn go_back(&mut self) {
+        if self.nav.is(&Screen::Reader) {
+            self.close_reader();
+        } else {
+            self.nav.back();
+        }
+    }
+
+    fn record_opened(&mut self, article: &NewsCardData) {
+        self.stats.record_opened(&article.source);
+        self.read.insert(article.url.clone());
+    }
+
+    /// Draws the top panel, the buttons acting on the window only with one
+    fn render_top_panel(&mut self, ctx: &Context, _frame: Option<&mut Frame>) {
+        TopBottomPanel::top("top_panel").show(ctx, |ui| {
+            ui.add_space(10.);
+            menu::bar(ui, |ui| {
+                // logo
+                ui.with_layout(Layout::left_to_right(Align::Min), |ui| {
+                    if self.nav.can_go_back() {
+                        let back_btn = accessibility::label(
+                            ui.add(Button::new(RichText::new("⬅").text_style(TextStyle::Body))),
+                            "Back",
+                        );
+                        if back_btn.clicked() {
+                            self.go_back();
+                        }
+                    }
+                    ui.add(Label::new(
+                        RichText::new("📓").text_style(TextStyle::Heading),
+                    ));
+
+                    let search = ui.add(
+                        TextEdit::singleline(&mut self.query)
+                            .hint_text("🔍 Search")
+                            .desired_width(120.),
+                    );
+                    if search.lost_focus() && ctx.input().key_pressed(Key::Enter) {
+                        self.search(ctx);
+                    }
+
+                    self.city_weather.show(ui, &self.config.weather);
+                });
+
+                // controls, laid out left to right for the focus order to follow the screen, pushed
+                // right by their width on the last frame
+                ui.add_space((ui.available_width() - self.controls_width).max(0.));
+                let controls = ui.with_layout(Layout::left_to_right(Align::Min), |ui| {
+                    if !self.outbox.is_empty() {
+                        ui.label(format!("📤 {}", self.outbox.len()))
+                            .on_hover_ui(|ui| {
+                                ui.label("Pending outbound actions");
+                                for pending in self.outbox.iter() {
+                                    ui.label(format!(
+                                        "{} ({} failed attempts)",
+                                        pending.request.label, pending.attempts
+                                    ));
+                                }
+                            });
+                    }
+
+                    let bookmarks_btn = accessibility::label(
+                        ui.add(Button::new(RichText::new("🔖").text_style(TextStyle::Body))),
+                        "Bookmarks",
+                    );
+                    if bookmarks_btn.clicked() {
+                        self.nav.toggle(Screen::Bookmarks);
+                    }
+
+                    if !self.errors.is_empty() {
+                        let errors_btn = accessibility::label(
+                            ui.add(Button::new(
+                                RichText::new(format!("⚠ {}", self.errors.len()))
+                                    .text_style(TextStyle::Body),
+                            )),
+                            "Error log",
+                        );
+                        if errors_btn.clicked() {
+                            self.nav.toggle(Screen::Errors);
+                        }
+                    }
+
+                    let stats_btn = accessibility::label(
+                        ui.add(Button::new(RichText::new("📊").text_style(TextStyle::Body))),
+                        "Statistics",
+                    );
+                    if stats_btn.clicked() {
+                        self.show_stats = !self.show_stats;
+                    }
+
+                    let history_btn = accessibility::label(
+                        ui.add(Button::new(RichText::new("🕘").text_style(TextStyle::Body))),
+                        "Past refreshes",
+                    );
+                    if history_btn.clicked() {
+                        self.show_history = !self.show_history;
+                    }
+
+                    let local_search_btn = accessibility::label(
+                        ui.add(Button::new(RichText::new("🔎").text_style(TextStyle::Body))),
+                        "Search bookmarks and past headlines",
+                    );
+                    if local_search_btn.clicked() {
+                        self.show_local_search = !self.show_local_search;
+                    }
+
+                    if self.speech.is_active() {
+                        self.render_speech_controls(ui);
+                    } else if self.speech.is_available() {
+                        let read_all_btn = accessibility::label(
+                            ui.add(Button::new(RichText::new("🔊").text_style(TextStyle::Body))),
+                            "Read all the headlines aloud",
+                        );
+                        if read_all_btn.clicked() {
+                            self.speech
+                                .read(self.articles.iter().map(speech_text).collect());
+                        }
+                    }
+
+                    if self.nav.is(&Screen::Review) || !self.review.is_empty() {
+                        let review_btn = accessibility::label(
+                            ui.add(Button::new(
+                                RichText::new(format!("📥 {}", self.review.len()))
+                                    .text_style(TextStyle::Body),
+                            )),
+                            "Review queue",
+                        );
+                        if review_btn.clicked() {
+                            self.nav.toggle(Screen::Review);
+                        }
+                    }
+
+                    let (home_icon, home_hint) = if self.config.dashboard.enabled {
+                        ("📰", "Show the feed")
+                    } else {
+                        ("🏠", "Show the dashboard")
+                    };
+                    let home_btn = accessibility::label(
+                        ui.add(Button::new(
+                            RichText::new(home_icon).text_style(TextStyle::Body),
+                        )),
+                        home_hint,
+                    );
+                    if home_btn.clicked() {
+                        self.config.dashboard.enabled = !self.config.dashboard.enabled;
+                    }
+
+                    let compare_btn = accessibility::label(
+                        ui.add(Button::new(RichText::new("⚖").text_style(TextStyle::Body))),
+                        "Feeds side by side in columns",
+                    );
+                    if compare_btn.clicked() {
+                        self.nav.toggle(Screen::Comparison);
+                        if self.nav.is(&Screen::Comparison) && self.comparison.is_empty() {
+                            let columns: Vec<_> =
+                                (0..self.config.comparison.columns.len()).collect();
+                            self.fetch_comparison(&columns, ctx);
+                        }
+                    }
+
+                    let export_menu =
+                        ui.menu_button(RichText::new("💾").text_style(TextStyle::Body), |ui| {
+                            if ui.button("Export headlines as HTML").clicked() {
+                                ui.close_menu();
+                                let html = export::to_html(&self.articles, Local::now());
+                                if let Err(e) =
+                                    files::save_file("headlines.html", "text/html", html.as_bytes())
+                                {
+                                    let error = format!("Failed exporting headlines: {}", e);
+                                    self.errors
+                                        .push(HeadlinesError::Storage(error), ctx.input().time);
+                                }
+                            }
+                            if ui.button("Export bookmarks as an Atom feed").clicked() {
+                                ui.close_menu();
+                                let atom = export::to_atom(&self.bookmarks, Utc::now());
+                                if let Err(e) = files::save_file(
+                                    "bookmarks.atom",
+                                    "application/atom+xml",
+                                    atom.as_bytes(),
+                                ) {
+                                    let error = format!("Failed exporting bookmarks: {}", e);
+                                    self.errors
+                                        .push(HeadlinesError::Storage(error), ctx.input().time);
+                                }
+                            }
+                            ui.separator();
+                            if ui.button("Export settings").clicked() {
+                                ui.close_menu();
+                                if let Err(e) = self.export_settings() {
+                                    let error = format!("Failed exporting settings: {}", e);
+                                    self.errors
+                                        .push(HeadlinesError::Storage(error), ctx.input().time);
+                                }
+                            }
+                            if ui.button("Import settings").clicked() {
+                                ui.close_menu();
+                                self.pick_settings_file(ctx);
+                            }
+                        });
+                    accessibility::label(export_menu.response, "Export");
+
+                    let settings_btn = accessibility::label(
+                        ui.add(Button::new(RichText::new("🛠").text_style(TextStyle::Body))),
+                        "Settings",
+                    );
+                    if settings_btn.clicked() {
+                        self.nav.open(Screen::Settings);
+                    }
+
+                    let profile_menu =
+                        ui.menu_button(RichText::new("👤").text_style(TextStyle::Body), |ui| {
+                            if let Some(name) = self.profiles.menu(ui) {
+                                self.pending_profile = Some(name);
+                            }
+                        });
+                    let profile = format!("Profile: {}", self.profiles.current);
+                    accessibility::label(profile_menu.response, &profile);
+
+                    // another country waits for the fetch under way
+                    let selected = countries::index(self.config.country);
+                    let picked = ui
+                        .add_enabled_ui(!self.refreshing, |ui| {
+                            combo::searchable(ui, "country", &countries::entries(), Some(selected))
+                        })
+                        .inner;
+                    if let Some(i) = picked.filter(|i| *i != selected) {
+                        self.config.country = countries::COUNTRIES[i].country;
+                        self.refresh(ctx);
+                    }
+
+                    let next_mode = self.config.theme_mode.next();
+                    let theme_btn = accessibility::label(
+                        ui.add(Button::new(
+                            RichText::new(self.config.theme_mode.icon())
+                                .text_style(TextStyle::Body),
+                        )),
+                        &format!("Switch to {} theme", next_mode.name()),
+                    );
+                    if theme_btn.clicked() {
+                        self.config.theme_mode = next_mode;
+                    }
+
+                    let refresh_btn = if self.refreshing {
+                        let elapsed = ctx.input().time - self.refresh_started;
+                        accessibility::spinner(ui, None)
+                            .on_hover_text(self.refresh_progress(elapsed))
+                    } else if self.unseen {
+                        let text = format!("🔄 {}", self.new_until);
+                        accessibility::label(
+                            ui.add(Button::new(RichText::new(text).text_style(TextStyle::Body))),
+                            &format!("Refresh, {} new articles above", self.new_until),
+                        )
+                    } else {
+                        accessibility::label(
+                            ui.add(Button::new(RichText::new("🔄").text_style(TextStyle::Body))),
+                            "Refresh",
+                        )
+                    };
+                    if refresh_btn.clicked() {
+                        self.refresh(ctx);
+                    }
+
+                    #[cfg(not(target_arch = "wasm32"))]
+                    if let Some(frame) = _frame {
+                        if self.ticker.is_available() {
+                            let ticker_btn = accessibility::label(
+                                ui.add(Button::new(RichText::new("▭").text_style(TextStyle::Body))),
+                                "Ticker mode",
+                            );
+                            if ticker_btn.clicked() {
+                                self.ticker.enter(frame, ctx.input().time);
+                            }
+                        }
+
+                        // the title bar has its own
+                        if !self.config.custom_title_bar {
+                            let close_btn = accessibility::label(
+                                ui.add(Button::new(
+                                    RichText::new("❌").text_style(TextStyle::Body),
+                                )),
+                                "Quit",
+                            );
+                            if close_btn.clicked() {
+                                self.close_window(ctx, frame);
+                            }
+                        }
+                    }
+                });
+                let width = controls.response.rect.width();
+                if (width - self.controls_width).abs() > 0.5 {
+                    self.controls_width = width;
+                    ctx.request_repaint();
+                }
+            });
+            ui.add_space(10.);
+        });
+    }
+
+    /// Fills the feed with the sample articles, nothing is fetched until an API key is entered
+    fn start_demo(&mut self) {
+        self.demo = true;
+        self.articles = demo::articles();
+        self.related.clear();
+        self.card_heights = vec![CARD_HEIGHT_ESTIMATE; self.articles.len()];
+        self.next_page = None;
+        self.refreshing = false;
+        self.nav.home();
+    }
+
+    /// Back to the wizard, where the demo was started from
+    fn leave_demo(&mut self) {
+        self.demo = false;
+        self.articles.clear();
+        self.related.clear();
+        self.card_heights.clear();
+        self.nav.restart();
+    }
+
+    /// First run wizard, the feed opens once it's done
+    fn render_onboarding(&mut self, ctx: &Context) {
+        CentralPanel::default().show(ctx, |ui| {
+            ui.vertical_centered(|ui| {
+                ui.set_max_width(ONBOARDING_WIDTH);
+                let choices = Choices {
+                    api_key: &mut self.config.api_key,
+                    country: &mut self.config.country,
+                    languages: &mut self.config.languages,
+                    categories: &mut self.config.categories,
+                };
+                match self.onboarding.show(ui, choices) {
+                    Some(Finish::Feed) => {
+                        self.onboarding = Wizard::default();
+                        self.nav.home();
+                        self.refreshing = true;
+                        self.refresh_started = ctx.input().time;
+                        // the worker waits for the key, the countries it fetches go first
+                        self.worker_queue
+                            .push(Msg::Refresh(self.config.countries()));
+                        self.worker_queue
+                            .push(Msg::APIKeySet(self.config.api_key.to_string()));
+                        tracing::info!("API key set");
+                    }
+                    Some(Finish::Demo) => self.start_demo(),
+                    Some(Finish::Import) => self.pick_settings_file(ctx),
+                    None => {}
+                }
+            });
+        });
+    }
+
+    fn render_config(&mut self, ctx: &Context) {
+        CentralPanel::default().show(ctx, |_| {
+            Window::new("Configuration").show(ctx, |ui| {
+                if ui.button("⬅ Back").clicked() {
+                    self.nav.back();
+                }
+                ui.horizontal(|ui| {
+                    ui.label("Profile");
+                    ui.menu_button(format!("👤 {}", self.profiles.current), |ui| {
+                        if let Some(name) = self.profiles.menu(ui) {
+                            self.pending_profile = Some(name);
+                        }
+                    });
+                });
+                ui.add_space(PADDING);
+
+                ui.label("Enter your API key for newsapi.org");
+                let text_input = ui.text_edit_singleline(&mut self.config.api_key);
+                if text_input.lost_focus() && ui.input().key_pressed(Key::Enter) {
+                    self.nav.back();
+                    self.demo = false;
+                    self.refreshing = true;
+                    self.refresh_started = ctx.input().time;
+                    self.worker_queue
+                        .push(Msg::APIKeySet(self.config.api_key.to_string()));
+                    tracing::info!("API key set");
+                }
+                ui.label("If you haven't registered for the API key, head over to");
+                ui.hyperlink("https://newsapi.org");
+
+                ui.add_space(PADDING);
+                ui.add(Separator::default());
+                ui.add(
+                    Slider::new(&mut self.config.ui_scale, UI_SCALE_MIN..=UI_SCALE_MAX)
+                        .text("UI scale"),
+                )
+                .on_hover_text("Also Ctrl+scroll, Ctrl+Plus, Ctrl+Minus and Ctrl+0 to reset");
+                ui.horizontal(|ui| {
+                    for (label, scale) in [("Small", 0.8), ("Medium", 1.), ("Large", 1.25)] {
+                        if ui.button(label).clicked() {
+                            self.config.ui_scale = scale;
+                        }
+                    }
+                });
+
+                let previous_font = self.config.font.clone();
+                ComboBox::from_label("Font")
+                    .selected_text(self.config.font.name())
+                    .show_ui(ui, |ui| {
+                        for font in FontChoice::bundled() {
+                            let name = font.name().to_string();
+                            ui.selectable_value(&mut self.config.font, font, name);
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        {
+                            ui.separator();
+                            let system_fonts =
+                                self.system_fonts.get_or_insert_with(fonts::system_fonts);
+                            for font in system_fonts.iter() {
+                                ui.selectable_value(&mut self.config.font, font.clone(), font.name());
+                            }
+                        }
+                    });
+                if self.config.font != previous_font {
+                    fonts::configure_fonts(ctx, &self.config.font);
+                }
+
+                let privacy_toggle = ui
+                    .add_enabled(
+                        privacy::SUPPORTED,
+                        Checkbox::new(&mut self.config.privacy_mode, "Hide from screen captures"),
+                    )
+                    .on_disabled_hover_text("Not supported on this platform");
+                if privacy_toggle.changed()
+                    && !privacy::set_content_protected(APP_NAME, self.config.privacy_mode)
+                {
+                    tracing::error!("Failed changing window content protection");
+                }
+
+                CollapsingHeader::new("Theme").show(ui, |ui| {
+                    if self.dark_mode {
+                        self.config.dark_theme.editor(ui, Theme::dark());
+                    } else {
+                        self.config.light_theme.editor(ui, Theme::light());
+                    }
+                    ui.add(
+                        Slider::new(&mut self.config.image_dimming, 0.0..=1.0)
+                            .text("Dim images in dark mode"),
+                    );
+                });
+
+                CollapsingHeader::new("Accessibility").show(ui, |ui| {
+                    ui.checkbox(&mut self.config.high_contrast, "High contrast");
+                    ui.checkbox(&mut self.config.reduced_motion, "Reduce motion");
+                });
+
+                CollapsingHeader::new("Night schedule").show(ui, |ui| {
+                    ui.label(format!(
+                        "Used by the scheduled theme mode {}",
+                        ThemeMode::Scheduled.icon()
+                    ));
+                    self.config.night_schedule.editor(ui);
+                });
+
+                if self.speech.is_available() {
+                    CollapsingHeader::new("Speech").show(ui, |ui| {
+                        ui.add(
+                            Slider::new(&mut self.config.speech_rate, 0.5..=2.0)
+                                .text("Speed")
+                                .suffix("×"),
+                        );
+                    });
+                }
+
+                CollapsingHeader::new("Links").show(ui, |ui| {
+                    ui.checkbox(
+                        &mut self.config.confirm_links,
+                        "Ask before opening external links",
+                    );
+                    #[cfg(not(target_arch = "wasm32"))]
+                    ui.horizontal(|ui| {
+                        ui.label("Browser command");
+                        ui.add(
+                            TextEdit::singleline(&mut self.config.browser_command)
+                                .hint_text("default browser"),
+                        );
+                    });
+                });
+
+                CollapsingHeader::new("Cards").show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Description lines");
+                        ui.add(DragValue::new(&mut self.config.desc_lines).clamp_range(1..=20));
+                    });
+                    let bindings = &mut self.config.card_bindings;
+                    Grid::new("card_bindings").show(ui, |ui| {
+                        for (label, binding) in [
+                            ("Click", &mut bindings.click),
+                            ("Double-click", &mut bindings.double_click),
+                            ("Middle-click", &mut bindings.middle_click),
+                        ] {
+                            ui.label(label);
+                            ComboBox::from_id_source(label)
+                                .selected_text(binding.name())
+                                .show_ui(ui, |ui| {
+                                    for action in CardAction::ALL {
+                                        ui.selectable_value(binding, action, action.name());
+                                    }
+                                });
+                            ui.end_row();
+                        }
+                    });
+                    if !self.hidden.is_empty() {
+                        let label = format!("{} hidden articles", self.hidden.len());
+                        ui.horizontal(|ui| {
+                            ui.label(label);
+                            if ui.button("Show them again").clicked() {
+                                self.hidden.clear();
+                            }
+                        });
+                    }
+                });
+
+                CollapsingHeader::new("Countries").show(ui, |ui| {
+                    ui.label("Also show headlines from");
+                    let mut changed = false;
+                    for info in &countries::COUNTRIES {
+                        let i = countries::index(info.country);
+                        if i == countries::index(self.config.country) {
+                            continue;
+                        }
+                        let more = &mut self.config.more_countries;
+                        let mut checked = more.iter().any(|c| countries::index(*c) == i);
+                        let label = format!("{} {}", info.flag, info.name);
+                        if ui.checkbox(&mut checked, label).changed() {
+                            changed = true;
+                            if checked {
+                                more.push(info.country);
+                            } else {
+                                more.retain(|c| countries::index(*c) != i);
+                            }
+                        }
+                    }
+                    ui.weak("Each country costs one request of the API quota per refresh");
+                    if changed {
+                        self.refresh(ctx);
+                    }
+                });
+
+                CollapsingHeader::new("Languages").show(ui, |ui| {
+                    ui.label("Only show articles in");
+                    let languages = &mut self.config.languages;
+                    let mut listed: Vec<_> =
+                        self.seen_languages.union(languages).cloned().collect();
+                    listed.sort_by_key(|code| language::name(code).to_string());
+                    let mut changed = false;
+                    for code in listed {
+                        let mut checked = languages.contains(&code);
+                        if ui.checkbox(&mut checked, language::name(&code)).changed() {
+                            changed = true;
+                            if checked {
+                                languages.insert(code);
+                            } else {
+                                languages.remove(&code);
+                            }
+                        }
+                    }
+                    ui.weak("Nothing checked shows every language, as do undetected ones");
+                    if changed {
+                        self.refresh(ctx);
+                    }
+                });
+
+                CollapsingHeader::new("Categories").show(ui, |ui| {
+                    ui.label("Tabs next to the top headlines");
+                    let categories = &mut self.config.categories;
+                    for category in Category::ALL {
+                        let mut checked = categories.contains(&category);
+                        if ui.checkbox(&mut checked, category.name()).changed() {
+                            if checked {
+                                categories.insert(category);
+                            } else {
+                                categories.remove(&category);
+                            }
+                        }
+                    }
+                    ui.weak("Nothing checked shows every category");
+                });
+
+                CollapsingHeader::new("Alerts").show(ui, |ui| {
+                    Grid::new("alerts").num_columns(2).show(ui, |ui| {
+                        ui.label("Keywords");
+                        ui.add(
+                            TextEdit::singleline(&mut self.config.alert_keywords)
+                                .hint_text("election, strike"),
+                        );
+                        ui.end_row();
+                        ui.label("Forward approved to");
+                        ui.add(
+                            TextEdit::singleline(&mut self.config.forward_url)
+                                .hint_text("https://…"),
+                        );
+                        ui.end_row();
+                    });
+                    ui.weak("Matching articles wait in the 📥 review queue");
+                });
+
+                CollapsingHeader::new("Webhook").show(ui, |ui| self.config.webhook.show(ui));
+
+                CollapsingHeader::new("Pocket").show(ui, |ui| {
+                    self.pocket_login.show_settings(ui, &mut self.config.pocket);
+                });
+
+                CollapsingHeader::new("Wallabag").show(ui, |ui| self.config.wallabag.show(ui));
+
+                CollapsingHeader::new("Mastodon").show(ui, |ui| self.config.mastodon.show(ui));
+
+                CollapsingHeader::new("Weather").show(ui, |ui| self.config.weather.show(ui));
+
+                CollapsingHeader::new("Prices").show(ui, |ui| self.config.quotes.show(ui));
+
+                CollapsingHeader::new("Scrolling headlines").show(ui, |ui| {
+                    ui.checkbox(
+                        &mut self.config.marquee,
+                        "Scroll the titles by above the footer",
+                    );
+                    ui.add_enabled(
+                        self.config.marquee,
+                        Slider::new(&mut self.config.marquee_speed, 10.0..=150.0).text("speed"),
+                    );
+                    ui.weak("Click a title to go to its card, reduced motion keeps it still");
+                });
+
+                CollapsingHeader::new("Card tint").show(ui, |ui| {
+                    let sentiment = &mut self.config.sentiment;
+                    ui.checkbox(
+                        &mut sentiment.enabled,
+                        "Tint cards by the mood of the headline",
+                    );
+                    ui.add_enabled_ui(sentiment.enabled, |ui| {
+                        ComboBox::from_id_source("sentiment_scoring")
+                            .selected_text(sentiment.scoring.name())
+                            .show_ui(ui, |ui| {
+                                for scoring in Scoring::ALL {
+                                    ui.selectable_value(
+                                        &mut sentiment.scoring,
+                                        scoring,
+                                        scoring.name(),
+                                    );
+                                }
+                            });
+                        Grid::new("sentiment").num_columns(2).show(ui, |ui| {
+                            ui.label("Positive words");
+                            ui.add(
+                                TextEdit::singleline(&mut sentiment.positive)
+                                    .hint_text("rescue, ceasefire"),
+                            );
+                            ui.end_row();
+                            ui.label("Negative words");
+                            ui.add(
+                                TextEdit::singleline(&mut sentiment.negative)
+                                    .hint_text("layoffs, recall"),
+                            );
+                            ui.end_row();
+                        });
+                        ui.weak("Green for positive headlines, red for negative ones");
+                    });
+                });
+
+                CollapsingHeader::new("Sources").show(ui, |ui| {
+                    let caps = &mut self.config.source_caps;
+                    let mut changed = ui
+                        .horizontal(|ui| {
+                            ui.label("Max articles per source");
+                            ui.add(DragValue::new(&mut caps.default).clamp_range(1..=100))
+                                .changed()
+                        })
+                        .inner;
+                    let sources = &mut self.config.sources;
+                    let previous = sources.enabled();
+                    let mut changed_sources = false;
+                    ui.checkbox(&mut sources.hacker_news, "Hacker News top stories");
+                    ui.checkbox(&mut sources.lobsters, "Lobsters hottest stories");
+                    ui.checkbox(&mut sources.dev_to, "DEV top articles of the day");
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut sources.reddit, "Subreddits");
+                        let subreddits = ui.add_enabled(
+                            sources.reddit,
+                            TextEdit::singleline(&mut sources.subreddits)
+                                .hint_text("worldnews, technology"),
+                        );
+                        // refetched once the names are typed
+                        if subreddits.lost_focus() {
+                            changed_sources = true;
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut sources.youtube, "YouTube");
+                        let channels = ui.add_enabled(
+                            sources.youtube,
+                            TextEdit::singleline(&mut sources.youtube_channels)
+                                .hint_text("channel or playlist ids"),
+                        );
+                        if channels.lost_focus() {
+                            changed_sources = true;
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut sources.feeds, "Feeds");
+                        let urls = ui.add_enabled(
+                            sources.feeds,
+                            TextEdit::singleline(&mut sources.feed_urls)
+                                .hint_text("RSS or Atom feed urls"),
+                        );
+                        if urls.lost_focus() {
+                            changed_sources = true;
+                        }
+                    });
+                    changed_sources |= sources.enabled() != previous;
+                    if changed_sources {
+                        if self.app_tx.is_some() {
+                            self.refreshing = true;
+                            self.refresh_started = ui.input().time;
+                            self.worker_queue.push(Msg::SetSources(sources.clone()));
+                        }
+                    }
+                    for source in std::iter::once(NEWSAPI_SOURCE).chain(sources.enabled()) {
+                        let mut cap = caps.cap(source);
+                        ui.horizontal(|ui| {
+                            ui.label(source);
+                            if ui.add(DragValue::new(&mut cap).clamp_range(1..=100)).changed() {
+                                caps.per_source.insert(source.to_string(), cap);
+                                changed = true;
+                            }
+                        });
+                    }
+                    if changed {
+                        self.worker_queue.push(Msg::SetSourceCaps(caps.clone()));
+                    }
+                    ui.checkbox(
+                        &mut self.config.group_stories,
+                        "One card per story, listing the sources covering it",
+                    );
+                    ui.weak("Grouping applies from the next refresh");
+                });
+
+                CollapsingHeader::new("Network").show(ui, |ui| {
+                    let retry = &mut self.config.retry;
+                    let mut changed = false;
+                    Grid::new("retry_policy").num_columns(2).show(ui, |ui| {
+                        ui.label("Timeout");
+                        changed |= ui
+                            .add(
+                                DragValue::new(&mut retry.timeout)
+                                    .clamp_range(1..=120)
+                                    .suffix(" s"),
+                            )
+                            .changed();
+                        ui.end_row();
+                        ui.label("Retries");
+                        changed |= ui
+                            .add(DragValue::new(&mut retry.retries).clamp_range(0..=5))
+                            .changed();
+                        ui.end_row();
+                    });
+                    ui.weak("Only network errors and timeouts are retried, after a growing delay");
+                    if changed {
+                        self.worker_queue.push(Msg::SetRetryPolicy(retry.clone()));
+                    }
+                });
+
+                // the browser's proxy applies on the web, only CORS is worked around there
+                #[cfg(target_arch = "wasm32")]
+                CollapsingHeader::new("CORS proxy").show(ui, |ui| {
+                    let proxy = &mut self.config.proxy;
+                    let changed = ui
+                        .add(
+                            TextEdit::singleline(&mut proxy.cors_proxy)
+                                .hint_text("https://proxy.example.com/"),
+                        )
+                        .changed();
+                    ui.weak("Prefixed to the app's requests, for sources the browser blocks");
+                    if changed {
+                        http::set_proxy(proxy);
+                    }
+                });
+                #[cfg(not(target_arch = "wasm32"))]
+                CollapsingHeader::new("Proxy").show(ui, |ui| {
+                    let proxy = &mut self.config.proxy;
+                    let previous = proxy.clone();
+                    ComboBox::from_id_source("proxy_kind")
+                        .selected_text(proxy.kind.name())
+                        .show_ui(ui, |ui| {
+                            for kind in http::ProxyKind::ALL {
+                                ui.selectable_value(&mut proxy.kind, kind, kind.name());
+                            }
+                        });
+                    if matches!(proxy.kind, http::ProxyKind::Http | http::ProxyKind::Socks5) {
+                        Grid::new("proxy").num_columns(2).show(ui, |ui| {
+                            ui.label("Host");
+                            ui.text_edit_singleline(&mut proxy.host);
+                            ui.end_row();
+                            ui.label("Port");
+                            ui.add(DragValue::new(&mut proxy.port));
+                            ui.end_row();
+                            ui.label("Username");
+                            ui.add(TextEdit::singleline(&mut proxy.username).hint_text("none"));
+                            ui.end_row();
+                            ui.label("Password");
+                            ui.add(TextEdit::singleline(&mut proxy.password).password(true));
+                            ui.end_row();
+                        });
+                        ui.weak("The password is stored unencrypted with the settings");
+                    }
+                    if *proxy != previous {
+                        http::set_proxy(proxy);
+                    }
+                });
+
+                CollapsingHeader::new("Cache").show(ui, |ui| {
+                    let in_memory = self.http_cache.lock().unwrap().size();
+                    if self.cleanup.show(ui, &mut self.config.retention, in_memory) {
+                        self.http_cache.lock().unwrap().clear();
+                    }
+                });
+
+                if store::SQLITE {
+                    CollapsingHeader::new("Storage").show(ui, |ui| {
+                        ui.checkbox(
+                            &mut self.config.database,
+                            "Keep bookmarks, read articles and history in a SQLite database",
+                        );
+                        ui.weak("Applies on the next launch");
+                    });
+                }
+
+                CollapsingHeader::new("Sync").show(ui, |ui| self.sync.show_settings(ui));
+
+                #[cfg(not(target_arch = "wasm32"))]
+                CollapsingHeader::new("Window").show(ui, |ui| {
+                    let close_action = &mut self.config.close_action;
+                    let actions = CloseAction::ALL.into_iter().filter(|action| {
+                        close::TRAY_SUPPORTED || *action != CloseAction::HideToTray
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Closing the window");
+                        ComboBox::from_id_source("close_action")
+                            .selected_text(close_action.name())
+                            .show_ui(ui, |ui| {
+                                for action in actions {
+                                    ui.selectable_value(close_action, action, action.name());
+                                }
+                            });
+                    });
+                    ui.checkbox(
+                        &mut self.config.custom_title_bar,
+                        "Headlines' own title bar instead of the system's",
+                    );
+                    // the window loses its system frame, and its resize edges with it
+                    ui.weak("The window can't be resized by its edges on Windows and Linux");
+                    if hotkey::SUPPORTED {
+                        self.hotkey
+                            .show_settings(ui, &mut self.config.global_hotkey);
+                    }
+                });
+
+                #[cfg(not(target_arch = "wasm32"))]
+                CollapsingHeader::new("Ticker").show(ui, |ui| {
+                    let ticker = &mut self.config.ticker;
+                    ui.checkbox(&mut ticker.enabled, "Ticker mode");
+                    ui.weak("Takes effect after a restart, the window is made see-through for it");
+                    Grid::new("ticker").num_columns(2).show(ui, |ui| {
+                        ui.label("Headline shown for");
+                        ui.add(
+                            DragValue::new(&mut ticker.interval)
+                                .clamp_range(2..=60)
+                                .suffix(" s"),
+                        );
+                        ui.end_row();
+                        ui.label("Opacity");
+                        ui.add(Slider::new(&mut ticker.opacity, 0.2..=1.));
+                        ui.end_row();
+                    });
+                });
+            });
+        });
+    }
+
+    fn render_whats_new(&mut self, ctx: &Context) {
+        let mut dismissed = false;
+        Window::new("What's new").collapsible(false).show(ctx, |ui| {
+            ScrollArea::vertical().max_height(300.).show(ui, |ui| {
+                for release in &self.whats_new {
+                    ui.heading(release.version);
+                    for note in &release.notes {
+                        ui.label(format!("• {}", note));
+                    }
+                    ui.add_space(PADDING);
+                }
+            });
+            if ui.button("Got it").clicked() {
+                dismissed = true;
+            }
+        });
+        if dismissed {
+            self.whats_new.clear();
+            self.config.last_seen_version = CURRENT_VERSION.to_string();
+        }
+    }
+
+    /// Changes the UI scale with Ctrl+scroll, pinching, Ctrl+Plus, Ctrl+Minus and Ctrl+0
+    fn zoom(&mut self, ctx: &Context) {
+        let mut input = ctx.input_mut();
+        let mut scale = self.config.ui_scale;
+        // Plus is Shift+Equals on most layouts
+        if input.consume_key(Modifiers::COMMAND, Key::PlusEquals)
+            || input.consume_key(Modifiers::COMMAND | Modifiers::SHIFT, Key::PlusEquals)
+        {
+            scale += ZOOM_STEP;
+        }
+        if input.consume_key(Modifiers::COMMAND, Key::Minus) {
+            scale -= ZOOM_STEP;
+        }
+        if input.consume_key(Modifiers::COMMAND, Key::Num0) {
+            scale = 1.;
+        }
+        scale *= input.zoom_delta();
+        drop(input);
+        let scale = scale.clamp(UI_SCALE_MIN, UI_SCALE_MAX);
+        if scale != self.config.ui_scale {
+            self.config.ui_scale = scale;
+        }
+    }
+
+    /// Brings a stored scale back within the slider's range, an edited config may be out of it
+    fn clamp_ui_scale(&mut self) {
+        self.config.ui_scale = if self.config.ui_scale.is_nan() {
+            1.
+        } else {
+            self.config.ui_scale.clamp(UI_SCALE_MIN, UI_SCALE_MAX)
+        };
+    }
+
+    fn apply_ui_scale(&self, ctx: &Context, frame: &Frame) {
+        // wait for the slider to be released, rescaling mid-drag moves it under the pointer
+        if ctx.is_using_pointer() {
+            return;
+        }
+        let native_ppp = frame.info().native_pixels_per_point.unwrap_or(1.);
+        let ppp = native_ppp * self.config.ui_scale;
+        if ctx.pixels_per_point() != ppp {
+            ctx.set_pixels_per_point(ppp);
+        }
+    }
+
+    fn render_link_confirmation(&mut self, ctx: &Context) {
+        let mut confirmed = None;
+        if let Some(link) = &self.pending_link {
+            Window::new("Open link?")
+                .collapsible(false)
+                .resizable(false)
+                .anchor(Align2::CENTER_CENTER, [0., 0.])
+                .show(ctx, |ui| {
+                    ui.add(Label::new(RichText::new(&link.url).monospace()).wrap(true));
+                    ui.add_space(PADDING);
+                    ui.horizontal(|ui| {
+                        if ui.button("Open").clicked() {
+                            confirmed = Some(true);
+                        }
+                        if ui.button("Cancel").clicked() || ui.input().key_pressed(Key::Escape) {
+                            confirmed = Some(false);
+                        }
+                    });
+                });
+        }
+        match confirmed {
+            Some(true) => {
+                if let Some(link) = self.pending_link.take() {
+                    self.open_link(ctx, link);
+                }
+            }
+            Some(false) => self.pending_link = None,
+            None => {}
+        }
+    }
+
+    fn render_import_confirmation(&mut self, ctx: &Context) {
+        let confirmed = match &self.pending_import {
+            Some(dropped) => dropped.confirm(ctx, &self.profiles.current),
+            None => return,
+        };
+        match confirmed {
+            Some(true) => {
+                if let Some(dropped) = self.pending_import.take() {
+                    self.import_dropped(dropped, ctx);
+                }
+            }
+            Some(false) => self.pending_import = None,
+            None => {}
+        }
+    }
+
+    fn import_dropped(&mut self, dropped: Dropped, ctx: &Context) {
+        match dropped {
+            Dropped::Settings { contents, .. } => {
+                // imported like a file picked in the menu, next frame
+                *self.imported.lock().unwrap() = Some(Ok(contents));
+                ctx.request_repaint();
+            }
+            Dropped::Feeds { urls, .. } => {
+                let now = ctx.input().time;
+                let added = self.config.sources.add_feeds(&urls);
+                self.config.sources.feeds = true;
+                // the worker waits for a key before fetching with them
+                if self.app_tx.is_some() && self.nav.onboarded() {
+                    self.refreshing = true;
+                    self.refresh_started = now;
+                }
+                self.worker_queue
+                    .push(Msg::SetSources(self.config.sources.clone()));
+                let notice = format!("{} feeds added to the feeds source", added);
+                self.errors.notify(notice, now);
+            }
+        }
+    }
+
+    /// Applies the close action, to the window or the app
+    #[cfg(not(target_arch = "wasm32"))]
+    fn close_window(&mut self, ctx: &Context, frame: &mut Frame) {
+        if let Err(e) = self.close.close(frame, self.config.close_action) {
+            let error = format!("Failed creating the tray icon: {}", e);
+            self.errors
+                .push(HeadlinesError::Internal(error), ctx.input().time);
+        }
+    }
+
+    /// Opens the link with the configured browser command, or else lets eframe open it
+    fn open_link(&self, ctx: &Context, link: OpenUrl) {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let mut command = self.config.browser_command.split_whitespace();
+            if let Some(program) = command.next() {
+                match std::process::Command::new(program)
+                    .args(command)
+                    .arg(&link.url)
+                    .spawn()
+                {
+                    Ok(_) => return,
+                    Err(e) => tracing::error!("Failed running {}: {}", program, e),
+                }
+            }
+            // the only platform whose opener can leave the browser behind the app
+            #[cfg(target_os = "macos")]
+            if link.new_tab {
+                match std::process::Command::new("open")
+                    .arg("-g")
+                    .arg(&link.url)
+                    .spawn()
+                {
+                    Ok(_) => return,
+                    Err(e) => {
+                        tracing::error!("Failed opening {} in the background: {}", link.url, e)
+                    }
+                }
+            }
+        }
+        ctx.output().open_url = Some(link);
+    }
+
+    fn remember_position(&mut self, url: String, progress: f32) {
+        // finished articles start over
+        if progress < 0.99 {
+            self.reader_positions.insert(url, progress);
+        } else {
+            self.reader_positions.remove(&url);
+        }
+    }
+
+    fn preload_articles(&mut self, ctx: &Context) {
+        let mut done = false;
+        let alert_triggers = self.config.alert_triggers();
+        let mut received = Vec::new();
+        // the worker holds the other end until it stops, which it only does when told to
+        let mut worker_stopped = false;
+        if let Some(rx) = &self.news_rx {
+            loop {
+                match rx.try_recv() {
+                    Ok(fetched) => received.push(fetched),
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => {
+                        worker_stopped = true;
+                        break;
+                    }
+                }
+            }
+        }
+        for fetched in received {
+            self.last_progress = ctx.input().time;
+            match fetched {
+                Fetched::Progress => self.answered += 1,
+                Fetched::Articles(articles) => {
+                    for news in articles {
+                        if self.admit(&news, &alert_triggers) {
+                            self.incoming.push(news);
+                        }
+                    }
+                }
+                Fetched::Failed(error) => {
+                    self.refresh_failed = true;
+                    self.errors.push(error, ctx.input().time);
+                }
+                Fetched::Done => done = true,
+            }
+        }
+        if done {
+            // a failed fetch keeps the articles there were rather than emptying the feed
+            if !self.refresh_failed || !self.incoming.is_empty() {
+                self.swap_in_refreshed();
+            } else if !self.articles.is_empty() {
+                let notice = "Showing the headlines of the last refresh".to_string();
+                self.errors.notify(notice, ctx.input().time);
+            }
+            if let Some(request) = self.webhook.notification(&self.config.webhook) {
+                self.outbox.push(request);
+            }
+            self.incoming.clear();
+            self.answered = 0;
+            self.refresh_failed = false;
+            self.refreshing = false;
+        }
+        if worker_stopped {
+            self.recover_worker(ctx, "stopped unexpectedly");
+        }
+    }
+
+    /// Whether a fetched article makes it into the feed, it's then counted in the stats and
+    /// offered to the review queue and the webhook
+    fn admit(&mut self, news: &NewsCardData, alert_triggers: &str) -> bool {
+        if self.config.muted_sources.contains(&news.source) || self.hidden.contains_key(&news.url) {
+            return false;
+        }
+        if !news.language.is_empty() {
+            self.seen_languages.insert(news.language.clone());
+            let languages = &self.config.languages;
+            if !languages.is_empty() && !languages.contains(&news.language) {
+                return false;
+            }
+        }
+        self.stats.record_fetched(&news.source);
+        self.trends.record(news);
+        self.review.offer(news, alert_triggers);
+        self.webhook.offer(news, &self.config.webhook);
+        true
+    }
+
+    /// Fetches the next page of the feed in the background, unless it's under way or there's none
+    fn prefetch_next_page(&mut self, ctx: &Context) {
+        let page = match self.next_page {
+            Some(page) if self.page_rx.is_none() && !self.refreshing => page,
+            _ => return,
+        };
+        let mut state = self.fetch_state(self.config.countries());
+        state.query = self.query.trim().to_string();
+        state.page = page;
+        self.page_rx = Some(spawn_fetch(state, ctx));
+    }
+
+    /// Appends the articles of the next page once it's fetched, those already in the feed left out
+    fn receive_next_page(&mut self, ctx: &Context) {
+        let received: Vec<_> = match &self.page_rx {
+            Some(rx) => rx.try_iter().collect(),
+            None => return,
+        };
+        let alert_triggers = self.config.alert_triggers();
+        let mut done = false;
+        for fetched in received {
+            match fetched {
+                Fetched::Progress => {}
+                Fetched::Articles(articles) => self.next_page_articles.extend(articles),
+                Fetched::Failed(error) => {
+                    // tried again on the next refresh
+                    self.next_page = None;
+                    self.errors.push(error, ctx.input().time);
+                }
+                Fetched::Done => done = true,
+            }
+        }
+        if !done {
+            return;
+        }
+        self.page_rx = None;
+        let known: BTreeSet<_> = self
+            .articles
+            .iter()
+            .chain(self.related.values().flatten())
+            .map(|a| a.url.clone())
+            .collect();
+        let mut page = std::mem::take(&mut self.next_page_articles);
+        page.retain(|a| !known.contains(&a.url));
+        if page.is_empty() {
+            self.next_page = None;
+            return;
+        }
+        self.next_page = self
+            .next_page
+            .map(|page| page + 1)
+            .filter(|page| *page <= NEWSAPI_PAGES);
+        page.retain(|news| self.admit(news, &alert_triggers));
+        let page = if self.config.group_stories {
+            let (leads, related) = cluster::group(page);
+            self.related.extend(related);
+            leads
+        } else {
+            page
+        };
+        self.card_heights
+            .resize(self.articles.len(), CARD_HEIGHT_ESTIMATE);
+        self.card_heights
+            .extend(page.iter().map(|_| CARD_HEIGHT_ESTIMATE));
+        self.articles.extend(page);
+    }
+
+    /// Bookmarks an article approved in the review queue, and forwards it through the outbox
+    fn approve(&mut self, article: NewsCardData) {
+        if !self.bookmarks.contains(&article.url) {
+            self.bookmarks.toggle(&article);
+        }
+        let url = self.config.forward_url.trim();
+        if url.is_empty() {
+            return;
+        }
+        match serde_json::to_string(&article) {
+            Ok(body) => self.outbox.push(OutboundRequest {
+                label: format!("Forwarding \"{}\"", article.title),
+                url: url.to_string(),
+                headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+                body,
+            }),
+            Err(e) => tracing::error!("Failed serializing {}: {}", article.url, e),
+        }
+    }
+
+    /// Queues the article for Pocket, the outbox tells how it went
+    fn save_to_pocket(&mut self, article: &NewsCardData) {
+        if self.config.pocket.is_connected() {
+            self.outbox.push(self.config.pocket.save_request(article));
+        } else {
+            tracing::error!("Can't save {} to Pocket, it isn't connected", article.url);
+        }
+    }
+
+    /// Queues the article for wallabag, it's sent once there's an access token
+    fn save_to_wallabag(&mut self, article: NewsCardData) {
+        if self.config.wallabag.is_configured() {
+            self.wallabag.save(article);
+        } else {
+            tracing::error!("Can't save {} to wallabag, it isn't set up", article.url);
+        }
+    }
+
+    /// Fetches the feed again, the articles there are stay until the new ones arrive
+    fn refresh(&mut self, ctx: &Context) {
+        if self.demo {
+            return self.start_demo();
+        }
+        // a worker that couldn't be started is tried again, its start fetches
+        if self.app_tx.is_none() {
+            self.refreshing = self.nav.onboarded();
+            self.refresh_started = ctx.input().time;
+            self.start_worker(ctx);
+        } else {
+            self.refreshing = true;
+            self.refresh_started = ctx.input().time;
+            self.worker_queue
+                .push(Msg::Refresh(self.config.countries()));
+        }
+    }
+
+    /// Position in what's read aloud, with pause, skip and stop buttons
+    fn render_speech_controls(&mut self, ui: &mut Ui) {
+        let (current, count) = self.speech.position();
+        ui.label(format!("🔊 {}/{}", current, count));
+        let (pause, hint) = if self.speech.is_playing() {
+            ("⏸", "Pause")
+        } else {
+            ("▶", "Resume")
+        };
+        if accessibility::label(ui.button(pause), hint).clicked() {
+            self.speech.toggle_pause();
+        }
+        if accessibility::label(ui.button("⏭"), "Next").clicked() {
+            self.speech.skip();
+        }
+        if accessibility::label(ui.button("⏹"), "Stop reading").clicked() {
+            self.speech.stop();
+        }
+    }
+
+    /// Fetches the queries of the layout's columns
+    fn fetch_comparison(&mut self, columns: &[usize], ctx: &Context) {
+        for &i in columns {
+            let query = &self.config.comparison.columns[i];
+            let mut state = self.fetch_state(vec![query.country]);
+            state.query = query.query.trim().to_string();
+            self.comparison.start(i, spawn_fetch(state, ctx));
+        }
+    }
+
+    /// What a fetch of the countries' headlines with the config depends on
+    fn fetch_state(&self, countries: Vec<Country>) -> FetchState {
+        let mut state = FetchState::new(
+            self.config.api_key.clone(),
+            countries,
+            self.config.source_caps.clone(),
+            self.config.retry.clone(),
+        );
+        state.source = self.source.clone();
+        state.http_cache = self.http_cache.clone();
+        state
+    }
+
+    /// Fetches the headlines of the category for the feed's countries
+    fn fetch_category(&mut self, category: Category, ctx: &Context) {
+        let mut state = self.fetch_state(self.config.countries());
+        state.category = Some(category);
+        self.categories.start(category, spawn_fetch(state, ctx));
+    }
+
+    /// Follows a drag pulling the feed down past its top, returns true when it's let go far enough
+    fn track_pull(&mut self, ctx: &Context, feed: Rect, at_top: bool) -> bool {
+        let input = ctx.input();
+        let pointer = &input.pointer;
+        if pointer.primary_down() {
+            let on_feed = pointer
+                .press_origin()
+                .map_or(false, |origin| feed.contains(origin));
+            self.pull = if at_top && on_feed {
+                (self.pull + pointer.delta().y).max(0.)
+            } else {
+                0.
+            };
+            false
+        } else {
+            std::mem::take(&mut self.pull) >= PULL_DISTANCE
+        }
+    }
+
+    /// Draws the current screen in the central panel
+    fn render_screen(&mut self, ui: &mut Ui) {
+        match self.nav.current().clone() {
+            Screen::Reader => {
+                // the article may have been dropped with the session it was restored from
+                let mut reader = match self.reader.take() {
+                    Some(reader) => reader,
+                    None => {
+                        self.nav.back();
+                        return;
+                    }
+                };
+                let (articles, bookmarks) = (&self.articles, &self.bookmarks);
+                let action = reader.show(ui, &mut self.reader_settings, &|url| {
+                    known_title(articles, bookmarks, url)
+                });
+                match action {
+                    ReaderAction::None => self.reader = Some(reader),
+                    ReaderAction::Close => {
+                        self.reader = Some(reader);
+                        self.close_reader();
+                    }
+                    ReaderAction::Open(article) => {
+                        self.reader_back.push(reader);
+                        self.open_reader(article, ui.ctx());
+                    }
+                }
+            }
+            Screen::Entity(entity) => {
+                let followed_entities = &mut self.config.followed_entities;
+                let mut followed = followed_entities.contains(&entity.name);
+                let mut listed = BTreeSet::new();
+                let articles = self
+                    .articles
+                    .iter()
+                    .chain(self.bookmarks.iter().map(|b| &b.article))
+                    .filter(|a| entities::mentions(a, &entity.name))
+                    .filter(|a| listed.insert(a.url.clone()));
+                let closed = entities::show_page(ui, &entity, articles, &mut followed);
+                if followed {
+                    followed_entities.insert(entity.name);
+                } else {
+                    followed_entities.remove(&entity.name);
+                }
+                if closed {
+                    self.nav.back();
+                }
+            }
+            Screen::Review => {
+                if let Some(article) = self.review.show(ui) {
+                    self.approve(article);
+                }
+            }
+            Screen::Comparison => {
+                let settings = &mut self.config.comparison;
+                let fetch = self.comparison.show(ui, settings, self.config.desc_lines);
+                self.fetch_comparison(&fetch, ui.ctx());
+            }
+            Screen::Bookmarks => {
+                ui.heading("Bookmarks");
+                let articles: Vec<_> = self.bookmarks.iter().map(|b| b.article.clone()).collect();
+                if articles.is_empty() {
+                    ui.label("No bookmarks yet");
+                    return;
+                }
+                ScrollArea::vertical().show(ui, |ui| {
+                    cards::show_list(ui, &articles, self.config.desc_lines)
+                });
+            }
+            Screen::Errors => {
+                ui.heading("Error log");
+                self.errors.show_log(ui);
+            }
+            Screen::Onboarding | Screen::Settings | Screen::Feed => self.render_feed(ui),
+        }
+    }
+
+    fn render_feed(&mut self, ui: &mut Ui) {
+        if self.config.dashboard.enabled {
+            let data = DashboardData {
+                articles: &self.articles,
+                bookmarks: &self.bookmarks,
+                daily_counts: self.trends.daily_counts(Utc::now()),
+            };
+            if self.dashboard.show(ui, &mut self.config.dashboard, &data) {
+                self.config.dashboard.enabled = false;
+            }
+            return;
+        }
+
+        render_header(ui);
+        if let Some(category) = self.categories.show_bar(ui, &self.config.categories) {
+            self.fetch_category(category, ui.ctx());
+        }
+        if self.categories.active().is_some() {
+            let refresh = self.categories.show_feed(ui, self.config.desc_lines);
+            if let Some(category) = refresh {
+                self.fetch_category(category, ui.ctx());
+            }
+            return;
+        }
+        self.render_pull_indicator(ui);
+        let mut feed = ScrollArea::vertical();
+        if let Some(offset) = self.scroll_to.take() {
+            feed = feed.vertical_scroll_offset(offset);
+        }
+        let output = feed.show_viewport(ui, |ui, viewport| {
+            if let Some(i) = self.render_news_cards(ui, viewport) {
+                let article = self.articles[i].clone();
+                self.record_opened(&article);
+            }
+        });
+        let at_top = output.state.offset.y <= 0.;
+        if self.track_pull(ui.ctx(), output.inner_rect, at_top) && !self.refreshing {
+            self.refresh(ui.ctx());
+        }
+    }
+
+    /// Room opened above the feed while it's pulled, then a spinner while it refreshes
+    fn render_pull_indicator(&self, ui: &mut Ui) {
+        if self.pull > 0. {
+            let text = if self.pull >= PULL_DISTANCE {
+                "⬆ Release to refresh"
+            } else {
+                "⬇ Pull to refresh"
+            };
+            let height = self.pull.min(PULL_DISTANCE);
+            ui.add_sized([ui.available_width(), height], Label::new(text));
+        } else if self.refreshing && !self.articles.is_empty() {
+            ui.vertical_centered(|ui| accessibility::spinner(ui, None));
+        }
+    }
+
+    fn refresh_progress(&self, elapsed: f64) -> String {
+        let requests = self.config.countries().len() + self.config.sources.enabled().len();
+        format!(
+            "Fetching headlines… {} of {} sources answered, {:.0} s",
+            self.answered, requests, elapsed
+        )
+    }
+
+    /// Shows the feed scrolled to the article's card
+    fn scroll_to_card(&mut self, i: usize) {
+        while self.reader.is_some() {
+            self.close_reader();
+        }
+        self.nav.home();
+        self.config.dashboard.enabled = false;
+        self.card_heights
+            .resize(self.articles.len(), CARD_HEIGHT_ESTIMATE);
+        self.scroll_to = Some(self.card_heights[..i].iter().sum());
+    }
+
+    /// Fetches the headlines matching the query in place of the feed's
+    fn search(&mut self, ctx: &Context) {
+        if self.app_tx.is_some() {
+            self.refreshing = true;
+            self.refresh_started = ctx.input().time;
+            self.worker_queue.push(Msg::Search(self.query.clone()));
+        }
+    }
+
+    /// Whether another source would still fill the feed with this one muted, muting the only
+    /// one left would keep the feed loading forever
+    fn can_mute(&self, source: &str) -> bool {
+        let enabled = std::iter::once(self.source.name())
+            .chain(self.config.sources.enabled())
+            // the feeds source's articles are credited to their sites
+            .filter(|enabled| *enabled != sources::FEEDS);
+        let fetched = self.articles.iter().map(|a| a.source.as_str());
+        enabled
+            .chain(fetched)
+            .any(|other| other != source && !self.config.muted_sources.contains(other))
+    }
+
+    /// Mutes the source, taking its articles out of the feed
+    fn mute_source(&mut self, source: String) {
+        self.remove_articles(|a| a.source == source);
+        self.config.muted_sources.insert(source);
+    }
+
+    /// Takes the articles `remove` matches out of the feed, along with their cards' heights
+    fn remove_articles(&mut self, remove: impl Fn(&NewsCardData) -> bool) {
+        let articles = std::mem::take(&mut self.articles);
+        let heights = std::mem::take(&mut self.card_heights);
+        let new_until = std::mem::take(&mut self.new_until);
+        for (i, article) in articles.into_iter().enumerate() {
+            if remove(&article) {
+                continue;
+            }
+            if i < new_until {
+                self.new_until += 1;
+            }
+            let height = heights.get(i).copied().unwrap_or(CARD_HEIGHT_ESTIMATE);
+            self.card_heights.push(height);
+            self.articles.push(article);
+        }
+    }
+
+    /// Replaces the feed with the fetched articles, keeping the first visible one in place
+    fn swap_in_refreshed(&mut self) {
+        let heights: BTreeMap<_, _> = self
+            .articles
+            .iter()
+            .map(|a| a.url.clone())
+            .zip(self.card_heights.iter().copied())
+            .collect();
+        let incoming = std::mem::take(&mut self.incoming);
+        self.history.record(&incoming, self.config.history_size);
+        // a page under way was of the list being replaced
+        self.next_page = Some(2);
+        self.page_rx = None;
+        self.next_page_articles.clear();
+        if self.config.group_stories {
+            (self.articles, self.related) = cluster::group(incoming);
+        } else {
+            self.articles = incoming;
+            self.related.clear();
+        }
+        let view = self.config.view_key();
+        self.new_until = self
+            .visits
+            .sort_unseen(&view, &mut self.articles, Utc::now());
+        self.unseen = self.new_until > 0;
+        self.card_heights = self
+            .articles
+            .iter()
+            .map(|a| heights.get(&a.url).copied().unwrap_or(CARD_HEIGHT_ESTIMATE))
+            .collect();
+        if let Some((url, offset)) = self.anchor.take() {
+            if let Some(i) = self.articles.iter().position(|a| a.url == url) {
+                self.scroll_to = Some(self.card_heights[..i].iter().sum::<f32>() + offset);
+            }
+        }
+    }
+
+    pub fn init(mut self, cc: &CreationContext) -> Self {
+        if let Some(storage) = cc.storage {
+            let now = cc.egui_ctx.input().time;
+            self.profiles = load(storage, PROFILES_KEY, &mut self.errors, now);
+            self.choosing_profile = self.profiles.ask_at_startup && self.profiles.names.len() > 1;
+            self.config = load(storage, &self.profiles.key(APP_NAME), &mut self.errors, now);
+            self.config.migrate_dark_mode();
+            self.clamp_ui_scale();
+            self.outbox = load(storage, OUTBOX_KEY, &mut self.errors, now);
+            self.stats = load(storage, STATS_KEY, &mut self.errors, now);
+            self.trends = load(storage, TRENDS_KEY, &mut self.errors, now);
+            let in_database = eframe::get_value(storage, IN_DATABASE_KEY);
+            self.store = match Store::open(self.config.database, in_database, &cc.egui_ctx) {
+                Ok(store) => store,
+                Err(e) => {
+                    let error = format!("Failed opening the database, using the settings: {}", e);
+                    self.errors.push(HeadlinesError::Storage(error), now);
+                    Store::default()
                 }
+            };
+            self.bookmarks = load_stored(
+                &self.store,
+                storage,
+                &self.profiles.key(BOOKMARKS_KEY),
+                &mut self.errors,
+                now,
+            );
+            self.review = load(storage, REVIEW_KEY, &mut self.errors, now);
+            self.http_cache = Arc::new(Mutex::new(load_stored(
+                &self.store,
+                storage,
+                HTTP_CACHE_KEY,
+                &mut self.errors,
+                now,
+            )));
+            self.read = load_stored(&self.store, storage, READ_KEY, &mut self.errors, now);
+            self.hidden = load_stored(&self.store, storage, HIDDEN_KEY, &mut self.errors, now);
+            self.reader_settings = load(storage, READER_KEY, &mut self.errors, now);
+            self.reader_positions = load(storage, READER_POSITIONS_KEY, &mut self.errors, now);
+            self.visits = load(storage, VISITS_KEY, &mut self.errors, now);
+            self.webhook = load(storage, WEBHOOK_KEY, &mut self.errors, now);
+            self.sync = load(storage, &self.profiles.key(SYNC_KEY), &mut self.errors, now);
 
-                // desc
-                ui.add_space(PADDING);
-                let desc = Label::new(RichText::new(&a.desc).text_style(TextStyle::Button));
-                ui.add(desc);
+            // opens where the last session was left off
+            let session: Session = load(storage, SESSION_KEY, &mut self.errors, now);
+            self.query = session.query;
+            self.anchor = session.anchor;
+            let onboarded = !self.config.api_key.is_empty();
+            self.nav.restore(session.screens, onboarded);
+            if let Some(article) = session.reader.filter(|_| onboarded) {
+                self.open_reader(article, &cc.egui_ctx);
+            }
+            if onboarded {
+                let feed_key = self.profiles.key(FEED_KEY);
+                self.articles = load_stored(&self.store, storage, &feed_key, &mut self.errors, now);
+                self.card_heights = vec![CARD_HEIGHT_ESTIMATE; self.articles.len()];
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            {
+                self.history =
+                    load_stored(&self.store, storage, HISTORY_KEY, &mut self.errors, now);
+            }
+            tracing::info!(onboarded);
+        }
+        #[cfg(target_arch = "wasm32")]
+        self.follow_link(deep_link::View::current(), &cc.egui_ctx);
+        self.start(&cc.egui_ctx)
+    }
 
-                // links
-                if self.config.dark_mode {
-                    ui.style_mut().visuals.hyperlink_color = CYAN;
-                } else {
-                    ui.style_mut().visuals.hyperlink_color = RED;
-                }
-                ui.add_space(PADDING);
-                ui.with_layout(Layout::right_to_left(Align::Min), |ui| {
-                    ui.add(Hyperlink::from_label_and_url("read more ⤴", &a.url));
-                });
+    /// Opens the view of the page's url over the one the last session left
+    #[cfg(target_arch = "wasm32")]
+    fn follow_link(&mut self, view: deep_link::View, ctx: &Context) {
+        if let Some(country) = view.country() {
+            self.config.country = country;
+        }
+        if !view.query.is_empty() {
+            self.query = view.query.clone();
+        }
+        if let Some(category) = view.category.filter(|_| self.nav.onboarded()) {
+            self.categories.open(Some(category));
+            self.fetch_category(category, ctx);
+        }
+        self.link = view;
+    }
 
-                ui.add_space(PADDING);
-                ui.add(Separator::default());
+    /// Keeps the page's url on the view, for it to be bookmarked or shared
+    #[cfg(target_arch = "wasm32")]
+    fn update_link(&mut self) {
+        let country = countries::COUNTRIES[countries::index(self.config.country)].code;
+        let view = deep_link::View {
+            country: country.to_lowercase(),
+            category: self.categories.active(),
+            query: self.query.trim().to_string(),
+        };
+        if view != self.link {
+            if let Err(e) = view.show() {
+                tracing::error!("Failed updating the page's url: {}", e);
             }
+            self.link = view;
         }
     }
 
-    fn render_top_panel(&mut self, ctx: &Context, _frame: &mut Frame) {
-        TopBottomPanel::top("top_panel").show(ctx, |ui| {
-            ui.add_space(10.);
-            menu::bar(ui, |ui| {
-                // logo
-                ui.with_layout(Layout::left_to_right(Align::Min), |ui| {
-                    ui.add(Label::new(
-                        RichText::new("📓").text_style(TextStyle::Heading),
-                    ));
-                });
+    /// Starts fetching with the config as it is, `init` does once it's loaded from eframe's storage
+    pub fn start(mut self, ctx: &Context) -> Self {
+        if self.config.last_seen_version != CURRENT_VERSION {
+            // fresh installs have nothing to catch up on
+            if self.nav.onboarded() {
+                self.whats_new = changelog::releases_since(&self.config.last_seen_version);
+            }
+            if self.whats_new.is_empty() {
+                self.config.last_seen_version = CURRENT_VERSION.to_string();
+            }
+        }
 
-                // controls
-                ui.with_layout(Layout::right_to_left(Align::Min), |ui| {
-                    #[cfg(not(target_arch = "wasm32"))]
-                    let close_btn =
-                        ui.add(Button::new(RichText::new("❌").text_style(TextStyle::Body)));
-                    #[cfg(not(target_arch = "wasm32"))]
-                    if close_btn.clicked() {
-                        _frame.close();
-                    }
+        self.start_worker(ctx);
 
-                    let refresh_btn =
-                        ui.add(Button::new(RichText::new("🔄").text_style(TextStyle::Body)));
-                    if refresh_btn.clicked() {
-                        if let Some(tx) = &self.app_tx {
-                            self.articles.clear();
-                            tx.send(Msg::Refresh(self.config.country)).expect("Failed sending refresh event");
-                        }
-                    }
+        fonts::configure_fonts(ctx, &self.config.font);
+        http::set_proxy(&self.config.proxy);
 
-                    let theme_btn = ui.add(Button::new(
-                        RichText::new({
-                            if self.config.dark_mode {
-                                "🌞"
-                            } else {
-                                "🌙"
-                            }
-                        })
-                        .text_style(TextStyle::Body),
-                    ));
-                    if theme_btn.clicked() {
-                        self.config.dark_mode = !self.config.dark_mode;
-                    }
+        if self.config.privacy_mode && !privacy::set_content_protected(APP_NAME, true) {
+            tracing::error!("Failed enabling window content protection");
+        }
 
-                    let country_btn =
-                        ui.add(Button::new(RichText::new("🌐").text_style(TextStyle::Body)));
-                    if country_btn.clicked() {
-                        let country;
-                        match self.config.country {
-                            Country::US => { country = Country::FR; }
-                            Country::FR => { country = Country::US; }
-                        }
-                        self.config.country = country;
+        self
+    }
 
-                        if let Some(tx) = &self.app_tx {
-                            self.articles.clear();
-                            tx.send(Msg::Refresh(country)).expect("Failed sending refresh event");
-                        }
-                    }
+    /// Keeps the config and bookmarks of the profile in use and loads those of `name` instead
+    fn switch_profile(&mut self, name: String, ctx: &Context, frame: &mut Frame) {
+        if name == self.profiles.current {
+            return;
+        }
+        let storage = match frame.storage_mut() {
+            Some(storage) => storage,
+            None => return,
+        };
+        let now = ctx.input().time;
+        eframe::set_value(storage, &self.profiles.key(APP_NAME), &self.config);
+        let bookmarks_key = self.profiles.key(BOOKMARKS_KEY);
+        save_stored(&mut self.store, storage, &bookmarks_key, &self.bookmarks);
+        eframe::set_value(storage, &self.profiles.key(SYNC_KEY), &self.sync);
+        self.profiles.current = name;
+        self.sync = load(storage, &self.profiles.key(SYNC_KEY), &mut self.errors, now);
+        self.config = load(storage, &self.profiles.key(APP_NAME), &mut self.errors, now);
+        self.bookmarks = load_stored(
+            &self.store,
+            storage,
+            &self.profiles.key(BOOKMARKS_KEY),
+            &mut self.errors,
+            now,
+        );
+        self.reload_config(ctx);
+    }
 
-                    let settings_btn =
-                        ui.add(Button::new(RichText::new("🛠").text_style(TextStyle::Body)));
-                    if settings_btn.clicked() {
-                        self.api_key_initialized = !self.api_key_initialized;
-                    }
-                });
-            });
-            ui.add_space(10.);
-        });
+    /// Replaces the values loaded from eframe's storage by those of the database opened since
+    fn reload_stored(&mut self, now: f64) {
+        let bookmarks_key = self.profiles.key(BOOKMARKS_KEY);
+        reload(
+            &self.store,
+            &bookmarks_key,
+            &mut self.bookmarks,
+            &mut self.errors,
+            now,
+        );
+        let mut http_cache = self.http_cache.lock().unwrap();
+        reload(
+            &self.store,
+            HTTP_CACHE_KEY,
+            &mut *http_cache,
+            &mut self.errors,
+            now,
+        );
+        drop(http_cache);
+        reload(&self.store, READ_KEY, &mut self.read, &mut self.errors, now);
+        reload(
+            &self.store,
+            HIDDEN_KEY,
+            &mut self.hidden,
+            &mut self.errors,
+            now,
+        );
+        // a refresh faster than the database already replaced them
+        if self.articles.is_empty() {
+            let feed_key = self.profiles.key(FEED_KEY);
+            reload(
+                &self.store,
+                &feed_key,
+                &mut self.articles,
+                &mut self.errors,
+                now,
+            );
+            self.card_heights = vec![CARD_HEIGHT_ESTIMATE; self.articles.len()];
+        }
     }
 
-    fn render_config(&mut self, ctx: &Context) {
-        CentralPanel::default().show(ctx, |_| {
-            Window::new("Configuration").show(ctx, |ui| {
-                ui.label("Enter your API key for newsapi.org");
-                let text_input = ui.text_edit_singleline(&mut self.config.api_key);
-                if text_input.lost_focus() && ui.input().key_pressed(Key::Enter) {
-                    self.api_key_initialized = true;
-                    if let Some(tx) = &self.app_tx {
-                        tx.send(Msg::APIKeySet(self.config.api_key.to_string()))
-                            .expect("Failed sending APIKeySet event");
-                    }
-                    tracing::info!("API key set");
-                }
-                ui.label("If you haven't registered for the API key, head over to");
-                ui.hyperlink("https://newsapi.org");
-            });
+    /// Asks for a settings file to import, it's imported once it's read
+    fn pick_settings_file(&self, ctx: &Context) {
+        let imported = self.imported.clone();
+        let ctx = ctx.clone();
+        files::open_file("json", move |contents| {
+            *imported.lock().unwrap() = Some(contents);
+            ctx.request_repaint();
         });
     }
 
-    fn preload_articles(&mut self) {
-        if let Some(rx) = &self.news_rx {
-            match rx.try_recv() {
-                Ok(news_data) => {
-                    self.articles.push(news_data);
-                }
-                Err(_) => {}
+    fn export_settings(&self) -> Result<bool, String> {
+        let settings = ExportedSettings::new(&self.config, &self.bookmarks);
+        let json = serde_json::to_vec_pretty(&settings).map_err(|e| e.to_string())?;
+        files::save_file("headlines-settings.json", "application/json", &json)
+    }
+
+    /// Replaces the config and bookmarks of the current profile with those of a settings file
+    fn import_settings(&mut self, contents: &[u8], ctx: &Context) -> Result<(), String> {
+        let settings: ImportedSettings =
+            serde_json::from_slice(contents).map_err(|e| e.to_string())?;
+        if settings.version > SETTINGS_FILE_VERSION {
+            return Err(format!(
+                "the file is from a newer version of headlines ({})",
+                settings.version
+            ));
+        }
+        self.config = settings.config;
+        self.bookmarks = settings.bookmarks;
+        self.reload_config(ctx);
+        Ok(())
+    }
+
+    /// Pulls the settings of the other instances when they're newer, pushes ours otherwise
+    fn sync_settings(&mut self, ctx: &Context) {
+        let local = || {
+            let settings = ExportedSettings::new(&self.config, &self.bookmarks);
+            serde_json::to_string(&settings).map_err(|e| e.to_string())
+        };
+        let result = self.sync.poll(ctx, local).and_then(|remote| match remote {
+            Some(remote) => {
+                self.import_settings(remote.as_bytes(), ctx)?;
+                let settings = ExportedSettings::new(&self.config, &self.bookmarks);
+                let local = serde_json::to_string(&settings).map_err(|e| e.to_string())?;
+                self.sync.applied(local);
+                Ok(())
             }
+            None => Ok(()),
+        });
+        if let Err(e) = result {
+            let error = format!("Failed syncing settings: {}", e);
+            self.errors
+                .push(HeadlinesError::Network(error), ctx.input().time);
         }
     }
 
-    pub fn init(mut self, cc: &CreationContext) -> Self {
-        if let Some(storage) = cc.storage {
-            self.config = eframe::get_value(storage, APP_NAME).unwrap_or_default();
-            self.api_key_initialized = !self.config.api_key.is_empty();
-            tracing::info!(self.api_key_initialized);
+    /// Applies a config that was swapped in and fetches the feed it asks for
+    fn reload_config(&mut self, ctx: &Context) {
+        let now = ctx.input().time;
+        self.config.migrate_dark_mode();
+        self.clamp_ui_scale();
+        if self.config.api_key.is_empty() {
+            self.nav.restart();
+        } else if !self.nav.onboarded() {
+            self.nav.home();
+        }
+
+        // the feed is the other config's to fetch
+        self.articles.clear();
+        self.related.clear();
+        self.card_heights.clear();
+        self.incoming.clear();
+        self.next_page = None;
+        self.page_rx = None;
+        self.next_page_articles.clear();
+        self.anchor = None;
+        self.new_until = 0;
+        self.unseen = false;
+        self.answered = 0;
+        self.refresh_failed = false;
+        self.refreshing = self.nav.onboarded();
+        self.refresh_started = now;
+
+        fonts::configure_fonts(ctx, &self.config.font);
+        http::set_proxy(&self.config.proxy);
+        if !privacy::set_content_protected(APP_NAME, self.config.privacy_mode) && privacy::SUPPORTED
+        {
+            tracing::error!("Failed changing window content protection");
         }
+        self.start_worker(ctx);
+    }
 
-        let api_key = self.config.api_key.to_string();
+    /// Starts a fetch worker, and a fetch if there's a key. A worker there was is left to stop
+    /// on its own once it finds its channels gone.
+    fn start_worker(&mut self, ctx: &Context) {
+        let mut state = self.fetch_state(self.config.countries());
+        state.query = self.query.trim().to_string();
+        state.sources = self.config.sources.clone();
+        let now = ctx.input().time;
+        self.worker_started = now;
+        // the worker wakes the UI up when articles arrive, it doesn't repaint on its own
+        let ctx = ctx.clone();
 
         #[cfg(not(target_arch = "wasm32"))]
         let (mut news_tx, news_rx) = channel();
@@ -239,93 +3109,543 @@ impl Headlines {
 
         let (app_tx, app_rx) = sync_channel(1);
         self.app_tx = Some(app_tx);
+        // the new worker starts from the config, what was queued for the last one is in it
+        self.worker_queue = WorkerQueue::default();
 
         #[cfg(not(target_arch = "wasm32"))]
-        thread::spawn(move || {
-            if !api_key.is_empty() {
-                fetch_news(&api_key, self.config.country, &mut news_tx);
-            }
-            loop {
-                match app_rx.recv() {
-                    Ok(Msg::APIKeySet(api_key)) => {
-                        fetch_news(&api_key, self.config.country, &mut news_tx);
-                    }
-                    Ok(Msg::Refresh(country)) => {
-                        fetch_news(&api_key, country, &mut news_tx);
-                    }
-                    Err(e) => {
-                        tracing::error!("Failed receiving msg: {}", e);
+        let spawned = thread::Builder::new()
+            .name("fetch-worker".to_string())
+            .spawn(move || {
+                let mut step = state.start();
+                while step != Step::Stop {
+                    if step == Step::Fetch {
+                        fetch_guarded(&state, &mut news_tx, &ctx);
                     }
+                    // a dropped sender means the app is gone too
+                    step = app_rx.recv().map_or(Step::Stop, |msg| state.handle(msg));
                 }
-            }
-        });
+                tracing::info!("Stopping the fetch worker");
+            });
+        // the next refresh tries again
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Err(e) = spawned {
+            let error = format!("Failed starting the fetch worker: {}", e);
+            self.errors.push(HeadlinesError::Internal(error), now);
+            self.app_tx = None;
+            self.news_rx = None;
+            self.refreshing = false;
+        }
 
         #[cfg(target_arch = "wasm32")]
         {
-            let api_key_web = api_key.clone();
-            let news_tx_web = news_tx.clone();
-            gloo_timers::callback::Timeout::new(10, move || {
-                wasm_bindgen_futures::spawn_local(async move {
-                    fetch_web(api_key_web, self.config.country, news_tx_web).await;
-                });
-            })
-            .forget();
+            if state.start() == Step::Fetch {
+                let state_web = state.clone();
+                let news_tx_web = news_tx.clone();
+                let ctx_web = ctx.clone();
+                gloo_timers::callback::Timeout::new(10, move || {
+                    wasm_bindgen_futures::spawn_local(fetch_web(state_web, news_tx_web, ctx_web));
+                })
+                .forget();
+            }
 
-            gloo_timers::callback::Interval::new(500, move || match app_rx.try_recv() {
-                Ok(Msg::APIKeySet(api_key)) => {
-                    wasm_bindgen_futures::spawn_local(fetch_web(api_key.clone(), self.config.country, news_tx.clone()));
-                }
-                Ok(Msg::Refresh(country)) => {
-                    wasm_bindgen_futures::spawn_local(fetch_web(api_key.clone(), country, news_tx.clone()));
-                }
-                Err(e) => {
-                    tracing::error!("Failed receiving msg: {}", e);
+            let worker = gloo_timers::callback::Interval::new(500, move || {
+                if let Ok(msg) = app_rx.try_recv() {
+                    // stopping needs nothing, the app cancels the interval when it drops it
+                    if state.handle(msg) == Step::Fetch {
+                        wasm_bindgen_futures::spawn_local(fetch_web(
+                            state.clone(),
+                            news_tx.clone(),
+                            ctx.clone(),
+                        ));
+                    }
                 }
-            })
-            .forget();
+            });
+            self.worker = Some(worker);
         }
+    }
 
-        self.configure_fonts(&cc.egui_ctx);
+    /// Hands the queued messages to the fetch worker, trying again shortly for those it has no
+    /// room for yet
+    fn flush_worker_queue(&mut self, ctx: &Context) {
+        let tx = match &self.app_tx {
+            Some(tx) => tx,
+            None => return,
+        };
+        match self.worker_queue.flush(tx) {
+            Ok(true) => ctx.request_repaint_after(WORKER_QUEUE_RETRY),
+            Ok(false) => {}
+            Err(e) => self.recover_worker(ctx, &e),
+        }
+    }
 
-        self
+    /// Replaces a fetch worker that stopped, unless the last one replaced stopped right away too,
+    /// which is left for the next refresh to try again
+    fn recover_worker(&mut self, ctx: &Context, reason: &str) {
+        let now = ctx.input().time;
+        if now - self.worker_started < WORKER_RESTART_BACKOFF {
+            let error = format!(
+                "The fetch worker {} right after starting, refresh to try again",
+                reason
+            );
+            self.errors.push(HeadlinesError::Internal(error), now);
+            self.app_tx = None;
+            self.news_rx = None;
+            self.refreshing = false;
+            return;
+        }
+        self.refreshing = self.nav.onboarded();
+        self.refresh_started = now;
+        self.start_worker(ctx);
+        let notice = format!("The fetch worker {}, a new one took over", reason);
+        self.errors.notify(notice, now);
     }
-}
 
-impl App for Headlines {
-    fn update(&mut self, ctx: &Context, frame: &mut Frame) {
-        ctx.request_repaint();
+    /// Restarts the fetch worker when the fetch under way stopped making progress
+    fn watch_fetch(&mut self, ctx: &Context) {
+        if !self.refreshing || self.config.api_key.is_empty() {
+            return;
+        }
+        let now = ctx.input().time;
+        let idle = now - self.last_progress.max(self.refresh_started);
+        let timeout = self.config.retry.stall_timeout();
+        if idle < timeout {
+            ctx.request_repaint_after(Duration::from_secs_f64(timeout - idle));
+            return;
+        }
+        let error = format!(
+            "the fetch made no progress for {:.0} s, restarting it",
+            idle
+        );
+        self.errors.push(HeadlinesError::Timeout(error), now);
+        self.incoming.clear();
+        self.answered = 0;
+        self.refresh_started = now;
+        self.start_worker(ctx);
+    }
+
+    /// Draws a frame of the app with a bare egui context, for driving it in tests without a
+    /// window. What needs eframe's window is skipped: the system theme, its scale, the tray, the
+    /// ticker, quitting and switching profiles, which reaches the storage.
+    pub fn show(&mut self, ctx: &Context) {
+        self.run(ctx, None);
+    }
 
-        if self.config.dark_mode {
-            ctx.set_visuals(Visuals::dark());
+    fn run(&mut self, ctx: &Context, mut frame: Option<&mut Frame>) {
+        let system_theme = frame.as_ref().and_then(|frame| frame.info().system_theme);
+        self.dark_mode = self
+            .config
+            .theme_mode
+            .is_dark(system_theme, &self.config.night_schedule);
+        if self.config.theme_mode == ThemeMode::Scheduled {
+            // catches the switch at sunset or sunrise without waiting for input
+            ctx.request_repaint_after(SCHEDULE_CHECK_INTERVAL);
+        }
+        ctx.set_visuals(
+            self.theme()
+                .visuals(self.dark_mode, self.config.high_contrast),
+        );
+        let animation_time = if self.config.reduced_motion {
+            0.
         } else {
-            ctx.set_visuals(Visuals::light());
+            Style::default().animation_time
+        };
+        if ctx.style().animation_time != animation_time {
+            let mut style = (*ctx.style()).clone();
+            style.animation_time = animation_time;
+            ctx.set_style(style);
+        }
+
+        self.zoom(ctx);
+        if let Some(frame) = &frame {
+            self.apply_ui_scale(ctx, frame);
+        }
+
+        if self.choosing_profile {
+            if let Some(name) = self.profiles.picker(ctx) {
+                self.choosing_profile = false;
+                self.pending_profile = Some(name);
+            }
+        }
+        if let Some(frame) = frame.as_deref_mut() {
+            if let Some(name) = self.pending_profile.take() {
+                self.switch_profile(name, ctx, frame);
+            }
+        }
+        self.sync_settings(ctx);
+        match self.store.poll() {
+            Some(Ok(())) => self.reload_stored(ctx.input().time),
+            Some(Err(e)) => {
+                let error = format!("Failed opening the database, using the settings: {}", e);
+                self.errors
+                    .push(HeadlinesError::Storage(error), ctx.input().time);
+            }
+            None => {}
+        }
+        if self.cleanup.poll(ctx, &self.config.retention) {
+            let cutoff = self.config.retention.cutoff();
+            self.http_cache.lock().unwrap().expire(cutoff);
+            self.history.expire(cutoff);
+            self.hidden.retain(|_, hidden_at| *hidden_at >= cutoff);
+        }
+        let imported = self.imported.lock().unwrap().take();
+        if let Some(contents) = imported {
+            match contents.and_then(|contents| self.import_settings(&contents, ctx)) {
+                Ok(()) => {
+                    let notice = format!(
+                        "Settings imported into the {} profile",
+                        self.profiles.current
+                    );
+                    self.errors.notify(notice, ctx.input().time);
+                }
+                Err(e) => {
+                    let error = format!("Failed importing settings: {}", e);
+                    self.errors
+                        .push(HeadlinesError::Storage(error), ctx.input().time);
+                }
+            }
+        }
+        #[cfg(target_arch = "wasm32")]
+        self.update_link();
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(frame) = frame.as_deref_mut() {
+            if let Err(e) = self.close.update(ctx, frame, self.config.close_action) {
+                let error = format!("Failed creating the tray icon: {}", e);
+                self.errors
+                    .push(HeadlinesError::Internal(error), ctx.input().time);
+            }
+            // the ticker has decorations of its own
+            let title_bar = (!self.ticker.is_active()).then_some(self.config.custom_title_bar);
+            self.title_bar.update(frame, title_bar);
+            if title_bar == Some(true) && self.title_bar.show(ctx, frame) {
+                self.close_window(ctx, frame);
+            }
+            if let Err(e) = self.hotkey.update(ctx, frame, &self.config.global_hotkey) {
+                let error = format!("Failed registering the hotkey: {}", e);
+                self.errors
+                    .push(HeadlinesError::Internal(error), ctx.input().time);
+            }
+        }
+
+        match self.wallabag.poll(&self.config.wallabag, ctx) {
+            Ok(requests) => {
+                for request in requests {
+                    self.outbox.push(request);
+                }
+            }
+            Err(e) => {
+                let error = format!("Failed logging in to wallabag: {}", e);
+                self.errors
+                    .push(HeadlinesError::from_fetch(error), ctx.input().time);
+            }
+        }
+        if let Some(delay) = self.outbox.poll(ctx.input().time) {
+            ctx.request_repaint_after(Duration::from_secs_f64(delay.max(0.)));
+        }
+        for report in self.outbox.take_reports() {
+            let now = ctx.input().time;
+            match report.error {
+                None => self.errors.notify(format!("{}: done", report.label), now),
+                Some(e) => {
+                    let error = format!("{} failed: {}", report.label, e);
+                    self.errors.push(HeadlinesError::from_fetch(error), now);
+                }
+            }
+        }
+        for e in self.quotes.poll(ctx, &self.config.quotes) {
+            let error = format!("Failed fetching prices: {}", e);
+            self.errors
+                .push(HeadlinesError::from_fetch(error), ctx.input().time);
+        }
+        let now = ctx.input().time;
+        if let Some(delay) = self.speech.poll(now, self.config.speech_rate) {
+            ctx.request_repaint_after(Duration::from_secs_f64(delay));
         }
 
-        if !self.api_key_initialized {
+        if !self.nav.onboarded() {
+            self.render_onboarding(ctx);
+        } else if self.nav.is(&Screen::Settings) {
             self.render_config(ctx);
         } else {
-            self.preload_articles();
+            self.flush_worker_queue(ctx);
+            self.preload_articles(ctx);
+            self.receive_next_page(ctx);
+            self.watch_fetch(ctx);
+            self.comparison.poll();
+            self.categories.poll();
+
+            #[cfg(not(target_arch = "wasm32"))]
+            if let Some(frame) = frame.as_deref_mut().filter(|_| self.ticker.is_active()) {
+                self.ticker
+                    .show(ctx, frame, &self.articles, &self.config.ticker);
+                return;
+            }
+
+            let (refresh_keys, back_keys) = {
+                let mut input = ctx.input_mut();
+                let refresh = input.consume_key(Modifiers::NONE, Key::F5)
+                    || input.consume_key(Modifiers::COMMAND, Key::R);
+                (refresh, input.consume_key(Modifiers::ALT, Key::ArrowLeft))
+            };
+            if refresh_keys && !self.refreshing {
+                self.refresh(ctx);
+            }
+            if back_keys {
+                self.go_back();
+            }
 
             self.render_top_panel(ctx, frame);
+            if self.config.quotes.enabled {
+                TopBottomPanel::top("quotes")
+                    .show(ctx, |ui| self.quotes.show(ui, &self.config.quotes));
+            }
+
+            if !self.whats_new.is_empty() {
+                self.render_whats_new(ctx);
+            }
 
             render_footer(ctx);
+            if self.demo
+                && TopBottomPanel::bottom("demo")
+                    .show(ctx, demo::show_banner)
+                    .inner
+            {
+                self.leave_demo();
+            }
 
-            CentralPanel::default().show(ctx, |ui| {
-                render_header(ui);
-                ScrollArea::vertical().show(ui, |ui| {
-                    self.render_news_cards(ui);
+            if self.config.marquee && !self.nav.is(&Screen::Reader) {
+                let speed = if self.config.reduced_motion {
+                    0.
+                } else {
+                    self.config.marquee_speed
+                };
+                let clicked = TopBottomPanel::bottom("marquee")
+                    .show(ctx, |ui| self.marquee.show(ui, &self.articles, speed))
+                    .inner;
+                if let Some(i) = clicked {
+                    self.scroll_to_card(i);
+                }
+            }
+
+            CentralPanel::default().show(ctx, |ui| self.render_screen(ui));
+            if self.demo {
+                demo::paint_watermark(ctx);
+            }
+            if self.show_stats {
+                self.render_stats(ctx);
+            }
+            Window::new("Past refreshes")
+                .open(&mut self.show_history)
+                .show(ctx, |ui| {
+                    // grouped stories are still part of the feed
+                    let current: Vec<_> = self
+                        .articles
+                        .iter()
+                        .chain(self.related.values().flatten())
+                        .collect();
+                    self.history
+                        .show(ui, &current, &mut self.config.history_size);
                 });
-            });
+            Window::new("Search saved headlines")
+                .open(&mut self.show_local_search)
+                .show(ctx, |ui| {
+                    self.local_search.show(ui, &self.bookmarks, &self.history);
+                });
+
+            self.render_details(ctx);
+            self.render_qr_code(ctx);
+            if let Some(request) = self.composer.show(ctx, &self.config.mastodon) {
+                self.outbox.push(request);
+            }
         }
+
+        self.errors.show_toasts(ctx);
+
+        match drop_import::take(ctx) {
+            Some(Ok(dropped)) => self.pending_import = Some(dropped),
+            Some(Err(e)) => {
+                let error = format!("Failed importing the dropped file {}", e);
+                self.errors
+                    .push(HeadlinesError::Storage(error), ctx.input().time);
+            }
+            None => {}
+        }
+        drop_import::paint_hover(ctx);
+        self.render_import_confirmation(ctx);
+
+        // every link opened this frame, whatever the widget, goes through the link settings
+        let requested = ctx.output().open_url.take();
+        if let Some(link) = requested {
+            if self.config.confirm_links {
+                self.pending_link = Some(link);
+            } else {
+                self.open_link(ctx, link);
+            }
+        }
+        // after the interception, a confirmed link is opened for good
+        self.render_link_confirmation(ctx);
+    }
+}
+
+impl App for Headlines {
+    fn update(&mut self, ctx: &Context, frame: &mut Frame) {
+        self.run(ctx, Some(frame));
     }
 
     fn save(&mut self, storage: &mut dyn Storage) {
-        eframe::set_value(storage, "headlines", &self.config);
+        eframe::set_value(storage, PROFILES_KEY, &self.profiles);
+        eframe::set_value(storage, &self.profiles.key(APP_NAME), &self.config);
+        eframe::set_value(storage, OUTBOX_KEY, &self.outbox);
+        eframe::set_value(storage, STATS_KEY, &self.stats);
+        eframe::set_value(storage, TRENDS_KEY, &self.trends);
+        let bookmarks_key = self.profiles.key(BOOKMARKS_KEY);
+        save_stored(&mut self.store, storage, &bookmarks_key, &self.bookmarks);
+        eframe::set_value(storage, REVIEW_KEY, &self.review);
+        let http_cache = self.http_cache.lock().unwrap();
+        save_stored(&mut self.store, storage, HTTP_CACHE_KEY, &*http_cache);
+        drop(http_cache);
+        save_stored(&mut self.store, storage, READ_KEY, &self.read);
+        save_stored(&mut self.store, storage, HIDDEN_KEY, &self.hidden);
+        eframe::set_value(storage, READER_KEY, &self.reader_settings);
+        eframe::set_value(storage, VISITS_KEY, &self.visits);
+        eframe::set_value(storage, WEBHOOK_KEY, &self.webhook);
+        eframe::set_value(storage, &self.profiles.key(SYNC_KEY), &self.sync);
+        let session = Session {
+            query: self.query.clone(),
+            anchor: self.anchor.clone(),
+            reader: self.reader.as_ref().map(|reader| reader.article.clone()),
+            screens: self.nav.stack().to_vec(),
+        };
+        eframe::set_value(storage, SESSION_KEY, &session);
+        #[cfg(not(target_arch = "wasm32"))]
+        save_stored(&mut self.store, storage, HISTORY_KEY, &self.history);
+        // the samples aren't headlines to start with
+        if !self.demo {
+            let feed_key = self.profiles.key(FEED_KEY);
+            save_stored(&mut self.store, storage, &feed_key, &self.articles);
+        }
+        // the next launch reads each value where it was saved, the database setting aside
+        if let Some(keys) = self.store.in_database() {
+            eframe::set_value(storage, IN_DATABASE_KEY, keys);
+        }
+        let readers: Vec<_> = self
+            .reader
+            .iter()
+            .chain(&self.reader_back)
+            .map(|reader| (reader.article.url.clone(), reader.progress()))
+            .collect();
+        for (url, progress) in readers {
+            self.remember_position(url, progress);
+        }
+        eframe::set_value(storage, READER_POSITIONS_KEY, &self.reader_positions);
+    }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        if let Some(tx) = self.app_tx.take() {
+            // a busy worker finds the channel disconnected instead
+            let _ = tx.try_send(Msg::Shutdown);
+        }
+        #[cfg(target_arch = "wasm32")]
+        if let Some(worker) = self.worker.take() {
+            worker.cancel();
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn on_close_event(&mut self) -> bool {
+        self.close.on_close_event(self.config.close_action)
     }
 
     fn persist_native_window(&self) -> bool {
         false
     }
+
+    fn clear_color(&self, _visuals: &egui::Visuals) -> egui::Rgba {
+        // the panels paint over it, only the ticker's translucent background lets it through
+        egui::Rgba::TRANSPARENT
+    }
+}
+
+/// What reading an article aloud says
+fn speech_text(article: &NewsCardData) -> String {
+    format!("{}. {}", article.title, article.desc)
+}
+
+/// Title of one of the feed's or the bookmarks' articles
+fn known_title(articles: &[NewsCardData], bookmarks: &Bookmarks, url: &str) -> Option<String> {
+    articles
+        .iter()
+        .chain(bookmarks.iter().map(|b| &b.article))
+        .find(|a| a.url == url)
+        .map(|a| a.title.clone())
+}
+
+/// Reads a value the store may keep, from eframe's storage when it doesn't have it yet
+fn load_stored<T: DeserializeOwned + Default>(
+    store: &Store,
+    storage: &dyn Storage,
+    key: &str,
+    errors: &mut ErrorLog,
+    now: f64,
+) -> T {
+    match store.load(key) {
+        Some(Ok(value)) => value,
+        Some(Err(e)) => {
+            let error = format!("Failed reading the stored {}, starting over: {}", key, e);
+            errors.push(HeadlinesError::Storage(error), now);
+            T::default()
+        }
+        None => load(storage, key, errors, now),
+    }
+}
+
+/// Replaces `value` by the one the store keeps, if it has one
+fn reload<T: DeserializeOwned>(
+    store: &Store,
+    key: &str,
+    value: &mut T,
+    errors: &mut ErrorLog,
+    now: f64,
+) {
+    match store.load(key) {
+        Some(Ok(stored)) => *value = stored,
+        Some(Err(e)) => {
+            let error = format!("Failed reading the stored {}: {}", key, e);
+            errors.push(HeadlinesError::Storage(error), now);
+        }
+        None => {}
+    }
+}
+
+/// Saves a value in the store, or in eframe's storage when it's the one keeping it or the store
+/// failed
+fn save_stored<T: Serialize>(store: &mut Store, storage: &mut dyn Storage, key: &str, value: &T) {
+    match store.save(key, value) {
+        Ok(true) => {}
+        Ok(false) => eframe::set_value(storage, key, value),
+        Err(e) => {
+            tracing::error!("Failed storing the {}: {}", key, e);
+            eframe::set_value(storage, key, value);
+        }
+    }
+}
+
+/// Reads a persisted value, a value that is there but unreadable is reported and replaced
+fn load<T: DeserializeOwned + Default>(
+    storage: &dyn Storage,
+    key: &str,
+    errors: &mut ErrorLog,
+    now: f64,
+) -> T {
+    if let Some(value) = eframe::get_value(storage, key) {
+        value
+    } else {
+        if storage.get_string(key).is_some() {
+            let error = format!("Failed reading the saved {}, starting over", key);
+            errors.push(HeadlinesError::Storage(error), now);
+        }
+        T::default()
+    }
 }
 
 fn render_header(ui: &mut Ui) {
@@ -364,37 +3684,155 @@ fn render_footer(ctx: &Context) {
     });
 }
 
+/// Fetches once outside the worker, the articles come through the returned receiver
+fn spawn_fetch(state: FetchState, ctx: &Context) -> Receiver<Fetched> {
+    let (news_tx, news_rx) = channel();
+    let ctx = ctx.clone();
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let failed_tx = news_tx.clone();
+        let spawned = thread::Builder::new()
+            .name("side-fetch".to_string())
+            .spawn(move || {
+                let mut news_tx = news_tx;
+                fetch_guarded(&state, &mut news_tx, &ctx);
+            });
+        if let Err(e) = spawned {
+            let error = format!("Failed starting the fetch: {}", e);
+            send(Fetched::Failed(HeadlinesError::Internal(error)), &failed_tx);
+            send(Fetched::Done, &failed_tx);
+        }
+    }
+    #[cfg(target_arch = "wasm32")]
+    wasm_bindgen_futures::spawn_local(fetch_web(state, news_tx, ctx));
+    news_rx
+}
+
 #[cfg(not(target_arch = "wasm32"))]
-fn fetch_news(api_key: &str, country: Country, news_tx: &mut Sender<NewsCardData>) {
-    if let Ok(response) = NewsAPI::new(api_key).country(country).fetch() {
-        generate_news_card_data(&response, news_tx);
+fn fetch_news(state: &FetchState, news_tx: &mut Sender<Fetched>, ctx: &Context) {
+    let mut batches = Vec::new();
+    for &country in &state.countries {
+        match pollster::block_on(state.source.top_headlines(state, country)) {
+            Ok(articles) => batches.push((state.source.name(), articles)),
+            Err(e) => {
+                if !send_fetch_error(e, news_tx) {
+                    break;
+                }
+            }
+        }
+        send(Fetched::Progress, news_tx);
+    }
+    // the other sources have a single page, already in the feed
+    let sources = if state.page == 1 {
+        state.sources.enabled()
     } else {
-        tracing::error!("Failed fetching news");
+        Vec::new()
+    };
+    for source in sources {
+        let limit = state.source_caps.cap(source);
+        let timeout = state.retry.timeout();
+        let fetched = pollster::block_on(sources::fetch(source, &state.sources, limit, timeout));
+        add_source_batch(&mut batches, source, fetched, state, news_tx);
+    }
+    send_articles(batches, state, news_tx);
+    send(Fetched::Done, news_tx);
+    ctx.request_repaint();
+}
+
+/// Fetches like `fetch_news`, a panic on the way ending the fetch with a failure instead of
+/// leaving the feed waiting for it
+#[cfg(not(target_arch = "wasm32"))]
+fn fetch_guarded(state: &FetchState, news_tx: &mut Sender<Fetched>, ctx: &Context) {
+    let fetched = panic::catch_unwind(AssertUnwindSafe(|| fetch_news(state, news_tx, ctx)));
+    if let Err(panic) = fetched {
+        let message = panic
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| panic.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        let error = HeadlinesError::Internal(format!("the fetch crashed: {}", message));
+        send(Fetched::Failed(error), news_tx);
+        send(Fetched::Done, news_tx);
+        ctx.request_repaint();
     }
 }
 
 #[cfg(target_arch = "wasm32")]
-async fn fetch_web(api_key: String, country: Country, news_tx: Sender<NewsCardData>) {
-    if let Ok(response) = NewsAPI::new(&api_key).country(country).fetch_web().await {
-        generate_news_card_data(&response, &news_tx);
+async fn fetch_web(state: FetchState, news_tx: Sender<Fetched>, ctx: Context) {
+    let mut batches = Vec::new();
+    for &country in &state.countries {
+        match state.source.top_headlines(&state, country).await {
+            Ok(articles) => batches.push((state.source.name(), articles)),
+            Err(e) => {
+                if !send_fetch_error(e, &news_tx) {
+                    break;
+                }
+            }
+        }
+        send(Fetched::Progress, &news_tx);
+    }
+    // the other sources have a single page, already in the feed
+    let sources = if state.page == 1 {
+        state.sources.enabled()
     } else {
-        tracing::error!("Failed fetching news");
+        Vec::new()
+    };
+    for source in sources {
+        let limit = state.source_caps.cap(source);
+        let timeout = state.retry.timeout();
+        let fetched = sources::fetch(source, &state.sources, limit, timeout).await;
+        add_source_batch(&mut batches, source, fetched, &state, &news_tx);
     }
+    send_articles(batches, &state, &news_tx);
+    send(Fetched::Done, &news_tx);
+    ctx.request_repaint();
 }
 
-fn generate_news_card_data(response: &NewsAPIResponse, news_tx: &Sender<NewsCardData>) {
-    for article in response.articles() {
-        let news = NewsCardData {
-            title: article.title().to_string(),
-            desc: article
-                .description()
-                .map(|s| s.to_string())
-                .unwrap_or("...".to_string()),
-            url: article.url().to_string(),
-        };
-        if let Err(e) = news_tx.send(news) {
-            tracing::error!("Error sending news data: {}", e);
+/// Keeps the articles of a source besides newsapi.org that match the query, or reports its failure
+fn add_source_batch(
+    batches: &mut Vec<(&'static str, Vec<NewsCardData>)>,
+    source: &'static str,
+    fetched: Result<Vec<NewsCardData>, HeadlinesError>,
+    state: &FetchState,
+    news_tx: &Sender<Fetched>,
+) {
+    match fetched {
+        Ok(articles) => {
+            let articles = articles
+                .into_iter()
+                .filter(|news| state.matches(&news.title, &news.desc))
+                .collect();
+            batches.push((source, articles));
         }
+        Err(e) => send(Fetched::Failed(e), news_tx),
     }
+    send(Fetched::Progress, news_tx);
+}
+
+/// Interleaves the articles of the countries and sources, each country capped like a source of
+/// its own
+fn send_articles(
+    batches: Vec<(&str, Vec<NewsCardData>)>,
+    state: &FetchState,
+    news_tx: &Sender<Fetched>,
+) {
+    let merged = aggregate::merge(batches, &state.source_caps);
+    send(Fetched::Articles(merged), news_tx);
 }
 
+/// Reports a failed request, returns whether requesting the next countries is worth it
+fn send_fetch_error(error: HeadlinesError, news_tx: &Sender<Fetched>) -> bool {
+    // the key is refused, or the quota used up, for every country alike
+    let go_on = !matches!(
+        error,
+        HeadlinesError::Auth(_) | HeadlinesError::RateLimited(_)
+    );
+    send(Fetched::Failed(error), news_tx);
+    go_on
+}
+
+fn send(fetched: Fetched, news_tx: &Sender<Fetched>) {
+    if let Err(e) = news_tx.send(fetched) {
+        tracing::error!("Error sending fetched news: {}", e);
+    }
+}
@@ -0,0 +1,145 @@
+use crate::news::NewsCardData;
+use chrono::{DateTime, Local, Utc};
+use eframe::egui::{ComboBox, DragValue, Hyperlink, ScrollArea, Ui};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, VecDeque};
+
+const MAX_SNAPSHOTS: usize = 100;
+const LIST_HEIGHT: f32 = 300.;
+
+#[derive(Serialize, Deserialize)]
+struct Headline {
+    title: String,
+    url: String,
+}
+
+/// The headlines a refresh brought
+#[derive(Serialize, Deserialize)]
+struct Snapshot {
+    taken_at: DateTime<Utc>,
+    headlines: Vec<Headline>,
+}
+
+impl Snapshot {
+    fn label(&self) -> String {
+        format!(
+            "{}, {} headlines",
+            self.taken_at.with_timezone(&Local).format("%a %d %b %H:%M"),
+            self.headlines.len()
+        )
+    }
+}
+
+/// The headlines of the last refreshes, newest first
+#[derive(Default, Serialize, Deserialize)]
+pub struct History {
+    snapshots: VecDeque<Snapshot>,
+    /// Index of the snapshot being browsed
+    #[serde(skip)]
+    selected: usize,
+    /// Whether only the headlines that came or went since the snapshot are listed
+    #[serde(skip)]
+    only_changes: bool,
+}
+
+impl History {
+    /// Keeps the headlines of a refresh, forgetting the oldest ones beyond `limit` refreshes
+    pub fn record(&mut self, articles: &[NewsCardData], limit: usize) {
+        let headlines = articles
+            .iter()
+            .map(|a| Headline {
+                title: a.title.clone(),
+                url: a.url.clone(),
+            })
+            .collect();
+        self.snapshots.push_front(Snapshot {
+            taken_at: Utc::now(),
+            headlines,
+        });
+        self.snapshots.truncate(limit.max(1));
+    }
+
+    /// Forgets the refreshes older than `cutoff`
+    pub fn expire(&mut self, cutoff: DateTime<Utc>) {
+        self.snapshots
+            .retain(|snapshot| snapshot.taken_at >= cutoff);
+        self.selected = self.selected.min(self.snapshots.len().saturating_sub(1));
+    }
+
+    /// When, title and url of every headline kept, the newest refresh first
+    pub fn headlines(&self) -> impl Iterator<Item = (DateTime<Utc>, &str, &str)> {
+        self.snapshots.iter().flat_map(|snapshot| {
+            snapshot
+                .headlines
+                .iter()
+                .map(|h| (snapshot.taken_at, h.title.as_str(), h.url.as_str()))
+        })
+    }
+
+    /// Lets a past refresh be picked and compared with the feed's `current` articles
+    pub fn show(&mut self, ui: &mut Ui, current: &[&NewsCardData], limit: &mut usize) {
+        ui.horizontal(|ui| {
+            ui.label("Keep the last");
+            ui.add(DragValue::new(limit).clamp_range(1..=MAX_SNAPSHOTS));
+            ui.label("refreshes");
+        });
+        self.snapshots.truncate(*limit);
+        if self.snapshots.is_empty() {
+            ui.weak("No refresh yet");
+            return;
+        }
+        self.selected = self.selected.min(self.snapshots.len() - 1);
+
+        ui.horizontal(|ui| {
+            ComboBox::from_id_source("history_snapshot")
+                .selected_text(self.snapshots[self.selected].label())
+                .show_ui(ui, |ui| {
+                    for (i, snapshot) in self.snapshots.iter().enumerate() {
+                        ui.selectable_value(&mut self.selected, i, snapshot.label());
+                    }
+                });
+            ui.checkbox(&mut self.only_changes, "Only what changed");
+        });
+        ui.separator();
+
+        let snapshot = &self.snapshots[self.selected];
+        let then: BTreeSet<_> = snapshot.headlines.iter().map(|h| h.url.as_str()).collect();
+        let now: BTreeSet<_> = current.iter().map(|a| a.url.as_str()).collect();
+        let arrived: Vec<_> = current
+            .iter()
+            .filter(|a| !then.contains(a.url.as_str()))
+            .collect();
+        let gone: Vec<_> = snapshot
+            .headlines
+            .iter()
+            .filter(|h| !now.contains(h.url.as_str()))
+            .collect();
+
+        ScrollArea::vertical()
+            .max_height(LIST_HEIGHT)
+            .show(ui, |ui| {
+                ui.strong(format!("{} new since then", arrived.len()));
+                for a in arrived {
+                    ui.add(Hyperlink::from_label_and_url(
+                        format!("+ {}", a.title),
+                        &a.url,
+                    ));
+                }
+                ui.add_space(8.);
+                ui.strong(format!("{} gone since then", gone.len()));
+                for h in gone {
+                    ui.add(Hyperlink::from_label_and_url(
+                        format!("− {}", h.title),
+                        &h.url,
+                    ));
+                }
+                if !self.only_changes {
+                    ui.add_space(8.);
+                    ui.strong("The headlines then");
+                    for h in &snapshot.headlines {
+                        ui.add(Hyperlink::from_label_and_url(&h.title, &h.url));
+                    }
+                }
+            });
+    }
+}
@@ -0,0 +1,116 @@
+use eframe::egui::{Context, TextEdit, Ui};
+use eframe::Frame;
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+use std::time::Duration;
+
+/// Whether the platform has global hotkeys, those of X11 on Linux
+pub const SUPPORTED: bool = cfg!(any(windows, target_os = "macos", target_os = "linux"));
+
+/// How often the hotkey is checked for, the window may be hidden and get no input
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Hotkey showing and hiding the window from anywhere, registered while it's set
+#[derive(Default)]
+pub struct GlobalHotkey {
+    /// Hotkey last registered or tried, as it's set
+    registered: String,
+    /// Hotkey being typed in the settings
+    draft: Option<String>,
+    hidden: bool,
+    #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+    registration: Option<imp::Registration>,
+}
+
+impl GlobalHotkey {
+    /// Registers `hotkey` when it changed, none when it's empty, and shows or hides the window
+    /// when it's pressed
+    pub fn update(
+        &mut self,
+        _ctx: &Context,
+        _frame: &mut Frame,
+        hotkey: &str,
+    ) -> Result<(), String> {
+        #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+        {
+            if hotkey != self.registered {
+                self.registered = hotkey.to_string();
+                // unregistered before the next one, which may be the same key combination
+                self.registration = None;
+                if !hotkey.trim().is_empty() {
+                    self.registration = Some(imp::Registration::new(hotkey.trim())?);
+                }
+            }
+            if let Some(registration) = &self.registration {
+                _ctx.request_repaint_after(POLL_INTERVAL);
+                if registration.pressed() {
+                    self.hidden = !self.hidden;
+                    _frame.set_visible(!self.hidden);
+                    if !self.hidden {
+                        _frame.set_minimized(false);
+                    }
+                }
+            }
+        }
+        #[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
+        {
+            self.registered = hotkey.to_string();
+        }
+        Ok(())
+    }
+
+    /// Field setting the hotkey, like `Ctrl+Shift+H`, applied once it's typed
+    pub fn show_settings(&mut self, ui: &mut Ui, hotkey: &mut String) {
+        let draft = self.draft.get_or_insert_with(|| hotkey.clone());
+        let mut typed = false;
+        ui.horizontal(|ui| {
+            ui.label("Show and hide from anywhere with");
+            let field = ui.add(TextEdit::singleline(draft).hint_text("Ctrl+Shift+H"));
+            typed = field.lost_focus();
+        });
+        if typed {
+            if let Some(draft) = self.draft.take() {
+                *hotkey = draft.trim().to_string();
+            }
+        }
+    }
+}
+
+#[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+mod imp {
+    use global_hotkey::hotkey::HotKey;
+    use global_hotkey::{GlobalHotKeyEvent, GlobalHotKeyManager};
+
+    pub struct Registration {
+        manager: GlobalHotKeyManager,
+        hotkey: HotKey,
+    }
+
+    impl Registration {
+        pub fn new(hotkey: &str) -> Result<Self, String> {
+            let hotkey: HotKey = hotkey
+                .parse()
+                .map_err(|e| format!("\"{}\" isn't a hotkey: {}", hotkey, e))?;
+            let manager = GlobalHotKeyManager::new().map_err(|e| e.to_string())?;
+            manager.register(hotkey).map_err(|e| e.to_string())?;
+            Ok(Self { manager, hotkey })
+        }
+
+        /// Whether the hotkey was pressed since the last call
+        pub fn pressed(&self) -> bool {
+            GlobalHotKeyEvent::receiver()
+                .try_iter()
+                .filter(|event| event.id == self.hotkey.id())
+                .count()
+                > 0
+        }
+    }
+
+    impl Drop for Registration {
+        fn drop(&mut self) {
+            if let Err(e) = self.manager.unregister(self.hotkey) {
+                tracing::error!("Failed unregistering the hotkey: {}", e);
+            }
+        }
+    }
+}
@@ -0,0 +1,243 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Duration;
+
+/// Proxy the native requests go through, set by the app whenever its settings change
+#[cfg(not(target_arch = "wasm32"))]
+static PROXY: Mutex<Option<ProxySettings>> = Mutex::new(None);
+
+/// Base url the web build's requests are prefixed with, none when empty
+#[cfg(target_arch = "wasm32")]
+static CORS_PROXY: Mutex<String> = Mutex::new(String::new());
+
+// without the app nothing picks a kind other than the environment's
+#[cfg_attr(not(feature = "gui"), allow(dead_code))]
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ProxyKind {
+    /// From the `HTTPS_PROXY`, `HTTP_PROXY`, `ALL_PROXY` and `NO_PROXY` environment variables
+    Environment,
+    Direct,
+    Http,
+    Socks5,
+}
+
+#[cfg(feature = "gui")]
+impl ProxyKind {
+    pub const ALL: [ProxyKind; 4] = [Self::Environment, Self::Direct, Self::Http, Self::Socks5];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Environment => "From the environment",
+            Self::Direct => "No proxy",
+            Self::Http => "HTTP",
+            Self::Socks5 => "SOCKS5",
+        }
+    }
+}
+
+/// Proxy for the app's own requests, the browser's applies on the web
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProxySettings {
+    pub kind: ProxyKind,
+    pub host: String,
+    pub port: u16,
+    /// No authentication when empty
+    pub username: String,
+    pub password: String,
+    /// Base url of a CORS proxy the web build prefixes its requests with, none when empty
+    #[cfg_attr(not(feature = "gui"), allow(dead_code))]
+    pub cors_proxy: String,
+}
+
+impl Default for ProxySettings {
+    fn default() -> Self {
+        Self {
+            kind: ProxyKind::Environment,
+            host: String::new(),
+            port: 8080,
+            username: String::new(),
+            password: String::new(),
+            cors_proxy: String::new(),
+        }
+    }
+}
+
+impl ProxySettings {
+    /// Url of the proxy for a request to `url`, none for a direct connection
+    #[cfg(not(target_arch = "wasm32"))]
+    fn proxy_url(&self, url: &str) -> Option<String> {
+        let scheme = match self.kind {
+            ProxyKind::Environment => return environment_proxy(url),
+            ProxyKind::Direct => return None,
+            ProxyKind::Http => "http",
+            ProxyKind::Socks5 => "socks5",
+        };
+        if self.host.is_empty() {
+            return None;
+        }
+        let auth = if self.username.is_empty() {
+            String::new()
+        } else {
+            format!("{}:{}@", self.username, self.password)
+        };
+        Some(format!("{}://{}{}:{}", scheme, auth, self.host, self.port))
+    }
+}
+
+/// Makes the native requests sent from now on go through the proxy
+#[cfg(all(feature = "gui", not(target_arch = "wasm32")))]
+pub fn set_proxy(settings: &ProxySettings) {
+    *PROXY.lock().unwrap() = Some(settings.clone());
+}
+
+/// Makes the requests sent from now on go through the CORS proxy
+#[cfg(all(feature = "gui", target_arch = "wasm32"))]
+pub fn set_proxy(settings: &ProxySettings) {
+    *CORS_PROXY.lock().unwrap() = settings.cors_proxy.trim().to_string();
+}
+
+/// `url` behind the CORS proxy, as is without one
+#[cfg(target_arch = "wasm32")]
+fn cors_proxied(url: &str) -> String {
+    format!("{}{}", CORS_PROXY.lock().unwrap(), url)
+}
+
+/// Sends the request through the proxy, like `ehttp::fetch` does without one
+#[cfg(all(feature = "gui", not(target_arch = "wasm32")))]
+pub fn fetch(
+    request: ehttp::Request,
+    on_done: impl 'static + Send + FnOnce(ehttp::Result<ehttp::Response>),
+) {
+    let proxy = PROXY
+        .lock()
+        .unwrap()
+        .clone()
+        .unwrap_or_default()
+        .proxy_url(&request.url);
+    std::thread::Builder::new()
+        .name("http-request".to_string())
+        .spawn(move || on_done(send(request, proxy, None)))
+        .expect("Failed spawning a request thread");
+}
+
+/// Sends the request through the proxy on the calling thread, giving up after `timeout`
+#[cfg(not(target_arch = "wasm32"))]
+pub fn fetch_blocking(
+    request: ehttp::Request,
+    timeout: Duration,
+) -> ehttp::Result<ehttp::Response> {
+    let proxy = PROXY
+        .lock()
+        .unwrap()
+        .clone()
+        .unwrap_or_default()
+        .proxy_url(&request.url);
+    send(request, proxy, Some(timeout))
+}
+
+#[cfg(all(feature = "gui", target_arch = "wasm32"))]
+pub fn fetch(
+    mut request: ehttp::Request,
+    on_done: impl 'static + Send + FnOnce(ehttp::Result<ehttp::Response>),
+) {
+    request.url = cors_proxied(&request.url);
+    ehttp::fetch(request, on_done);
+}
+
+/// Sends the request through the CORS proxy, like `ehttp::fetch_async` does without one
+#[cfg(target_arch = "wasm32")]
+pub async fn fetch_async(mut request: ehttp::Request) -> ehttp::Result<ehttp::Response> {
+    request.url = cors_proxied(&request.url);
+    ehttp::fetch_async(&request).await
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn send(
+    request: ehttp::Request,
+    proxy: Option<String>,
+    timeout: Option<Duration>,
+) -> ehttp::Result<ehttp::Response> {
+    use std::io::Read;
+
+    let mut agent = ureq::AgentBuilder::new();
+    if let Some(timeout) = timeout {
+        agent = agent.timeout(timeout);
+    }
+    if let Some(proxy) = proxy {
+        let proxy = ureq::Proxy::new(&proxy).map_err(|e| format!("Invalid proxy: {}", e))?;
+        agent = agent.proxy(proxy);
+    }
+    let mut http_request = agent.build().request(&request.method, &request.url);
+    for (name, value) in &request.headers {
+        http_request = http_request.set(name, value);
+    }
+    let result = if request.body.is_empty() {
+        http_request.call()
+    } else {
+        http_request.send_bytes(&request.body)
+    };
+    let response = match result {
+        Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+        Err(e) => return Err(e.to_string()),
+    };
+
+    let url = response.get_url().to_string();
+    let status = response.status();
+    let status_text = response.status_text().to_string();
+    let headers = response
+        .headers_names()
+        .into_iter()
+        .filter_map(|name| {
+            let value = response.header(&name)?.to_string();
+            Some((name.to_lowercase(), value))
+        })
+        .collect();
+    let mut bytes = Vec::new();
+    response
+        .into_reader()
+        .read_to_end(&mut bytes)
+        .map_err(|e| e.to_string())?;
+
+    Ok(ehttp::Response {
+        url,
+        ok: (200..300).contains(&status),
+        status,
+        status_text,
+        bytes,
+        headers,
+    })
+}
+
+/// Proxy the environment variables give for `url`, the lowercase ones included
+#[cfg(not(target_arch = "wasm32"))]
+fn environment_proxy(url: &str) -> Option<String> {
+    let var = |name: &str| {
+        std::env::var(name)
+            .or_else(|_| std::env::var(name.to_lowercase()))
+            .ok()
+            .filter(|value| !value.is_empty())
+    };
+    let host = url
+        .split("://")
+        .nth(1)?
+        .split(['/', ':', '?'])
+        .next()?
+        .to_lowercase();
+    let bypassed = var("NO_PROXY").map_or(false, |no_proxy| {
+        no_proxy.split(',').map(str::trim).any(|domain| {
+            let domain = domain.trim_start_matches('.').to_lowercase();
+            domain == "*" || host == domain || host.ends_with(&format!(".{}", domain))
+        })
+    });
+    if bypassed {
+        return None;
+    }
+    let specific = if url.starts_with("https:") {
+        var("HTTPS_PROXY")
+    } else {
+        var("HTTP_PROXY")
+    };
+    specific.or_else(|| var("ALL_PROXY"))
+}
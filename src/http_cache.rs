@@ -0,0 +1,113 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+/// Status of a conditional request whose cached copy is still current
+pub const NOT_MODIFIED: u16 = 304;
+
+/// Pages kept, the least recently fetched are dropped first
+const MAX_PAGES: usize = 20;
+/// Bytes the kept bodies may take, as the cache is saved with the rest of the app's state
+const MAX_BYTES: usize = 512 * 1024;
+
+/// Cache shared with the fetch callbacks
+pub type SharedHttpCache = Arc<Mutex<HttpCache>>;
+
+/// A fetched page with the validators its server sent
+#[derive(Serialize, Deserialize)]
+struct CachedPage {
+    url: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    body: String,
+    /// When the page was last fetched or found current, pages cached before it was kept count
+    /// as fetched on launch
+    #[serde(default = "Utc::now")]
+    fetched_at: DateTime<Utc>,
+}
+
+/// Pages kept with their ETag or Last-Modified validators, so that fetching them again is a
+/// conditional request the server answers with a bodiless 304 when they haven't changed
+#[derive(Default, Serialize, Deserialize)]
+pub struct HttpCache {
+    pages: VecDeque<CachedPage>,
+}
+
+impl HttpCache {
+    /// GET request for `url`, conditional when a copy of it is cached
+    pub fn request(&self, url: &str) -> ehttp::Request {
+        self.conditional(ehttp::Request::get(url))
+    }
+
+    /// The request, made conditional when a copy of its url is cached
+    pub fn conditional(&self, mut request: ehttp::Request) -> ehttp::Request {
+        // the browser's own cache already revalidates on the web, and extra headers would cost
+        // a CORS preflight
+        if cfg!(target_arch = "wasm32") {
+            return request;
+        }
+        if let Some(page) = self.pages.iter().find(|page| page.url == request.url) {
+            if let Some(etag) = &page.etag {
+                request
+                    .headers
+                    .insert("If-None-Match".to_string(), etag.clone());
+            }
+            if let Some(last_modified) = &page.last_modified {
+                request
+                    .headers
+                    .insert("If-Modified-Since".to_string(), last_modified.clone());
+            }
+        }
+        request
+    }
+
+    /// Cached body of `url`, for when the server answered NOT_MODIFIED
+    pub fn body(&mut self, url: &str) -> Option<String> {
+        let index = self.pages.iter().position(|page| page.url == url)?;
+        let mut page = self.pages.remove(index)?;
+        page.fetched_at = Utc::now();
+        let body = page.body.clone();
+        self.pages.push_back(page);
+        Some(body)
+    }
+
+    /// Keeps the body of a successful response, if its server sent validators for it
+    pub fn store(&mut self, url: &str, headers: &BTreeMap<String, String>, body: &str) {
+        self.pages.retain(|page| page.url != url);
+        let header = |name: &str| {
+            headers
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.clone())
+        };
+        let (etag, last_modified) = (header("etag"), header("last-modified"));
+        if etag.is_none() && last_modified.is_none() {
+            return;
+        }
+        self.pages.push_back(CachedPage {
+            url: url.to_string(),
+            etag,
+            last_modified,
+            body: body.to_string(),
+            fetched_at: Utc::now(),
+        });
+        while self.pages.len() > MAX_PAGES || self.size() > MAX_BYTES {
+            self.pages.pop_front();
+        }
+    }
+
+    /// Forgets the pages not fetched since `cutoff`
+    pub fn expire(&mut self, cutoff: DateTime<Utc>) {
+        self.pages.retain(|page| page.fetched_at >= cutoff);
+    }
+
+    pub fn clear(&mut self) {
+        self.pages.clear();
+    }
+
+    /// Bytes the cached bodies take
+    pub fn size(&self) -> usize {
+        self.pages.iter().map(|page| page.body.len()).sum()
+    }
+}
@@ -0,0 +1,18 @@
+#[cfg(feature = "gui")]
+use whatlang::Lang;
+
+/// Below this whatlang's guesses on a title and a description are mostly noise
+const MIN_CONFIDENCE: f64 = 0.5;
+
+/// ISO 639-3 code of the text's language, None when it can't be told
+pub fn detect(text: &str) -> Option<&'static str> {
+    whatlang::detect(text)
+        .filter(|info| info.confidence() >= MIN_CONFIDENCE)
+        .map(|info| info.lang().code())
+}
+
+/// English name of the language with this ISO 639-3 code
+#[cfg(feature = "gui")]
+pub fn name(code: &str) -> &str {
+    Lang::from_code(code).map_or(code, |lang| lang.eng_name())
+}
@@ -1,13 +1,154 @@
+#[cfg(feature = "gui")]
+mod accessibility;
+mod aggregate;
+#[cfg(feature = "gui")]
+mod bookmarks;
+#[cfg(feature = "gui")]
+mod cards;
+mod categories;
+#[cfg(feature = "gui")]
+mod category_tabs;
+#[cfg(feature = "gui")]
+mod changelog;
+#[cfg(feature = "gui")]
+mod charset;
+#[cfg(all(feature = "gui", not(target_arch = "wasm32")))]
+mod close;
+#[cfg(feature = "gui")]
+mod cluster;
+#[cfg(feature = "gui")]
+mod combo;
+#[cfg(feature = "gui")]
+mod compare;
+mod countries;
+#[cfg(feature = "gui")]
+mod dashboard;
+#[cfg(all(feature = "gui", target_arch = "wasm32"))]
+mod deep_link;
+#[cfg(feature = "gui")]
+mod demo;
+#[cfg(feature = "gui")]
+mod drop_import;
+#[cfg(feature = "gui")]
+mod entities;
+#[cfg(feature = "gui")]
+mod error_log;
+mod errors;
+#[cfg(feature = "gui")]
+mod export;
+#[cfg(feature = "gui")]
+mod favicons;
+mod feeds;
+#[cfg(feature = "gui")]
+mod files;
+#[cfg(feature = "gui")]
+mod fonts;
+#[cfg(not(target_arch = "wasm32"))]
+mod headless;
+#[cfg(feature = "gui")]
 mod headlines;
+#[cfg(feature = "gui")]
+mod history;
+#[cfg(all(feature = "gui", not(target_arch = "wasm32")))]
+mod hotkey;
+mod http;
+mod http_cache;
+mod language;
+#[cfg(feature = "gui")]
+mod marquee;
+#[cfg(feature = "gui")]
+mod mastodon;
+mod news;
+mod news_source;
+#[cfg(feature = "gui")]
+mod onboarding;
+#[cfg(feature = "gui")]
+mod outbox;
+#[cfg(feature = "gui")]
+mod pocket;
+#[cfg(feature = "gui")]
+mod privacy;
+#[cfg(feature = "gui")]
+mod profiles;
+#[cfg(feature = "gui")]
+mod qr;
+#[cfg(feature = "gui")]
+mod quotes;
+#[cfg(feature = "gui")]
+mod reader;
+#[cfg(feature = "gui")]
+mod retention;
+#[cfg(feature = "gui")]
+mod review;
+#[cfg(feature = "gui")]
+mod schedule;
+#[cfg(feature = "gui")]
+mod screens;
+#[cfg(feature = "gui")]
+mod search;
+#[cfg(feature = "gui")]
+mod sentiment;
+#[cfg(feature = "gui")]
+mod session;
+mod share;
+mod sources;
+#[cfg(feature = "gui")]
+mod speech;
+#[cfg(feature = "gui")]
+mod stats;
+#[cfg(feature = "gui")]
+mod store;
+#[cfg(not(target_arch = "wasm32"))]
+mod stored_config;
+#[cfg(feature = "gui")]
+mod sync;
+#[cfg(feature = "gui")]
+mod theme;
+#[cfg(feature = "gui")]
+mod thumbnails;
+#[cfg(all(feature = "gui", not(target_arch = "wasm32")))]
+mod ticker;
+#[cfg(all(feature = "gui", not(target_arch = "wasm32")))]
+mod title_bar;
+#[cfg(feature = "gui")]
+mod trends;
+#[cfg(feature = "gui")]
+mod visits;
+#[cfg(feature = "gui")]
+mod wallabag;
+#[cfg(feature = "gui")]
+mod weather;
+#[cfg(feature = "gui")]
+mod webhook;
+mod worker;
 
+#[cfg(not(target_arch = "wasm32"))]
+pub use headless::run_headless;
+#[cfg(not(target_arch = "wasm32"))]
+pub use news_source::fetch_all;
+#[cfg(not(target_arch = "wasm32"))]
+pub use stored_config::StoredConfig;
+#[cfg(feature = "gui")]
 pub use headlines::Headlines;
 
-#[cfg(target_arch = "wasm32")]
+// the data layer, usable without the egui app
+pub use aggregate::SourceCaps;
+pub use categories::Category;
+pub use countries::{CountryInfo, COUNTRIES};
+pub use errors::HeadlinesError;
+pub use http_cache::{HttpCache, SharedHttpCache};
+pub use news::{Discussion, NewsCardData};
+pub use news_source::{FakeSource, HeadlinesFuture, NewsApi, NewsSource};
+pub use newsapi::Country;
+pub use sources::{fetch as fetch_source, SourceSettings};
+pub use worker::{FetchState, RetryPolicy};
+
+#[cfg(all(feature = "gui", target_arch = "wasm32"))]
 use eframe::wasm_bindgen::{self, prelude::*};
-#[cfg(target_arch = "wasm32")]
+#[cfg(all(feature = "gui", target_arch = "wasm32"))]
 use eframe::WebOptions;
 
-#[cfg(target_arch = "wasm32")]
+#[cfg(all(feature = "gui", target_arch = "wasm32"))]
 #[wasm_bindgen]
 pub async fn main_web(canvas_id: &str)
 {
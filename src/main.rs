@@ -2,17 +2,45 @@
 
 use eframe::egui::Vec2;
 use eframe::{NativeOptions, run_native};
-use headlines::Headlines;
+use headlines::{Headlines, StoredConfig};
 
 fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "--headless") {
+        #[cfg(windows)]
+        attach_parent_console();
+        // stdout is for the headlines
+        tracing_subscriber::fmt().with_writer(std::io::stderr).init();
+        std::process::exit(headlines::run_headless(&args));
+    }
+
     tracing_subscriber::fmt::init();
 
-    let headlines = Headlines::new();
+    let mut headlines = Headlines::new();
     let mut win_option = NativeOptions::default();
     win_option.min_window_size = Some(Vec2::new(540., 480.));
     win_option.initial_window_size = Some(Vec2::new(540., 960.));
+    // eframe only sets these when creating the window
+    if StoredConfig::load().ticker.enabled {
+        headlines = headlines.with_ticker();
+        // small enough for the ticker mode
+        win_option.min_window_size = Some(Vec2::new(320., 72.));
+        // lets the ticker's background be see-through, the panels are opaque otherwise
+        win_option.transparent = true;
+    }
 
     tracing::info!("cc les boys");
 
     run_native("headlines", win_option, Box::new(|cc| Box::new(headlines.init(cc))));
 }
+
+/// Prints to the console the app was started from, a windows subsystem app has none of its own
+#[cfg(windows)]
+fn attach_parent_console() {
+    use windows_sys::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+
+    // SAFETY: takes no pointer, and only fails when the parent has no console to attach to
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
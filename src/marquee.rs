@@ -0,0 +1,83 @@
+use crate::news::NewsCardData;
+use eframe::egui::{pos2, vec2, Rect, Sense, TextStyle, Ui};
+
+/// Space between two titles
+const GAP: f32 = 32.;
+const MARGIN: f32 = 4.;
+/// Longest time the strip moves for at once, it jumps ahead otherwise when it was hidden a while
+const MAX_STEP: f32 = 0.1;
+
+/// Strip of titles scrolling by continuously, it stops under the pointer and can be dragged
+#[derive(Default)]
+pub struct Marquee {
+    /// How far the titles have scrolled, in points
+    offset: f32,
+    /// When the strip last moved
+    last_time: Option<f64>,
+}
+
+impl Marquee {
+    /// Scrolls the titles by `speed` points a second, still when it's 0. Returns the index of the
+    /// article whose title was clicked.
+    pub fn show(&mut self, ui: &mut Ui, articles: &[NewsCardData], speed: f32) -> Option<usize> {
+        let font = TextStyle::Body.resolve(ui.style());
+        let text_color = ui.visuals().text_color();
+        let hover_color = ui.visuals().hyperlink_color;
+        let galleys: Vec<_> = articles
+            .iter()
+            .map(|a| {
+                ui.painter()
+                    .layout_no_wrap(a.title.clone(), font.clone(), text_color)
+            })
+            .collect();
+        let lap: f32 = galleys.iter().map(|g| g.size().x + GAP).sum();
+        let height = ui.fonts().row_height(&font) + 2. * MARGIN;
+        let (rect, strip) =
+            ui.allocate_exact_size(vec2(ui.available_width(), height), Sense::drag());
+        if galleys.is_empty() {
+            return None;
+        }
+
+        let now = ui.input().time;
+        let elapsed = self
+            .last_time
+            .map_or(0., |last| (now - last) as f32)
+            .min(MAX_STEP);
+        self.last_time = Some(now);
+        if !ui.rect_contains_pointer(rect) {
+            self.offset += elapsed * speed;
+        }
+        self.offset = (self.offset - strip.drag_delta().x).rem_euclid(lap);
+        if speed > 0. {
+            ui.ctx().request_repaint();
+        }
+
+        let painter = ui.painter_at(rect);
+        let mut clicked = None;
+        let mut x = rect.left() - self.offset;
+        let mut round = 0;
+        // as many rounds of the titles as it takes to fill the strip
+        while x < rect.right() {
+            for (i, galley) in galleys.iter().enumerate() {
+                let title = Rect::from_min_size(pos2(x, rect.top() + MARGIN), galley.size());
+                x += galley.size().x + GAP;
+                if title.right() < rect.left() || title.left() > rect.right() {
+                    continue;
+                }
+                let id = ui.id().with(("marquee", round, i));
+                let response = ui.interact(title.intersect(rect), id, Sense::click());
+                let color = if response.hovered() {
+                    hover_color
+                } else {
+                    text_color
+                };
+                painter.galley_with_color(title.min, galley.clone(), color);
+                if response.clicked() {
+                    clicked = Some(i);
+                }
+            }
+            round += 1;
+        }
+        clicked
+    }
+}
@@ -0,0 +1,169 @@
+use crate::news::NewsCardData;
+use crate::outbox::OutboundRequest;
+use eframe::egui::{Button, ComboBox, Context, Grid, TextEdit, Ui, Window};
+use serde::{Deserialize, Serialize};
+
+/// Characters a toot may have on a stock Mastodon instance
+const MAX_CHARS: usize = 500;
+/// Characters Mastodon counts a link as, whatever its length
+const LINK_CHARS: usize = 23;
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Visibility {
+    Public,
+    Unlisted,
+    Private,
+}
+
+impl Visibility {
+    pub const ALL: [Visibility; 3] = [Self::Public, Self::Unlisted, Self::Private];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Public => "Public",
+            Self::Unlisted => "Unlisted",
+            Self::Private => "Followers only",
+        }
+    }
+
+    /// Name of the visibility in Mastodon's API
+    fn api_name(&self) -> &'static str {
+        match self {
+            Self::Public => "public",
+            Self::Unlisted => "unlisted",
+            Self::Private => "private",
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MastodonSettings {
+    /// Url of the instance, like https://mastodon.social
+    pub server: String,
+    /// Token of an app created under Preferences > Development, with the write:statuses scope
+    pub access_token: String,
+    pub visibility: Visibility,
+}
+
+impl Default for MastodonSettings {
+    fn default() -> Self {
+        Self {
+            server: String::new(),
+            access_token: String::new(),
+            visibility: Visibility::Public,
+        }
+    }
+}
+
+impl MastodonSettings {
+    pub fn is_configured(&self) -> bool {
+        !self.server.trim().is_empty() && !self.access_token.trim().is_empty()
+    }
+
+    pub fn show(&mut self, ui: &mut Ui) {
+        Grid::new("mastodon").num_columns(2).show(ui, |ui| {
+            ui.label("Server");
+            ui.add(TextEdit::singleline(&mut self.server).hint_text("https://mastodon.social"));
+            ui.end_row();
+            ui.label("Access token");
+            ui.add(TextEdit::singleline(&mut self.access_token).password(true));
+            ui.end_row();
+            ui.label("Visibility");
+            ComboBox::from_id_source("toot_visibility")
+                .selected_text(self.visibility.name())
+                .show_ui(ui, |ui| {
+                    for visibility in Visibility::ALL {
+                        ui.selectable_value(&mut self.visibility, visibility, visibility.name());
+                    }
+                });
+            ui.end_row();
+        });
+        ui.weak("The token is stored unencrypted with the settings");
+    }
+
+    fn status_request(&self, text: &str, title: &str) -> OutboundRequest {
+        let body = serde_json::json!({
+            "status": text,
+            "visibility": self.visibility.api_name(),
+        });
+        OutboundRequest {
+            label: format!("Tooting \"{}\"", title),
+            url: format!(
+                "{}/api/v1/statuses",
+                self.server.trim().trim_end_matches('/')
+            ),
+            headers: vec![
+                ("Content-Type".to_string(), "application/json".to_string()),
+                (
+                    "Authorization".to_string(),
+                    format!("Bearer {}", self.access_token.trim()),
+                ),
+            ],
+            body: body.to_string(),
+        }
+    }
+}
+
+/// Popup to edit a toot about an article before it's sent
+#[derive(Default)]
+pub struct Composer {
+    /// Title of the article being tooted about and the toot's text, none when closed
+    draft: Option<(String, String)>,
+}
+
+impl Composer {
+    pub fn open(&mut self, article: &NewsCardData) {
+        let text = format!("{}\n\n{}", article.title, article.url);
+        self.draft = Some((article.title.clone(), text));
+    }
+
+    /// Returns the request posting the toot once it's sent
+    pub fn show(&mut self, ctx: &Context, settings: &MastodonSettings) -> Option<OutboundRequest> {
+        let (title, text) = self.draft.as_mut()?;
+        let mut open = true;
+        let mut sent = None;
+        let mut cancelled = false;
+        Window::new("Toot this")
+            .open(&mut open)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.add(
+                    TextEdit::multiline(text)
+                        .desired_rows(5)
+                        .desired_width(360.),
+                );
+                let left = MAX_CHARS as i64 - count_chars(text) as i64;
+                ui.horizontal(|ui| {
+                    if left < 0 {
+                        ui.colored_label(ui.visuals().error_fg_color, left.to_string());
+                    } else {
+                        ui.weak(left.to_string());
+                    }
+                    let can_send = left >= 0 && !text.trim().is_empty();
+                    if ui.add_enabled(can_send, Button::new("Toot")).clicked() {
+                        sent = Some(settings.status_request(text, title));
+                    }
+                    cancelled = ui.button("Cancel").clicked();
+                });
+            });
+        if !open || cancelled || sent.is_some() {
+            self.draft = None;
+        }
+        sent
+    }
+}
+
+/// Length of the text as Mastodon counts it, links count for a fixed length
+fn count_chars(text: &str) -> usize {
+    text.split_whitespace()
+        .map(|word| {
+            if word.starts_with("http://") || word.starts_with("https://") {
+                LINK_CHARS
+            } else {
+                word.chars().count()
+            }
+        })
+        .sum::<usize>()
+        + text.chars().filter(|c| c.is_whitespace()).count()
+}
@@ -0,0 +1,46 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct NewsCardData {
+    pub title: String,
+    pub desc: String,
+    pub url: String,
+    pub source: String,
+    pub fetched_at: DateTime<Utc>,
+    /// ISO code of the country whose headlines the article is from, empty when unknown
+    #[serde(default)]
+    pub country: String,
+    /// ISO 639-3 code of the article's language, empty when it couldn't be detected
+    #[serde(default)]
+    pub language: String,
+    /// Thread of the article on the link aggregator it comes from, like Hacker News
+    #[serde(default)]
+    pub discussion: Option<Discussion>,
+    /// Url of the article's image, shown on its card
+    #[serde(default)]
+    pub thumbnail: Option<String>,
+    #[serde(default)]
+    pub author: Option<String>,
+    /// When the article was published, if its source tells
+    #[serde(default)]
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Discussion {
+    pub url: String,
+    /// Points or upvotes
+    pub score: u32,
+    pub comments: u32,
+}
+
+/// Host of the url, without its "www."
+pub fn domain(url: &str) -> &str {
+    let host = url.split("://").nth(1).unwrap_or(url);
+    let host = host
+        .split(|c| matches!(c, '/' | ':' | '?' | '#'))
+        .next()
+        .unwrap_or(host);
+    host.strip_prefix("www.").unwrap_or(host)
+}
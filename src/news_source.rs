@@ -0,0 +1,335 @@
+#[cfg(not(target_arch = "wasm32"))]
+use crate::aggregate;
+use crate::categories::Category;
+use crate::countries;
+use crate::errors::HeadlinesError;
+use crate::http;
+use crate::http_cache::{SharedHttpCache, NOT_MODIFIED};
+use crate::language;
+use crate::news::NewsCardData;
+use crate::worker::FetchState;
+use chrono::{DateTime, TimeZone, Utc};
+use newsapi::Country;
+use serde::Deserialize;
+use std::future::Future;
+use std::pin::Pin;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::mpsc::{channel, RecvTimeoutError};
+#[cfg(not(target_arch = "wasm32"))]
+use std::thread;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const NEWSAPI_SOURCE: &str = "newsapi.org";
+/// Endpoint called without newsapi, through a CORS proxy or for a category
+const NEWSAPI_TOP_HEADLINES: &str = "https://newsapi.org/v2/top-headlines";
+/// Articles in a page of newsapi.org's results, its default
+const NEWSAPI_PAGE_SIZE: u32 = 20;
+/// Pages newsapi.org serves, its developer plan stops at the first 100 results
+#[cfg(feature = "gui")]
+pub const NEWSAPI_PAGES: u32 = 5;
+
+/// Name the fake source's articles are credited to
+const FAKE_SOURCE: &str = "fake";
+/// Site of the fake articles, under a reserved TLD so that nothing is ever fetched from it
+const FAKE_SITE: &str = "https://headlines.invalid";
+/// When the fake source's articles were fetched, 2023-01-01 at midnight UTC
+const FAKE_FETCHED_AT: i64 = 1_672_531_200;
+
+/// newsapi.org's answer to a request of top headlines
+#[derive(Deserialize)]
+struct TopHeadlines {
+    articles: Vec<TopHeadline>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TopHeadline {
+    title: Option<String>,
+    description: Option<String>,
+    url: String,
+    author: Option<String>,
+    /// RFC 3339, parsed apart so that a malformed date doesn't lose the whole page
+    published_at: Option<String>,
+    url_to_image: Option<String>,
+}
+
+/// Articles of a country's top headlines, or why they couldn't be had
+pub type HeadlinesFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<Vec<NewsCardData>, HeadlinesError>> + 'a>>;
+
+/// Where the top headlines of the feed's countries come from, newsapi.org unless the app was
+/// built with another one
+pub trait NewsSource: Send + Sync {
+    /// Name the articles are credited to
+    fn name(&self) -> &'static str;
+
+    /// The headlines of the country matching the state's query, of its category and page. Native
+    /// fetches block on it, so it may block too.
+    fn top_headlines<'a>(&'a self, state: &'a FetchState, country: Country) -> HeadlinesFuture<'a>;
+}
+
+/// newsapi.org's top headlines
+pub struct NewsApi;
+
+impl NewsSource for NewsApi {
+    fn name(&self) -> &'static str {
+        NEWSAPI_SOURCE
+    }
+
+    fn top_headlines<'a>(&'a self, state: &'a FetchState, country: Country) -> HeadlinesFuture<'a> {
+        Box::pin(async move {
+            #[cfg(not(target_arch = "wasm32"))]
+            let response = fetch_country(state, country)?;
+            #[cfg(target_arch = "wasm32")]
+            let response = fetch_country_web(state, country).await?;
+            Ok(generate_news_card_data(&response, country, state))
+        })
+    }
+}
+
+/// Made up headlines, the same for the same request, for driving the app in tests without network
+pub struct FakeSource {
+    per_page: usize,
+    /// Error every request fails with, if any
+    failure: Option<String>,
+}
+
+impl FakeSource {
+    /// Source answering `per_page` articles for every country and page
+    pub fn new(per_page: usize) -> Self {
+        Self {
+            per_page,
+            failure: None,
+        }
+    }
+
+    /// Source whose every request fails with a network error saying `message`
+    pub fn failing(message: &str) -> Self {
+        Self {
+            per_page: 0,
+            failure: Some(message.to_string()),
+        }
+    }
+
+    /// Title of the fake article numbered `n` from 1 on the page of the country's headlines
+    pub fn title(country: Country, page: u32, n: usize) -> String {
+        let code = countries::COUNTRIES[countries::index(country)].code;
+        format!("{} headline {} of page {}", code, n, page)
+    }
+}
+
+impl NewsSource for FakeSource {
+    fn name(&self) -> &'static str {
+        FAKE_SOURCE
+    }
+
+    fn top_headlines<'a>(&'a self, state: &'a FetchState, country: Country) -> HeadlinesFuture<'a> {
+        Box::pin(async move {
+            if let Some(message) = &self.failure {
+                return Err(HeadlinesError::Network(message.clone()));
+            }
+            let code = countries::COUNTRIES[countries::index(country)].code;
+            let fetched_at = Utc.timestamp_opt(FAKE_FETCHED_AT, 0).unwrap();
+            let articles = (1..=self.per_page)
+                .map(|n| {
+                    let title = Self::title(country, state.page, n);
+                    NewsCardData {
+                        desc: format!("Description of the {}", title.to_lowercase()),
+                        url: format!("{}/{}/{}/{}", FAKE_SITE, code.to_lowercase(), state.page, n),
+                        title,
+                        source: FAKE_SOURCE.to_string(),
+                        fetched_at,
+                        country: code.to_string(),
+                        language: "eng".to_string(),
+                        discussion: None,
+                        thumbnail: None,
+                        author: None,
+                        published_at: None,
+                    }
+                })
+                .filter(|news| state.matches(&news.title, &news.desc))
+                .collect();
+            Ok(articles)
+        })
+    }
+}
+
+/// Top headlines of every country of the state, merged as the feed merges them, along with the
+/// errors of the countries that failed
+#[cfg(not(target_arch = "wasm32"))]
+pub fn fetch_all(state: &FetchState) -> (Vec<NewsCardData>, Vec<HeadlinesError>) {
+    let mut batches = Vec::new();
+    let mut errors = Vec::new();
+    for &country in &state.countries {
+        match pollster::block_on(state.source.top_headlines(state, country)) {
+            Ok(articles) => batches.push((state.source.name(), articles)),
+            Err(e) => errors.push(e),
+        }
+    }
+    (aggregate::merge(batches, &state.source_caps), errors)
+}
+
+/// Fetches the headlines of a country, giving up on an attempt after the policy's timeout and
+/// retrying network errors and timeouts
+#[cfg(not(target_arch = "wasm32"))]
+fn fetch_country(state: &FetchState, country: Country) -> Result<TopHeadlines, HeadlinesError> {
+    let mut attempt = 0;
+    loop {
+        let (tx, rx) = channel();
+        let request = top_headlines_request(&state.api_key, country, state.category, state.page);
+        let cache = state.http_cache.clone();
+        let timeout = state.retry.timeout();
+        // an attempt that hangs is left behind on its thread
+        let spawned = thread::Builder::new()
+            .name(format!("newsapi-{:?}", country))
+            .spawn(move || {
+                let url = request.url.clone();
+                let request = cache.lock().unwrap().conditional(request);
+                let result =
+                    read_top_headlines(http::fetch_blocking(request, timeout), &url, &cache);
+                // nobody is waiting anymore after a timeout
+                let _ = tx.send(result);
+            });
+        if let Err(e) = spawned {
+            let error = format!("Failed starting the request: {}", e);
+            return Err(HeadlinesError::Internal(error));
+        }
+        let result = match rx.recv_timeout(state.retry.timeout()) {
+            Ok(result) => result,
+            Err(RecvTimeoutError::Timeout) => Err(HeadlinesError::Timeout(format!(
+                "no answer from {} within {} s",
+                NEWSAPI_SOURCE,
+                state.retry.timeout().as_secs()
+            ))),
+            Err(RecvTimeoutError::Disconnected) => Err(HeadlinesError::Network(
+                "the request stopped without an answer".to_string(),
+            )),
+        };
+        match result {
+            Err(e) if e.is_transient() && attempt < state.retry.retries => {
+                tracing::warn!("{}, retrying", e);
+                let jitter = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0., |now| now.subsec_nanos() as f64 / 1e9);
+                thread::sleep(state.retry.backoff(attempt, jitter));
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Fetches the headlines of a country, retrying network errors. The browser bounds how long a
+/// request may hang, the policy's timeout isn't applied on the web.
+#[cfg(target_arch = "wasm32")]
+async fn fetch_country_web(
+    state: &FetchState,
+    country: Country,
+) -> Result<TopHeadlines, HeadlinesError> {
+    let mut attempt = 0;
+    loop {
+        let request = top_headlines_request(&state.api_key, country, state.category, state.page);
+        let url = request.url.clone();
+        let response = http::fetch_async(request).await;
+        let result = read_top_headlines(response, &url, &state.http_cache);
+        match result {
+            Err(e) if e.is_transient() && attempt < state.retry.retries => {
+                tracing::warn!("{}, retrying", e);
+                let delay = state.retry.backoff(attempt, js_sys::Math::random());
+                gloo_timers::future::TimeoutFuture::new(delay.as_millis() as u32).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Request of the country's headlines, sent without the newsapi crate as it can't change its url
+/// for the CORS proxy, ask for a category, make the request conditional nor read the articles'
+/// author, publish time and image
+fn top_headlines_request(
+    api_key: &str,
+    country: Country,
+    category: Option<Category>,
+    page: u32,
+) -> ehttp::Request {
+    let code = countries::COUNTRIES[countries::index(country)]
+        .code
+        .to_lowercase();
+    let mut url = format!("{}?country={}", NEWSAPI_TOP_HEADLINES, code);
+    if let Some(category) = category {
+        url.push_str(&format!("&category={}", category.api_name()));
+    }
+    if page > 1 {
+        url.push_str(&format!("&pageSize={}&page={}", NEWSAPI_PAGE_SIZE, page));
+    }
+    let mut request = ehttp::Request::get(url);
+    request
+        .headers
+        .insert("X-Api-Key".to_string(), api_key.to_string());
+    request
+}
+
+/// Headlines of the response to the request of `url`, the cached ones if they haven't changed
+fn read_top_headlines(
+    response: ehttp::Result<ehttp::Response>,
+    url: &str,
+    cache: &SharedHttpCache,
+) -> Result<TopHeadlines, HeadlinesError> {
+    let response = response.map_err(HeadlinesError::from_fetch)?;
+    if response.status == NOT_MODIFIED {
+        let body =
+            cache.lock().unwrap().body(url).ok_or_else(|| {
+                HeadlinesError::Internal("Not modified, yet not cached".to_string())
+            })?;
+        return serde_json::from_str(&body).map_err(|e| HeadlinesError::Parse(e.to_string()));
+    }
+    if !response.ok {
+        let body = String::from_utf8_lossy(&response.bytes);
+        let error = format!("{} {}: {}", response.status, response.status_text, body);
+        return Err(HeadlinesError::from_fetch(error));
+    }
+    let articles = serde_json::from_slice(&response.bytes)
+        .map_err(|e| HeadlinesError::Parse(e.to_string()))?;
+    if let Ok(body) = std::str::from_utf8(&response.bytes) {
+        cache.lock().unwrap().store(url, &response.headers, body);
+    }
+    Ok(articles)
+}
+
+fn generate_news_card_data(
+    response: &TopHeadlines,
+    country: Country,
+    state: &FetchState,
+) -> Vec<NewsCardData> {
+    let fetched_at = Utc::now();
+    let country = countries::COUNTRIES[countries::index(country)].code;
+    response
+        .articles
+        .iter()
+        .map(|article| {
+            let title = article.title.clone().unwrap_or_default();
+            let desc = article.description.clone().unwrap_or("...".to_string());
+            let language = language::detect(&format!("{} {}", title, desc)).unwrap_or_default();
+            NewsCardData {
+                title,
+                desc,
+                url: article.url.clone(),
+                source: NEWSAPI_SOURCE.to_string(),
+                fetched_at,
+                country: country.to_string(),
+                language: language.to_string(),
+                discussion: None,
+                thumbnail: article.url_to_image.clone(),
+                author: article.author.clone(),
+                published_at: article
+                    .published_at
+                    .as_deref()
+                    .and_then(|date| DateTime::parse_from_rfc3339(date).ok())
+                    .map(|date| date.with_timezone(&Utc)),
+            }
+        })
+        .filter(|news| state.matches(&news.title, &news.desc))
+        .collect()
+}
@@ -0,0 +1,266 @@
+use crate::accessibility;
+use crate::categories::Category;
+use crate::combo;
+use crate::countries;
+use crate::http;
+use crate::language;
+use eframe::egui::{Button, Key, TextEdit, Ui};
+use newsapi::Country;
+use serde::Deserialize;
+use std::collections::BTreeSet;
+use std::sync::{Arc, Mutex};
+
+const REGISTER_URL: &str = "https://newsapi.org/register";
+/// Cheapest request telling whether newsapi.org accepts a key
+const KEY_CHECK_URL: &str = "https://newsapi.org/v2/top-headlines?country=us&pageSize=1";
+/// ISO 639-3 codes of the languages offered before any article was seen
+const LANGUAGES: [&str; 6] = ["eng", "fra", "deu", "spa", "ita", "por"];
+
+#[derive(Clone, Copy, PartialEq)]
+enum Step {
+    Welcome,
+    GetKey,
+    EnterKey,
+    Preferences,
+    Done,
+}
+
+impl Step {
+    const ALL: [Step; 5] = [
+        Self::Welcome,
+        Self::GetKey,
+        Self::EnterKey,
+        Self::Preferences,
+        Self::Done,
+    ];
+
+    fn previous(self) -> Self {
+        match self {
+            Self::Welcome | Self::GetKey => Self::Welcome,
+            Self::EnterKey => Self::GetKey,
+            Self::Preferences => Self::EnterKey,
+            Self::Done => Self::Preferences,
+        }
+    }
+}
+
+/// What newsapi.org says is wrong with a request
+#[derive(Deserialize)]
+struct ApiError {
+    message: String,
+}
+
+/// How the wizard was left
+pub enum Finish {
+    /// For the feed of the key and preferences picked
+    Feed,
+    /// For sample articles, without a key
+    Demo,
+    /// For a settings file exported from another browser or the desktop app, picked next
+    Import,
+}
+
+/// Settings the wizard fills in, those of the app's config
+pub struct Choices<'a> {
+    pub api_key: &'a mut String,
+    pub country: &'a mut Country,
+    /// ISO 639-3 codes of the languages the feed keeps, every language when empty
+    pub languages: &'a mut BTreeSet<String>,
+    /// Categories with a tab next to the top headlines, every category when empty
+    pub categories: &'a mut BTreeSet<Category>,
+}
+
+/// First run wizard, from a welcome to a checked API key and the feed's country, languages and
+/// categories
+pub struct Wizard {
+    step: Step,
+    key: String,
+    checking: bool,
+    status: String,
+    answer: Arc<Mutex<Option<Result<(), String>>>>,
+}
+
+impl Default for Wizard {
+    fn default() -> Self {
+        Self {
+            step: Step::Welcome,
+            key: String::new(),
+            checking: false,
+            status: String::new(),
+            answer: Default::default(),
+        }
+    }
+}
+
+impl Wizard {
+    /// Draws the current step, returns how the wizard was left if it was
+    pub fn show(&mut self, ui: &mut Ui, choices: Choices) -> Option<Finish> {
+        let answer = self.answer.lock().unwrap().take();
+        match answer {
+            Some(Ok(())) => {
+                *choices.api_key = self.key.trim().to_string();
+                self.checking = false;
+                self.status.clear();
+                self.step = Step::Preferences;
+            }
+            Some(Err(e)) => {
+                self.checking = false;
+                self.status = e;
+            }
+            None => {}
+        }
+
+        let number = Step::ALL
+            .iter()
+            .position(|step| *step == self.step)
+            .unwrap_or(0);
+        ui.weak(format!("Step {} of {}", number + 1, Step::ALL.len()));
+        ui.add_space(8.);
+        let mut finish = None;
+        match self.step {
+            Step::Welcome => {
+                ui.heading("Welcome to Headlines");
+                ui.label("The top headlines of the countries you pick, in a few clicks.");
+                if ui.button("Get started").clicked() {
+                    self.step = Step::GetKey;
+                }
+                ui.add_space(8.);
+                if ui.button("Import settings…").clicked() {
+                    finish = Some(Finish::Import);
+                }
+                ui.weak("Exported from another browser or the desktop app");
+            }
+            Step::GetKey => {
+                ui.heading("Get an API key");
+                ui.label("Headlines are fetched from newsapi.org, which asks for a free API key.");
+                ui.hyperlink_to("Register at newsapi.org", REGISTER_URL);
+                ui.horizontal(|ui| {
+                    if ui.button("⬅ Back").clicked() {
+                        self.step = self.step.previous();
+                    }
+                    if ui.button("I have a key").clicked() {
+                        self.step = Step::EnterKey;
+                    }
+                });
+            }
+            Step::EnterKey => {
+                ui.heading("Paste your API key");
+                let input = ui.add_enabled(
+                    !self.checking,
+                    TextEdit::singleline(&mut self.key).hint_text("API key"),
+                );
+                let entered = input.lost_focus() && ui.input().key_pressed(Key::Enter);
+                ui.horizontal(|ui| {
+                    if ui.button("⬅ Back").clicked() {
+                        self.step = self.step.previous();
+                    }
+                    let can_check = !self.key.trim().is_empty() && !self.checking;
+                    let check = ui
+                        .add_enabled(can_check, Button::new("Check key"))
+                        .clicked();
+                    if (check || entered) && can_check {
+                        self.check_key(ui);
+                    }
+                    if self.checking {
+                        accessibility::spinner(ui, None);
+                    }
+                });
+                if !self.status.is_empty() {
+                    ui.colored_label(ui.visuals().error_fg_color, &self.status);
+                }
+            }
+            Step::Preferences => {
+                ui.heading("What to read");
+                ui.label("Country");
+                let selected = countries::index(*choices.country);
+                let entries = countries::entries();
+                if let Some(i) =
+                    combo::searchable(ui, "onboarding_country", &entries, Some(selected))
+                {
+                    *choices.country = countries::COUNTRIES[i].country;
+                }
+                ui.add_space(8.);
+                ui.label("Languages, none for every language");
+                ui.horizontal_wrapped(|ui| {
+                    for code in LANGUAGES {
+                        let mut checked = choices.languages.contains(code);
+                        if ui.checkbox(&mut checked, language::name(code)).changed() {
+                            if checked {
+                                choices.languages.insert(code.to_string());
+                            } else {
+                                choices.languages.remove(code);
+                            }
+                        }
+                    }
+                });
+                ui.add_space(8.);
+                ui.label("Categories, none for every category");
+                ui.horizontal_wrapped(|ui| {
+                    for category in Category::ALL {
+                        let mut checked = choices.categories.contains(&category);
+                        if ui.checkbox(&mut checked, category.name()).changed() {
+                            if checked {
+                                choices.categories.insert(category);
+                            } else {
+                                choices.categories.remove(&category);
+                            }
+                        }
+                    }
+                });
+                ui.add_space(8.);
+                ui.horizontal(|ui| {
+                    if ui.button("⬅ Back").clicked() {
+                        self.step = self.step.previous();
+                    }
+                    if ui.button("Next").clicked() {
+                        self.step = Step::Done;
+                    }
+                });
+            }
+            Step::Done => {
+                ui.heading("You're all set");
+                ui.label("Everything picked here can be changed later in the settings.");
+                ui.horizontal(|ui| {
+                    if ui.button("⬅ Back").clicked() {
+                        self.step = self.step.previous();
+                    }
+                    if ui.button("Start reading").clicked() {
+                        finish = Some(Finish::Feed);
+                    }
+                });
+            }
+        }
+        if matches!(self.step, Step::Welcome | Step::GetKey | Step::EnterKey) {
+            ui.add_space(16.);
+            ui.separator();
+            if ui.button("Try without API key").clicked() {
+                finish = Some(Finish::Demo);
+            }
+            ui.weak("Shows made up sample articles");
+        }
+        finish
+    }
+
+    /// Asks newsapi.org for a single headline with the key
+    fn check_key(&mut self, ui: &Ui) {
+        let mut request = ehttp::Request::get(KEY_CHECK_URL);
+        request
+            .headers
+            .insert("X-Api-Key".to_string(), self.key.trim().to_string());
+        self.checking = true;
+        self.status.clear();
+        let answer = self.answer.clone();
+        let ctx = ui.ctx().clone();
+        http::fetch(request, move |response| {
+            let result = match response {
+                Ok(response) if response.ok => Ok(()),
+                Ok(response) => Err(serde_json::from_slice::<ApiError>(&response.bytes)
+                    .map(|error| error.message)
+                    .unwrap_or_else(|_| format!("{} {}", response.status, response.status_text))),
+                Err(e) => Err(format!("Failed reaching newsapi.org: {}", e)),
+            };
+            *answer.lock().unwrap() = Some(result);
+            ctx.request_repaint();
+        });
+    }
+}
@@ -0,0 +1,162 @@
+use crate::http;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// Seconds before the first retry, doubled after every failed attempt
+const RETRY_DELAY: f64 = 5.;
+const MAX_RETRY_DELAY: f64 = 60. * 60.;
+/// Seconds between checks for the outcome of requests being sent
+const IN_FLIGHT_POLL_INTERVAL: f64 = 1.;
+
+/// A POST to an integration (webhook, read-later service…), kept until it is delivered
+#[derive(Clone, Serialize, Deserialize)]
+pub struct OutboundRequest {
+    /// What the request does, shown in the pending actions list
+    pub label: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+enum Outcome {
+    Delivered,
+    Retry(String),
+    Failed(String),
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PendingRequest {
+    id: u64,
+    pub request: OutboundRequest,
+    pub attempts: u32,
+    /// Relative to the session start, so a restart retries everything right away
+    #[serde(skip)]
+    next_attempt: f64,
+    #[serde(skip)]
+    in_flight: bool,
+}
+
+/// A request that was delivered, or rejected with an error, for the app to tell
+pub struct Report {
+    pub label: String,
+    pub error: Option<String>,
+}
+
+/// Persisted queue of outbound requests, retried with exponential backoff on network errors
+#[derive(Default, Serialize, Deserialize)]
+pub struct Outbox {
+    pending: Vec<PendingRequest>,
+    next_id: u64,
+    #[serde(skip)]
+    outcomes: Arc<Mutex<Vec<(u64, Outcome)>>>,
+    #[serde(skip)]
+    reports: Vec<Report>,
+}
+
+impl Outbox {
+    pub fn push(&mut self, request: OutboundRequest) {
+        self.pending.push(PendingRequest {
+            id: self.next_id,
+            request,
+            attempts: 0,
+            next_attempt: 0.,
+            in_flight: false,
+        });
+        self.next_id += 1;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &PendingRequest> {
+        self.pending.iter()
+    }
+
+    /// The requests done with since the last call
+    pub fn take_reports(&mut self) -> Vec<Report> {
+        std::mem::take(&mut self.reports)
+    }
+
+    /// Applies the outcomes of finished requests and sends the ones that are due.
+    /// Returns how many seconds until the outbox needs polling again, if at all.
+    pub fn poll(&mut self, now: f64) -> Option<f64> {
+        let outcomes = std::mem::take(&mut *self.outcomes.lock().unwrap());
+        for (id, outcome) in outcomes {
+            let index = match self.pending.iter().position(|p| p.id == id) {
+                Some(index) => index,
+                None => continue,
+            };
+            match outcome {
+                Outcome::Delivered => {
+                    let pending = self.pending.remove(index);
+                    tracing::info!("{} delivered", pending.request.label);
+                    self.reports.push(Report {
+                        label: pending.request.label,
+                        error: None,
+                    });
+                }
+                Outcome::Retry(e) => {
+                    let pending = &mut self.pending[index];
+                    pending.attempts += 1;
+                    pending.in_flight = false;
+                    let delay = RETRY_DELAY * 2f64.powi(pending.attempts as i32 - 1);
+                    pending.next_attempt = now + delay.min(MAX_RETRY_DELAY);
+                    tracing::warn!("{} failed, retrying later: {}", pending.request.label, e);
+                }
+                Outcome::Failed(e) => {
+                    let pending = self.pending.remove(index);
+                    tracing::error!("{} rejected: {}", pending.request.label, e);
+                    self.reports.push(Report {
+                        label: pending.request.label,
+                        error: Some(e),
+                    });
+                }
+            }
+        }
+
+        for pending in self
+            .pending
+            .iter_mut()
+            .filter(|p| !p.in_flight && p.next_attempt <= now)
+        {
+            pending.in_flight = true;
+            send(pending.id, &pending.request, self.outcomes.clone());
+        }
+
+        self.pending
+            .iter()
+            .map(|p| {
+                if p.in_flight {
+                    IN_FLIGHT_POLL_INTERVAL
+                } else {
+                    p.next_attempt - now
+                }
+            })
+            .reduce(f64::min)
+    }
+}
+
+fn send(id: u64, request: &OutboundRequest, outcomes: Arc<Mutex<Vec<(u64, Outcome)>>>) {
+    let mut http_request = ehttp::Request::post(&request.url, request.body.clone().into_bytes());
+    for (name, value) in &request.headers {
+        http_request.headers.insert(name.clone(), value.clone());
+    }
+
+    http::fetch(http_request, move |response| {
+        let outcome = match response {
+            Ok(response) if response.ok => Outcome::Delivered,
+            // timeouts, rate limits and server errors are worth another try
+            Ok(response) if matches!(response.status, 408 | 429 | 500..=599) => {
+                Outcome::Retry(format!("{} {}", response.status, response.status_text))
+            }
+            Ok(response) => Outcome::Failed(format!("{} {}", response.status, response.status_text)),
+            Err(e) => Outcome::Retry(e),
+        };
+        outcomes.lock().unwrap().push((id, outcome));
+    });
+}
@@ -0,0 +1,201 @@
+use crate::http;
+use crate::news::NewsCardData;
+use crate::outbox::OutboundRequest;
+use eframe::egui::{Button, Grid, TextEdit, Ui};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+const POCKET_API: &str = "https://getpocket.com/v3";
+const AUTHORIZE_URL: &str = "https://getpocket.com/auth/authorize";
+/// Where Pocket sends the browser once access is granted, the app only waits for a click
+const REDIRECT_URI: &str = "https://getpocket.com/connected_applications";
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PocketSettings {
+    /// Key of an app registered at getpocket.com/developer
+    pub consumer_key: String,
+    pub access_token: String,
+    /// Pocket account the access token is for
+    pub username: String,
+}
+
+impl PocketSettings {
+    pub fn is_connected(&self) -> bool {
+        !self.consumer_key.is_empty() && !self.access_token.is_empty()
+    }
+
+    /// Request adding the article to the Pocket list, for the outbox
+    pub fn save_request(&self, article: &NewsCardData) -> OutboundRequest {
+        let body = serde_json::json!({
+            "url": article.url,
+            "title": article.title,
+            "consumer_key": self.consumer_key,
+            "access_token": self.access_token,
+        });
+        OutboundRequest {
+            label: format!("Saving \"{}\" to Pocket", article.title),
+            url: format!("{}/add", POCKET_API),
+            headers: headers(),
+            body: body.to_string(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct RequestToken {
+    code: String,
+}
+
+#[derive(Deserialize)]
+struct AccessToken {
+    access_token: String,
+    username: String,
+}
+
+enum Answer {
+    RequestToken(String),
+    AccessToken(AccessToken),
+}
+
+/// Pocket's OAuth flow: a request token is granted access by the user in the browser, then traded
+/// for an access token
+#[derive(Default)]
+pub struct PocketLogin {
+    /// Request token waiting for the user to grant it access
+    code: Option<String>,
+    in_flight: bool,
+    status: String,
+    answer: Arc<Mutex<Option<Result<Answer, String>>>>,
+}
+
+impl PocketLogin {
+    pub fn show_settings(&mut self, ui: &mut Ui, settings: &mut PocketSettings) {
+        let answer = self.answer.lock().unwrap().take();
+        match answer {
+            Some(Ok(Answer::RequestToken(code))) => {
+                let url = format!(
+                    "{}?request_token={}&redirect_uri={}",
+                    AUTHORIZE_URL, code, REDIRECT_URI
+                );
+                ui.ctx().output().open_url(url);
+                self.code = Some(code);
+                self.in_flight = false;
+                self.status.clear();
+            }
+            Some(Ok(Answer::AccessToken(token))) => {
+                settings.access_token = token.access_token;
+                settings.username = token.username;
+                self.code = None;
+                self.in_flight = false;
+                self.status.clear();
+            }
+            Some(Err(e)) => {
+                self.code = None;
+                self.in_flight = false;
+                self.status = format!("Failed connecting: {}", e);
+            }
+            None => {}
+        }
+
+        Grid::new("pocket").num_columns(2).show(ui, |ui| {
+            ui.label("Consumer key");
+            ui.add(TextEdit::singleline(&mut settings.consumer_key).password(true));
+            ui.end_row();
+        });
+        if settings.is_connected() {
+            ui.horizontal(|ui| {
+                ui.label(format!("Connected as {}", settings.username));
+                if ui.button("Disconnect").clicked() {
+                    settings.access_token.clear();
+                    settings.username.clear();
+                }
+            });
+        } else if self.code.is_some() {
+            ui.weak("Grant headlines access in the browser, then come back here");
+            if ui
+                .add_enabled(!self.in_flight, Button::new("Finish connecting"))
+                .clicked()
+            {
+                self.authorize(settings, ui);
+            }
+        } else {
+            let can_connect = !settings.consumer_key.is_empty() && !self.in_flight;
+            if ui
+                .add_enabled(can_connect, Button::new("Connect"))
+                .on_disabled_hover_text("Needs the consumer key of a Pocket app")
+                .clicked()
+            {
+                self.request_token(settings, ui);
+            }
+        }
+        if !self.status.is_empty() {
+            ui.colored_label(ui.visuals().error_fg_color, &self.status);
+        }
+        ui.weak("The access token is stored unencrypted with the settings");
+    }
+
+    fn request_token(&mut self, settings: &PocketSettings, ui: &Ui) {
+        let body = serde_json::json!({
+            "consumer_key": settings.consumer_key,
+            "redirect_uri": REDIRECT_URI,
+        });
+        self.post("oauth/request", body, ui, |bytes| {
+            serde_json::from_slice::<RequestToken>(bytes)
+                .map(|token| Answer::RequestToken(token.code))
+        });
+    }
+
+    fn authorize(&mut self, settings: &PocketSettings, ui: &Ui) {
+        let code = match &self.code {
+            Some(code) => code,
+            None => return,
+        };
+        let body = serde_json::json!({
+            "consumer_key": settings.consumer_key,
+            "code": code,
+        });
+        self.post("oauth/authorize", body, ui, |bytes| {
+            serde_json::from_slice(bytes).map(Answer::AccessToken)
+        });
+    }
+
+    fn post(
+        &mut self,
+        path: &str,
+        body: serde_json::Value,
+        ui: &Ui,
+        parse: impl 'static + Send + FnOnce(&[u8]) -> serde_json::Result<Answer>,
+    ) {
+        let url = format!("{}/{}", POCKET_API, path);
+        let mut request = ehttp::Request::post(url, body.to_string().into_bytes());
+        request.headers.extend(headers());
+        self.in_flight = true;
+        let answer = self.answer.clone();
+        let ctx = ui.ctx().clone();
+        http::fetch(request, move |response| {
+            let result = match response {
+                Ok(response) if response.ok => parse(&response.bytes).map_err(|e| e.to_string()),
+                // Pocket tells what went wrong in a header
+                Ok(response) => Err(response
+                    .headers
+                    .get("x-error")
+                    .cloned()
+                    .unwrap_or_else(|| format!("{} {}", response.status, response.status_text))),
+                Err(e) => Err(e),
+            };
+            *answer.lock().unwrap() = Some(result);
+            ctx.request_repaint();
+        });
+    }
+}
+
+fn headers() -> Vec<(String, String)> {
+    vec![
+        (
+            "Content-Type".to_string(),
+            "application/json; charset=UTF-8".to_string(),
+        ),
+        ("X-Accept".to_string(), "application/json".to_string()),
+    ]
+}
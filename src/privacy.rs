@@ -0,0 +1,28 @@
+/// Whether window content protection is available on this platform
+pub const SUPPORTED: bool = cfg!(target_os = "windows");
+
+/// Excludes the window titled `title` from screen captures and recordings.
+/// Returns whether the protection could be changed.
+#[cfg(target_os = "windows")]
+pub fn set_content_protected(title: &str, protected: bool) -> bool {
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        FindWindowW, SetWindowDisplayAffinity, WDA_EXCLUDEFROMCAPTURE, WDA_NONE,
+    };
+
+    let title: Vec<u16> = title.encode_utf16().chain(Some(0)).collect();
+    let affinity = if protected {
+        WDA_EXCLUDEFROMCAPTURE
+    } else {
+        WDA_NONE
+    };
+    // SAFETY: `title` is a nul terminated UTF-16 string that outlives both calls
+    unsafe {
+        let hwnd = FindWindowW(std::ptr::null(), title.as_ptr());
+        hwnd != 0 && SetWindowDisplayAffinity(hwnd, affinity) != 0
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn set_content_protected(_title: &str, _protected: bool) -> bool {
+    false
+}
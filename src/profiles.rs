@@ -0,0 +1,114 @@
+use eframe::egui::{Align2, Button, Context, Key, TextEdit, Ui, Window};
+use serde::{Deserialize, Serialize};
+
+/// Profile whose settings are stored under the keys there were before profiles
+pub const DEFAULT_PROFILE: &str = "Default";
+
+/// Named sets of settings and bookmarks, like "work" and "personal", one of them in use
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profiles {
+    pub names: Vec<String>,
+    pub current: String,
+    /// Whether the app asks which profile to use when it starts
+    pub ask_at_startup: bool,
+    /// Name being typed for a new profile
+    #[serde(skip)]
+    new_name: String,
+}
+
+impl Default for Profiles {
+    fn default() -> Self {
+        Self {
+            names: vec![DEFAULT_PROFILE.to_string()],
+            current: DEFAULT_PROFILE.to_string(),
+            ask_at_startup: false,
+            new_name: String::new(),
+        }
+    }
+}
+
+impl Profiles {
+    /// Storage key of the current profile's value stored under `key`
+    pub fn key(&self, key: &str) -> String {
+        Self::key_of(key, &self.current)
+    }
+
+    pub fn key_of(key: &str, profile: &str) -> String {
+        if profile == DEFAULT_PROFILE {
+            key.to_string()
+        } else {
+            format!("{}@{}", key, profile)
+        }
+    }
+
+    /// Lists the profiles in a menu, to switch to one, add or remove one. Returns the profile to
+    /// switch to.
+    pub fn menu(&mut self, ui: &mut Ui) -> Option<String> {
+        let mut chosen = None;
+        for name in &self.names {
+            if ui.radio(*name == self.current, name).clicked() {
+                ui.close_menu();
+                if *name != self.current {
+                    chosen = Some(name.clone());
+                }
+            }
+        }
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            let name = ui.add(
+                TextEdit::singleline(&mut self.new_name)
+                    .hint_text("New profile")
+                    .desired_width(100.),
+            );
+            let entered = name.lost_focus() && ui.input().key_pressed(Key::Enter);
+            if (ui.button("Add").clicked() || entered) && self.add() {
+                ui.close_menu();
+                chosen = self.names.last().cloned();
+            }
+        });
+
+        let removable = self.current != DEFAULT_PROFILE;
+        if ui
+            .add_enabled(removable, Button::new("Remove this profile"))
+            .on_disabled_hover_text("The default profile can't be removed")
+            .clicked()
+        {
+            ui.close_menu();
+            let removed = self.current.clone();
+            self.names.retain(|name| *name != removed);
+            chosen = Some(DEFAULT_PROFILE.to_string());
+        }
+        ui.checkbox(&mut self.ask_at_startup, "Ask at startup");
+        chosen
+    }
+
+    /// Asks which profile to use. Returns the one picked.
+    pub fn picker(&self, ctx: &Context) -> Option<String> {
+        let mut chosen = None;
+        Window::new("Choose a profile")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, [0., 0.])
+            .show(ctx, |ui| {
+                for name in &self.names {
+                    if ui.button(name).clicked() {
+                        chosen = Some(name.clone());
+                    }
+                }
+            });
+        chosen
+    }
+
+    /// Adds the profile being typed, returns whether its name was free
+    fn add(&mut self) -> bool {
+        let name = self.new_name.trim().to_string();
+        if name.is_empty() || self.names.contains(&name) {
+            return false;
+        }
+        self.names.push(name);
+        self.new_name.clear();
+        true
+    }
+}
@@ -0,0 +1,32 @@
+use eframe::egui::{vec2, Color32, Rect, Sense, Ui, Vec2};
+use qrcode::{Color, QrCode};
+
+/// Side of a module, the smallest square of the code, in points
+const MODULE_SIZE: f32 = 4.;
+/// Light modules around the code, scanners need them to find it
+const QUIET_ZONE: usize = 4;
+
+/// Draws `data` as a QR code, always dark on white so phones can scan it whatever the theme
+pub fn show(ui: &mut Ui, data: &str) {
+    let code = match QrCode::new(data) {
+        Ok(code) => code,
+        Err(e) => {
+            ui.label(format!("Can't encode this link as a QR code: {}", e));
+            return;
+        }
+    };
+
+    let width = code.width();
+    let side = (width + 2 * QUIET_ZONE) as f32 * MODULE_SIZE;
+    let (rect, _) = ui.allocate_exact_size(Vec2::splat(side), Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 0., Color32::WHITE);
+    for (i, color) in code.to_colors().into_iter().enumerate() {
+        if color == Color::Dark {
+            let x = (i % width + QUIET_ZONE) as f32 * MODULE_SIZE;
+            let y = (i / width + QUIET_ZONE) as f32 * MODULE_SIZE;
+            let module = Rect::from_min_size(rect.min + vec2(x, y), Vec2::splat(MODULE_SIZE));
+            painter.rect_filled(module, 0., Color32::BLACK);
+        }
+    }
+}
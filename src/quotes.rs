@@ -0,0 +1,234 @@
+use crate::http;
+use eframe::egui::{Color32, Context, DragValue, Grid, RichText, TextEdit, Ui};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const STOOQ_QUOTE: &str = "https://stooq.com/q/l/";
+const COINGECKO_PRICE: &str = "https://api.coingecko.com/api/v3/simple/price";
+const RISING: Color32 = Color32::from_rgb(40, 170, 70);
+const FALLING: Color32 = Color32::from_rgb(200, 40, 40);
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuoteSettings {
+    /// Whether the strip of prices is shown under the top panel
+    pub enabled: bool,
+    /// Comma separated Stooq symbols, like aapl.us
+    pub stocks: String,
+    /// Comma separated CoinGecko ids, like bitcoin
+    pub crypto: String,
+    /// Currency the crypto prices are in
+    pub currency: String,
+    /// Minutes between two fetches of the prices
+    pub interval: u32,
+}
+
+impl Default for QuoteSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            stocks: "aapl.us, msft.us".to_string(),
+            crypto: "bitcoin, ethereum".to_string(),
+            currency: "usd".to_string(),
+            interval: 5,
+        }
+    }
+}
+
+impl QuoteSettings {
+    pub fn show(&mut self, ui: &mut Ui) {
+        ui.checkbox(&mut self.enabled, "Show prices under the top panel");
+        Grid::new("quotes").num_columns(2).show(ui, |ui| {
+            ui.label("Stocks");
+            ui.add(TextEdit::singleline(&mut self.stocks).hint_text("aapl.us, msft.us"));
+            ui.end_row();
+            ui.label("Crypto");
+            ui.add(TextEdit::singleline(&mut self.crypto).hint_text("bitcoin, ethereum"));
+            ui.end_row();
+            ui.label("Crypto currency");
+            ui.add(TextEdit::singleline(&mut self.currency).desired_width(60.));
+            ui.end_row();
+            ui.label("Every");
+            ui.horizontal(|ui| {
+                ui.add(DragValue::new(&mut self.interval).clamp_range(1..=120));
+                ui.label("minutes");
+            });
+            ui.end_row();
+        });
+        ui.weak("Stock prices by Stooq, crypto prices by CoinGecko");
+    }
+
+    fn stocks(&self) -> impl Iterator<Item = String> + '_ {
+        list(&self.stocks)
+    }
+
+    fn crypto(&self) -> impl Iterator<Item = String> + '_ {
+        list(&self.crypto)
+    }
+
+    /// Symbols in the order they're shown
+    fn symbols(&self) -> impl Iterator<Item = String> + '_ {
+        self.stocks().chain(self.crypto())
+    }
+}
+
+fn list(symbols: &str) -> impl Iterator<Item = String> + '_ {
+    symbols
+        .split(',')
+        .map(|symbol| symbol.trim().to_lowercase())
+        .filter(|symbol| !symbol.is_empty())
+}
+
+#[derive(Clone)]
+struct Quote {
+    price: f64,
+    /// Percent the price moved, since the opening for stocks and over 24 hours for crypto
+    change: Option<f64>,
+}
+
+/// Prices of the symbols a request got, or why it failed
+type Answers = Arc<Mutex<Vec<Result<Vec<(String, Quote)>, String>>>>;
+
+/// Latest prices of the symbols, fetched every few minutes
+#[derive(Default)]
+pub struct Quotes {
+    quotes: BTreeMap<String, Quote>,
+    next_fetch: f64,
+    /// Settings the prices were last fetched for, they're fetched again when it changes
+    fetched_for: String,
+    answers: Answers,
+}
+
+impl Quotes {
+    /// Fetches the prices when they're due, returns the errors of the last fetch
+    pub fn poll(&mut self, ctx: &Context, settings: &QuoteSettings) -> Vec<String> {
+        let mut errors = Vec::new();
+        for answer in self.answers.lock().unwrap().drain(..) {
+            match answer {
+                Ok(quotes) => self.quotes.extend(quotes),
+                Err(e) => errors.push(e),
+            }
+        }
+        if !settings.enabled {
+            return errors;
+        }
+
+        let now = ctx.input().time;
+        let fetched_for = format!(
+            "{}|{}|{}",
+            settings.stocks, settings.crypto, settings.currency
+        );
+        if fetched_for != self.fetched_for {
+            // the prices of removed symbols or of another currency would linger
+            self.quotes.clear();
+            self.fetched_for = fetched_for;
+            self.next_fetch = now;
+        }
+        if now >= self.next_fetch {
+            self.next_fetch = now + f64::from(settings.interval.max(1)) * 60.;
+            for symbol in settings.stocks() {
+                fetch_stock(symbol, self.answers.clone(), ctx.clone());
+            }
+            let crypto: Vec<_> = settings.crypto().collect();
+            if !crypto.is_empty() {
+                let currency = settings.currency.trim().to_lowercase();
+                fetch_crypto(crypto, currency, self.answers.clone(), ctx.clone());
+            }
+        }
+        ctx.request_repaint_after(Duration::from_secs_f64((self.next_fetch - now).max(0.)));
+        errors
+    }
+
+    /// Draws the strip of prices, a symbol is left out until its price is known
+    pub fn show(&self, ui: &mut Ui, settings: &QuoteSettings) {
+        ui.horizontal_wrapped(|ui| {
+            for symbol in settings.symbols() {
+                let quote = match self.quotes.get(&symbol) {
+                    Some(quote) => quote,
+                    None => continue,
+                };
+                ui.label(RichText::new(symbol.to_uppercase()).strong());
+                ui.label(format!("{:.2}", quote.price));
+                if let Some(change) = quote.change {
+                    let (arrow, color) = if change < 0. {
+                        ("▼", FALLING)
+                    } else {
+                        ("▲", RISING)
+                    };
+                    ui.colored_label(color, format!("{} {:.2} %", arrow, change.abs()));
+                }
+                ui.add_space(8.);
+            }
+        });
+    }
+}
+
+/// Today's quote of a stock, as Stooq's CSV
+fn fetch_stock(symbol: String, answers: Answers, ctx: Context) {
+    let url = format!("{}?s={}&f=sd2t2ohlcv&h&e=csv", STOOQ_QUOTE, symbol);
+    http::fetch(ehttp::Request::get(url), move |response| {
+        let answer = match response {
+            Ok(response) if response.ok => read_stock(&response.bytes)
+                .map(|quote| vec![(symbol.clone(), quote)])
+                .ok_or_else(|| format!("No price for {}", symbol)),
+            Ok(response) => Err(format!("{} {}", response.status, response.status_text)),
+            Err(e) => Err(e),
+        };
+        answers.lock().unwrap().push(answer);
+        ctx.request_repaint();
+    });
+}
+
+/// Reads the header and the row of the quote, unknown symbols have N/D for their values
+fn read_stock(csv: &[u8]) -> Option<Quote> {
+    let csv = String::from_utf8_lossy(csv);
+    let mut lines = csv.lines();
+    let header: Vec<_> = lines.next()?.split(',').collect();
+    let row: Vec<_> = lines.next()?.split(',').collect();
+    let value = |name: &str| -> Option<f64> {
+        let i = header
+            .iter()
+            .position(|column| column.eq_ignore_ascii_case(name))?;
+        row.get(i)?.parse().ok()
+    };
+    let price = value("close")?;
+    let change = value("open")
+        .filter(|open| *open > 0.)
+        .map(|open| (price - open) / open * 100.);
+    Some(Quote { price, change })
+}
+
+/// The prices of the coins in the currency, with CoinGecko's change over 24 hours
+fn fetch_crypto(ids: Vec<String>, currency: String, answers: Answers, ctx: Context) {
+    let url = format!(
+        "{}?ids={}&vs_currencies={}&include_24hr_change=true",
+        COINGECKO_PRICE,
+        ids.join(","),
+        currency
+    );
+    http::fetch(ehttp::Request::get(url), move |response| {
+        let answer = match response {
+            Ok(response) if response.ok => serde_json::from_slice::<
+                BTreeMap<String, BTreeMap<String, Option<f64>>>,
+            >(&response.bytes)
+            .map_err(|e| format!("Unreadable prices: {}", e))
+            .map(|prices| {
+                let change_key = format!("{}_24h_change", currency);
+                prices
+                    .into_iter()
+                    .filter_map(|(id, values)| {
+                        let price = values.get(&currency).copied().flatten()?;
+                        let change = values.get(&change_key).copied().flatten();
+                        Some((id, Quote { price, change }))
+                    })
+                    .collect()
+            }),
+            Ok(response) => Err(format!("{} {}", response.status, response.status_text)),
+            Err(e) => Err(e),
+        };
+        answers.lock().unwrap().push(answer);
+        ctx.request_repaint();
+    });
+}
@@ -0,0 +1,684 @@
+use crate::accessibility;
+use crate::charset;
+use crate::fonts;
+use crate::http;
+use crate::http_cache::{SharedHttpCache, NOT_MODIFIED};
+use crate::news::{domain, NewsCardData};
+use chrono::Utc;
+use eframe::egui::popup::popup_below_widget;
+use eframe::egui::text::LayoutJob;
+use eframe::egui::{
+    vec2, Align, Button, Checkbox, Color32, Context, CursorIcon, FontFamily, FontId, Galley,
+    Hyperlink, Key, Label, Modifiers, ProgressBar, ScrollArea, Sense, Slider, Stroke, TextEdit,
+    TextFormat, TextStyle, Ui,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::sync::{Arc, Mutex};
+
+/// Titles of the pages loaded in the reader, by url
+pub type PageTitles = Arc<Mutex<BTreeMap<String, String>>>;
+
+/// Paragraphs shorter than this are menus, bylines and captions rather than article text
+const MIN_PARAGRAPH_LEN: usize = 40;
+
+const PARAGRAPH_SPACING: f32 = 8.;
+
+/// Typography of the reader, independent from the feed's
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReaderSettings {
+    /// Widest the text column gets, in points
+    pub line_width: f32,
+    /// Line height as a multiple of the font size
+    pub line_height: f32,
+    pub serif: bool,
+    pub justify: bool,
+    /// Space kept on both sides of the text column, in points
+    pub margin: f32,
+}
+
+impl Default for ReaderSettings {
+    fn default() -> Self {
+        Self {
+            line_width: 600.,
+            line_height: 1.3,
+            serif: false,
+            justify: false,
+            margin: 16.,
+        }
+    }
+}
+
+impl ReaderSettings {
+    fn editor(&mut self, ui: &mut Ui) {
+        ui.add(Slider::new(&mut self.line_width, 300.0..=1200.0).text("Line width"));
+        ui.add(Slider::new(&mut self.line_height, 1.0..=2.0).text("Line height"));
+        ui.add(Slider::new(&mut self.margin, 0.0..=100.0).text("Margins"));
+        ui.add(Checkbox::new(&mut self.serif, "Serif font"));
+        ui.add(Checkbox::new(&mut self.justify, "Justify"));
+        if ui.button("Reset").clicked() {
+            *self = Self::default();
+        }
+    }
+}
+
+/// Paragraph of the article with the links it contains
+struct Paragraph {
+    text: String,
+    /// Byte range in `text` and absolute url of each link
+    links: Vec<(Range<usize>, String)>,
+}
+
+impl Paragraph {
+    fn link_at(&self, byte: usize) -> Option<&(Range<usize>, String)> {
+        self.links.iter().find(|(range, _)| range.contains(&byte))
+    }
+}
+
+enum Content {
+    Loading,
+    Loaded(Vec<Paragraph>),
+    Failed(String),
+}
+
+/// What the reader asks of the app
+pub enum ReaderAction {
+    None,
+    Close,
+    /// Open a link of the article in the reader
+    Open(NewsCardData),
+}
+
+#[derive(Default)]
+struct Search {
+    query: String,
+    /// Index of the selected match
+    current: usize,
+    /// Scroll to the selected match on the next frame
+    scroll: bool,
+    focus: bool,
+}
+
+impl Search {
+    fn next(&mut self, count: usize) {
+        if count > 0 {
+            self.current = (self.current + 1) % count;
+            self.scroll = true;
+        }
+    }
+
+    fn previous(&mut self, count: usize) {
+        if count > 0 {
+            self.current = (self.current + count - 1) % count;
+            self.scroll = true;
+        }
+    }
+}
+
+/// Full text of an article, extracted from its page
+pub struct Reader {
+    pub article: NewsCardData,
+    content: Arc<Mutex<Content>>,
+    titles: PageTitles,
+    search: Option<Search>,
+    /// Paragraph and url of the link whose menu is open
+    link_menu: Option<(usize, String)>,
+    /// Share of the text scrolled past, as of the last frame
+    progress: f32,
+    /// Progress to scroll back to once the text is laid out
+    restore: Option<f32>,
+    /// How far the text scrolls, as of the last frame
+    scroll_range: f32,
+}
+
+impl Reader {
+    /// Opens the article, scrolled to `progress` if it was read before
+    pub fn open(
+        article: NewsCardData,
+        progress: Option<f32>,
+        titles: PageTitles,
+        cache: SharedHttpCache,
+        ctx: &Context,
+    ) -> Self {
+        let content = Arc::new(Mutex::new(Content::Loading));
+        let result = content.clone();
+        let page_titles = titles.clone();
+        let url = article.url.clone();
+        let ctx = ctx.clone();
+        let request = cache.lock().unwrap().request(&article.url);
+        http::fetch(request, move |response| {
+            let html = match response {
+                Ok(response) if response.status == NOT_MODIFIED => cache
+                    .lock()
+                    .unwrap()
+                    .body(&url)
+                    .ok_or_else(|| "Not modified, yet not cached".to_string()),
+                Ok(response) if response.ok => {
+                    let content_type = response.headers.get("content-type");
+                    let html = charset::decode(&response.bytes, content_type.map(String::as_str));
+                    cache.lock().unwrap().store(&url, &response.headers, &html);
+                    Ok(html.into_owned())
+                }
+                Ok(response) => Err(format!("{} {}", response.status, response.status_text)),
+                Err(e) => Err(e),
+            };
+            let loaded = match html {
+                Ok(html) => {
+                    let paragraphs = extract_paragraphs(&html, &url);
+                    if let Some(title) = extract_title(&html) {
+                        page_titles.lock().unwrap().insert(url, title);
+                    }
+                    Content::Loaded(paragraphs)
+                }
+                Err(e) => Content::Failed(e),
+            };
+            *result.lock().unwrap() = loaded;
+            ctx.request_repaint();
+        });
+
+        Self {
+            article,
+            content,
+            titles,
+            search: None,
+            link_menu: None,
+            progress: progress.unwrap_or(0.),
+            restore: progress,
+            scroll_range: 0.,
+        }
+    }
+
+    pub fn progress(&self) -> f32 {
+        self.progress
+    }
+
+    /// Draws the reader, `known_title` finds the titles of the app's own articles for link previews
+    pub fn show(
+        &mut self,
+        ui: &mut Ui,
+        settings: &mut ReaderSettings,
+        known_title: &dyn Fn(&str) -> Option<String>,
+    ) -> ReaderAction {
+        let mut action = ReaderAction::None;
+        ui.horizontal(|ui| {
+            if ui.button("⬅ Back").clicked() {
+                action = ReaderAction::Close;
+            }
+            ui.add(Hyperlink::from_label_and_url(
+                "open in browser ⤴",
+                &self.article.url,
+            ));
+            let typography = ui.menu_button("Aa", |ui| settings.editor(ui));
+            accessibility::label(typography.response, "Reader typography");
+        });
+        ui.heading(&self.article.title);
+        ui.separator();
+
+        if ui.input_mut().consume_key(Modifiers::COMMAND, Key::F) {
+            self.search.get_or_insert_with(Search::default).focus = true;
+        }
+
+        let content = self.content.lock().unwrap();
+        let matches = match (&*content, &self.search) {
+            (Content::Loaded(paragraphs), Some(search)) => find_matches(paragraphs, &search.query),
+            _ => Vec::new(),
+        };
+        if let Some(search) = &mut self.search {
+            if !search_bar(ui, search, matches.len()) {
+                self.search = None;
+            }
+        }
+
+        match &*content {
+            Content::Loading => {
+                ui.label("Loading ⌛");
+            }
+            Content::Failed(e) => {
+                ui.label(format!("Couldn't load the article: {}", e));
+            }
+            Content::Loaded(paragraphs) if paragraphs.is_empty() => {
+                ui.label("No article text found on this page");
+            }
+            Content::Loaded(paragraphs) => {
+                let current = self.search.as_ref().map(|search| search.current);
+                let scroll = self
+                    .search
+                    .as_mut()
+                    .map_or(false, |search| std::mem::take(&mut search.scroll));
+                // paragraph holding the selected match
+                let target = current.and_then(|n| matches.get(n)).map(|m| m.0);
+
+                ui.add(ProgressBar::new(self.progress).show_percentage());
+
+                let mut scroll_area = ScrollArea::vertical().id_source(&self.article.url);
+                if self.scroll_range > 0. {
+                    if let Some(progress) = self.restore.take() {
+                        scroll_area =
+                            scroll_area.vertical_scroll_offset(progress * self.scroll_range);
+                    }
+                }
+                let output = scroll_area.show(ui, |ui| {
+                    let available = ui.available_width();
+                    let column = settings
+                        .line_width
+                        .min(available - 2. * settings.margin)
+                        .max(0.);
+                    ui.horizontal(|ui| {
+                        ui.add_space((available - column) / 2.);
+                        ui.vertical(|ui| {
+                            ui.set_width(column);
+                            for (i, paragraph) in paragraphs.iter().enumerate() {
+                                let highlights: Vec<_> = matches
+                                    .iter()
+                                    .enumerate()
+                                    .filter(|(_, (p, _))| *p == i)
+                                    .map(|(n, (_, range))| (range.clone(), Some(n) == current))
+                                    .collect();
+                                let galley =
+                                    layout_paragraph(ui, paragraph, &highlights, settings, column);
+                                let response =
+                                    ui.add(Label::new(galley.clone()).sense(Sense::click()));
+                                if scroll && target == Some(i) {
+                                    response.scroll_to_me(Some(Align::Center));
+                                }
+
+                                let hovered_link = response.hover_pos().and_then(|pos| {
+                                    let cursor = galley.cursor_from_pos(pos - response.rect.min);
+                                    let byte = byte_offset(&paragraph.text, cursor.ccursor.index);
+                                    paragraph.link_at(byte)
+                                });
+                                if let Some((_, url)) = hovered_link {
+                                    ui.output().cursor_icon = CursorIcon::PointingHand;
+                                    let title = page_title(&self.titles, known_title, url);
+                                    response.clone().on_hover_ui_at_pointer(|ui| {
+                                        ui.strong(domain(url));
+                                        if let Some(title) = title {
+                                            ui.label(title);
+                                        }
+                                        ui.weak("Click for options");
+                                    });
+                                    if response.clicked() {
+                                        self.link_menu = Some((i, url.clone()));
+                                        ui.memory().open_popup(ui.id().with(("link_menu", i)));
+                                    }
+                                }
+
+                                if let Some((p, url)) = &self.link_menu {
+                                    if *p == i {
+                                        let popup_id = ui.id().with(("link_menu", i));
+                                        popup_below_widget(ui, popup_id, &response, |ui| {
+                                            if ui.button("Open in browser ⤴").clicked() {
+                                                ui.output().open_url(url);
+                                                ui.memory().close_popup();
+                                            }
+                                            if ui.button("Open in reader 📖").clicked() {
+                                                action = ReaderAction::Open(link_article(
+                                                    paragraph,
+                                                    url,
+                                                    page_title(&self.titles, known_title, url),
+                                                ));
+                                                ui.memory().close_popup();
+                                            }
+                                        });
+                                    }
+                                }
+                                ui.add_space(PARAGRAPH_SPACING);
+                            }
+                        });
+                    });
+                    ui.min_rect().height()
+                });
+
+                self.scroll_range = (output.inner - output.inner_rect.height()).max(0.);
+                self.progress = if self.scroll_range > 0. {
+                    (output.state.offset.y / self.scroll_range).clamp(0., 1.)
+                } else {
+                    1.
+                };
+                if self.restore.is_some() {
+                    if self.scroll_range > 0. {
+                        // laid out now, the offset can be restored on the next frame
+                        ui.ctx().request_repaint();
+                    } else {
+                        self.restore = None;
+                    }
+                }
+            }
+        }
+
+        action
+    }
+}
+
+/// Title of the page at `url`, when it's one of the app's articles or was loaded in the reader
+fn page_title(
+    titles: &PageTitles,
+    known_title: &dyn Fn(&str) -> Option<String>,
+    url: &str,
+) -> Option<String> {
+    known_title(url).or_else(|| titles.lock().unwrap().get(url).cloned())
+}
+
+/// Article to open in the reader for a link of the paragraph
+fn link_article(paragraph: &Paragraph, url: &str, title: Option<String>) -> NewsCardData {
+    let text = paragraph
+        .links
+        .iter()
+        .find(|(_, link)| link == url)
+        .map(|(range, _)| paragraph.text[range.clone()].to_string());
+    NewsCardData {
+        title: title.or(text).unwrap_or_else(|| url.to_string()),
+        desc: String::new(),
+        url: url.to_string(),
+        source: domain(url).to_string(),
+        fetched_at: Utc::now(),
+        country: String::new(),
+        language: String::new(),
+        discussion: None,
+        thumbnail: None,
+        author: None,
+        published_at: None,
+    }
+}
+
+fn byte_offset(text: &str, char_index: usize) -> usize {
+    text.char_indices()
+        .nth(char_index)
+        .map_or(text.len(), |(byte, _)| byte)
+}
+
+/// Find bar, returns false once it's closed
+fn search_bar(ui: &mut Ui, search: &mut Search, count: usize) -> bool {
+    let mut open = true;
+    search.current = search.current.min(count.saturating_sub(1));
+    ui.horizontal(|ui| {
+        let field = ui.add(
+            TextEdit::singleline(&mut search.query)
+                .hint_text("Find in article")
+                .desired_width(160.),
+        );
+        if std::mem::take(&mut search.focus) {
+            field.request_focus();
+        }
+        if field.changed() {
+            search.current = 0;
+            search.scroll = true;
+        }
+        if field.lost_focus() && ui.input().key_pressed(Key::Enter) {
+            if ui.input().modifiers.shift {
+                search.previous(count);
+            } else {
+                search.next(count);
+            }
+            field.request_focus();
+        }
+
+        if count > 0 {
+            ui.label(format!("{}/{}", search.current + 1, count));
+        } else if !search.query.is_empty() {
+            ui.label("No matches");
+        }
+        let previous_btn = ui
+            .add_enabled(count > 0, Button::new("⏶"))
+            .on_hover_text("Previous match (Shift+Enter)");
+        if previous_btn.clicked() {
+            search.previous(count);
+        }
+        let next_btn = ui
+            .add_enabled(count > 0, Button::new("⏷"))
+            .on_hover_text("Next match (Enter)");
+        if next_btn.clicked() {
+            search.next(count);
+        }
+        let close_btn = accessibility::label(ui.button("✖"), "Close search");
+        if close_btn.clicked() || ui.input().key_pressed(Key::Escape) {
+            open = false;
+        }
+    });
+    open
+}
+
+/// Paragraph index and byte range of every occurrence of the query, ignoring ASCII case
+fn find_matches(paragraphs: &[Paragraph], query: &str) -> Vec<(usize, Range<usize>)> {
+    let mut matches = Vec::new();
+    if query.is_empty() {
+        return matches;
+    }
+    let query = query.to_ascii_lowercase();
+    for (i, paragraph) in paragraphs.iter().enumerate() {
+        // ASCII lowercasing keeps the byte offsets of the original text
+        let lower = paragraph.text.to_ascii_lowercase();
+        for (start, _) in lower.match_indices(&query) {
+            matches.push((i, start..start + query.len()));
+        }
+    }
+    matches
+}
+
+/// Layout of a paragraph with its links and matches highlighted, the selected match stands out
+fn layout_paragraph(
+    ui: &Ui,
+    paragraph: &Paragraph,
+    highlights: &[(Range<usize>, bool)],
+    settings: &ReaderSettings,
+    width: f32,
+) -> Arc<Galley> {
+    let size = TextStyle::Body.resolve(ui.style()).size;
+    let family = if settings.serif {
+        FontFamily::Name(fonts::SERIF.into())
+    } else {
+        FontFamily::Proportional
+    };
+    let normal = TextFormat {
+        font_id: FontId::new(size, family),
+        color: ui.visuals().text_color(),
+        ..Default::default()
+    };
+    let mut job = LayoutJob::default();
+    job.wrap.max_width = width;
+    job.justify = settings.justify;
+
+    // split the text wherever a link or a match starts or ends
+    let text = &paragraph.text;
+    let mut bounds = vec![0, text.len()];
+    for range in highlights
+        .iter()
+        .map(|(range, _)| range)
+        .chain(paragraph.links.iter().map(|(range, _)| range))
+    {
+        bounds.push(range.start);
+        bounds.push(range.end);
+    }
+    bounds.sort_unstable();
+    bounds.dedup();
+    for span in bounds.windows(2) {
+        let mut format = normal.clone();
+        if paragraph.link_at(span[0]).is_some() {
+            format.color = ui.visuals().hyperlink_color;
+            format.underline = Stroke::new(1., format.color);
+        }
+        match highlights
+            .iter()
+            .find(|(range, _)| range.contains(&span[0]))
+        {
+            Some((_, true)) => {
+                format.background = ui.visuals().warn_fg_color;
+                format.color = Color32::BLACK;
+            }
+            Some((_, false)) => format.background = ui.visuals().selection.bg_fill,
+            None => {}
+        }
+        job.append(&text[span[0]..span[1]], 0., format);
+    }
+
+    let galley = ui.fonts().layout_job(job);
+    let extra = (settings.line_height - 1.) * size;
+    if extra <= 0. {
+        return galley;
+    }
+    Arc::new(spread_rows(&galley, extra))
+}
+
+/// Copy of the galley with `extra` points added between its rows, egui has no line height setting
+fn spread_rows(galley: &Galley, extra: f32) -> Galley {
+    let mut galley = galley.clone();
+    for (i, row) in galley.rows.iter_mut().enumerate() {
+        let offset = vec2(0., i as f32 * extra);
+        row.rect = row.rect.translate(offset);
+        row.visuals.mesh.translate(offset);
+        row.visuals.mesh_bounds = row.visuals.mesh_bounds.translate(offset);
+        for glyph in &mut row.glyphs {
+            glyph.pos += offset;
+        }
+    }
+    let spread = galley.rows.len().saturating_sub(1) as f32 * extra;
+    galley.rect.max.y += spread;
+    galley.mesh_bounds.max.y += spread;
+    galley
+}
+
+/// Text of the `<p>` elements of a page, without the markup but with their links
+fn extract_paragraphs(html: &str, url: &str) -> Vec<Paragraph> {
+    // ASCII lowercasing keeps the byte offsets of the original page
+    let lower = html.to_ascii_lowercase();
+    let mut paragraphs = Vec::new();
+    let mut pos = 0;
+    while let Some(offset) = lower[pos..].find("<p") {
+        pos += offset + 2;
+        // <p> and <p class="..."> but not <pre> or <picture>
+        if !matches!(
+            lower.as_bytes().get(pos),
+            Some(b'>' | b' ' | b'\t' | b'\n' | b'\r')
+        ) {
+            continue;
+        }
+        let start = match lower[pos..].find('>') {
+            Some(i) => pos + i + 1,
+            None => break,
+        };
+        let end = lower[start..]
+            .find("</p")
+            .map_or(lower.len(), |i| start + i);
+        let paragraph = parse_paragraph(&html[start..end], url);
+        if paragraph.text.len() >= MIN_PARAGRAPH_LEN {
+            paragraphs.push(paragraph);
+        }
+        pos = end;
+    }
+    paragraphs
+}
+
+/// Text of a paragraph's markup with collapsed whitespace, and the ranges of its links
+fn parse_paragraph(html: &str, url: &str) -> Paragraph {
+    let mut text = String::new();
+    let mut links = Vec::new();
+    let mut open_link: Option<(usize, String)> = None;
+    let mut rest = html;
+    loop {
+        let (chunk, tag) = match rest.find('<') {
+            Some(i) => {
+                let close = rest[i..].find('>').map_or(rest.len(), |j| i + j);
+                let tag = &rest[i + 1..close];
+                let chunk = &rest[..i];
+                rest = rest.get(close + 1..).unwrap_or("");
+                (chunk, Some(tag))
+            }
+            None => (std::mem::take(&mut rest), None),
+        };
+        push_text(&mut text, &decode_entities(chunk));
+
+        let tag = match tag {
+            Some(tag) => tag,
+            None => break,
+        };
+        // the name is matched whatever its case, the attributes' values are kept as they are
+        let name = tag.split_whitespace().next().unwrap_or("");
+        if name.eq_ignore_ascii_case("/a") {
+            if let Some((start, href)) = open_link.take() {
+                let end = text.trim_end().len();
+                if end > start {
+                    links.push((start..end, href));
+                }
+            }
+        } else if name.eq_ignore_ascii_case("a") && name.len() < tag.len() {
+            open_link = attribute(tag, "href")
+                .and_then(|href| resolve_link(url, &href))
+                .map(|href| (text.len(), href));
+        }
+    }
+
+    text.truncate(text.trim_end().len());
+    for (range, _) in &mut links {
+        range.end = range.end.min(text.len());
+    }
+    Paragraph { text, links }
+}
+
+/// Appends the text with its runs of whitespace collapsed to single spaces
+fn push_text(text: &mut String, chunk: &str) {
+    for c in chunk.chars() {
+        if !c.is_whitespace() {
+            text.push(c);
+        } else if !text.is_empty() && !text.ends_with(' ') {
+            text.push(' ');
+        }
+    }
+}
+
+/// Value of an attribute in the contents of a tag, as in `a href="..."`, its name in any case
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    // lowercasing ASCII keeps the offsets
+    let start = tag.to_ascii_lowercase().find(&format!("{}=", name))? + name.len() + 1;
+    let value = &tag[start..];
+    let value = match value.chars().next()? {
+        quote @ ('"' | '\'') => value[1..].split(quote).next()?,
+        _ => value.split(char::is_whitespace).next()?,
+    };
+    Some(decode_entities(value))
+}
+
+/// Absolute url of a link found on the page at `base`, anchors and relative paths are dropped
+fn resolve_link(base: &str, href: &str) -> Option<String> {
+    let href = href.trim();
+    if href.starts_with("http://") || href.starts_with("https://") {
+        Some(href.to_string())
+    } else if let Some(rest) = href.strip_prefix("//") {
+        let scheme = base.split("://").next()?;
+        Some(format!("{}://{}", scheme, rest))
+    } else if href.starts_with('/') {
+        let host = base.find("://")? + 3;
+        let origin = base[host..].find('/').map_or(base, |i| &base[..host + i]);
+        Some(format!("{}{}", origin, href))
+    } else {
+        None
+    }
+}
+
+fn extract_title(html: &str) -> Option<String> {
+    let lower = html.to_ascii_lowercase();
+    let open = lower.find("<title")?;
+    let start = open + lower[open..].find('>')? + 1;
+    let end = start + lower[start..].find("</title")?;
+    let mut title = String::new();
+    push_text(&mut title, &decode_entities(&html[start..end]));
+    let title = title.trim_end().to_string();
+    if title.is_empty() {
+        None
+    } else {
+        Some(title)
+    }
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&nbsp;", " ")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#039;", "'")
+        .replace("&apos;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        // last so that "&amp;lt;" stays "&lt;"
+        .replace("&amp;", "&")
+}
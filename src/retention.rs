@@ -0,0 +1,205 @@
+use chrono::{DateTime, Utc};
+use eframe::egui::{Context, DragValue, Grid, Ui};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// Seconds between two cleanups of the caches
+const CLEANUP_INTERVAL: f64 = 60. * 60.;
+const MEGABYTE: u64 = 1024 * 1024;
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetentionSettings {
+    /// Days the cached pages, images, past headlines and hidden articles are kept
+    pub days: u32,
+    /// Megabytes the caches may take on disk, the least recently used files go first
+    pub max_disk_mb: u32,
+}
+
+impl Default for RetentionSettings {
+    fn default() -> Self {
+        Self {
+            days: 30,
+            max_disk_mb: 200,
+        }
+    }
+}
+
+impl RetentionSettings {
+    /// Oldest an entry may be to be kept
+    pub fn cutoff(&self) -> DateTime<Utc> {
+        Utc::now() - chrono::Duration::days(self.days.into())
+    }
+}
+
+/// Cleans the caches on launch and every hour, the files on disk in the background
+#[derive(Default)]
+pub struct Cleanup {
+    next: f64,
+    /// Bytes the caches take on disk, none until they're measured
+    usage: Arc<Mutex<Option<u64>>>,
+}
+
+impl Cleanup {
+    /// Whether a cleanup is due, the caches kept in memory are then for the caller to clean
+    #[cfg_attr(target_arch = "wasm32", allow(unused_variables))]
+    pub fn poll(&mut self, ctx: &Context, settings: &RetentionSettings) -> bool {
+        let now = ctx.input().time;
+        if now < self.next {
+            return false;
+        }
+        self.next = now + CLEANUP_INTERVAL;
+        #[cfg(not(target_arch = "wasm32"))]
+        disk::clean(settings.clone(), self.usage.clone(), ctx.clone());
+        true
+    }
+
+    /// Draws the settings with what the caches take, returns whether they're to be cleared, the
+    /// files on disk already are
+    pub fn show(
+        &mut self,
+        ui: &mut Ui,
+        settings: &mut RetentionSettings,
+        in_memory: usize,
+    ) -> bool {
+        Grid::new("retention").num_columns(2).show(ui, |ui| {
+            ui.label("Keep for");
+            ui.horizontal(|ui| {
+                ui.add(DragValue::new(&mut settings.days).clamp_range(1..=365));
+                ui.label("days");
+            });
+            ui.end_row();
+            #[cfg(not(target_arch = "wasm32"))]
+            {
+                ui.label("Use at most");
+                ui.horizontal(|ui| {
+                    ui.add(DragValue::new(&mut settings.max_disk_mb).clamp_range(10..=10_000));
+                    ui.label("MB on disk");
+                });
+                ui.end_row();
+            }
+        });
+        ui.label(format!("Cached responses: {}", size(in_memory as u64)));
+        #[cfg(not(target_arch = "wasm32"))]
+        match *self.usage.lock().unwrap() {
+            Some(usage) => ui.label(format!("Images on disk: {}", size(usage))),
+            None => ui.weak("Measuring the images on disk…"),
+        };
+        let clear = ui.button("🗑 Clear cache").clicked();
+        #[cfg(not(target_arch = "wasm32"))]
+        if clear {
+            *self.usage.lock().unwrap() = None;
+            disk::clear(self.usage.clone(), ui.ctx().clone());
+        }
+        clear
+    }
+}
+
+fn size(bytes: u64) -> String {
+    if bytes < MEGABYTE {
+        format!("{} KB", (bytes + 1023) / 1024)
+    } else {
+        format!("{:.1} MB", bytes as f64 / MEGABYTE as f64)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod disk {
+    use super::{RetentionSettings, MEGABYTE};
+    use eframe::egui::Context;
+    use std::cmp::Reverse;
+    use std::fs;
+    use std::io::ErrorKind;
+    use std::path::{Path, PathBuf};
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::{Duration, SystemTime};
+
+    struct CachedFile {
+        path: PathBuf,
+        len: u64,
+        modified: SystemTime,
+    }
+
+    fn dir() -> Option<PathBuf> {
+        directories_next::ProjectDirs::from("", "", "headlines")
+            .map(|dirs| dirs.cache_dir().to_path_buf())
+    }
+
+    fn walk(dir: &Path, files: &mut Vec<CachedFile>) {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(_) => return,
+        };
+        for entry in entries.flatten() {
+            let metadata = match entry.metadata() {
+                Ok(metadata) => metadata,
+                Err(_) => continue,
+            };
+            if metadata.is_dir() {
+                walk(&entry.path(), files);
+            } else if let Ok(modified) = metadata.modified() {
+                files.push(CachedFile {
+                    path: entry.path(),
+                    len: metadata.len(),
+                    modified,
+                });
+            }
+        }
+    }
+
+    /// Removes the files older than the settings allow, then the least recently used ones beyond
+    /// the size cap
+    pub fn clean(settings: RetentionSettings, usage: Arc<Mutex<Option<u64>>>, ctx: Context) {
+        let spawned = thread::Builder::new()
+            .name("cache-cleanup".to_string())
+            .spawn(move || {
+                let mut files = Vec::new();
+                if let Some(dir) = dir() {
+                    walk(&dir, &mut files);
+                }
+                files.sort_by_key(|file| Reverse(file.modified));
+                let max_age = Duration::from_secs(u64::from(settings.days) * 24 * 60 * 60);
+                let max_size = u64::from(settings.max_disk_mb) * MEGABYTE;
+                let mut total = 0;
+                for file in files {
+                    let expired = file.modified.elapsed().map_or(false, |age| age > max_age);
+                    if !expired && total + file.len <= max_size {
+                        total += file.len;
+                    } else if let Err(e) = fs::remove_file(&file.path) {
+                        tracing::error!("Failed removing {}: {}", file.path.display(), e);
+                        total += file.len;
+                    }
+                }
+                *usage.lock().unwrap() = Some(total);
+                ctx.request_repaint();
+            });
+        if let Err(e) = spawned {
+            tracing::error!("Failed spawning the cache cleanup: {}", e);
+        }
+    }
+
+    /// Removes every cached file
+    pub fn clear(usage: Arc<Mutex<Option<u64>>>, ctx: Context) {
+        let spawned = thread::Builder::new()
+            .name("cache-cleanup".to_string())
+            .spawn(move || {
+                let mut left = Vec::new();
+                if let Some(dir) = dir() {
+                    match fs::remove_dir_all(&dir) {
+                        Ok(()) => {}
+                        Err(e) if e.kind() == ErrorKind::NotFound => {}
+                        Err(e) => {
+                            tracing::error!("Failed clearing the cache: {}", e);
+                            walk(&dir, &mut left);
+                        }
+                    }
+                }
+                *usage.lock().unwrap() = Some(left.iter().map(|file| file.len).sum());
+                ctx.request_repaint();
+            });
+        if let Err(e) = spawned {
+            tracing::error!("Failed spawning the cache cleanup: {}", e);
+        }
+    }
+}
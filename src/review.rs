@@ -0,0 +1,116 @@
+use crate::news::NewsCardData;
+use eframe::egui::{Button, Key, Label, Modifiers, RichText, ScrollArea, TextStyle, Ui};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Urls of triaged articles remembered so that they aren't queued again, the oldest are forgotten
+const MAX_TRIAGED: usize = 1000;
+
+/// Whether the title or description of `article` contains one of the comma separated `keywords`
+pub fn matches(article: &NewsCardData, keywords: &str) -> bool {
+    let text = format!("{} {}", article.title, article.desc).to_lowercase();
+    keywords
+        .split(',')
+        .map(|keyword| keyword.trim().to_lowercase())
+        .any(|keyword| !keyword.is_empty() && text.contains(&keyword))
+}
+
+/// Articles matching an alert keyword, waiting to be approved or dismissed one by one
+#[derive(Default, Serialize, Deserialize)]
+pub struct ReviewQueue {
+    items: VecDeque<NewsCardData>,
+    triaged: VecDeque<String>,
+}
+
+impl ReviewQueue {
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Queues `article` if it contains one of the comma separated `keywords` and was neither
+    /// queued nor triaged before
+    pub fn offer(&mut self, article: &NewsCardData, keywords: &str) {
+        let known = self.triaged.contains(&article.url)
+            || self.items.iter().any(|item| item.url == article.url);
+        if matches(article, keywords) && !known {
+            self.items.push_back(article.clone());
+        }
+    }
+
+    /// Takes the article under review out of the queue
+    fn triage(&mut self) -> Option<NewsCardData> {
+        let article = self.items.pop_front()?;
+        self.triaged.push_back(article.url.clone());
+        if self.triaged.len() > MAX_TRIAGED {
+            self.triaged.pop_front();
+        }
+        Some(article)
+    }
+
+    /// Moves the article under review to the end of the queue
+    fn skip(&mut self) {
+        if let Some(article) = self.items.pop_front() {
+            self.items.push_back(article);
+        }
+    }
+
+    /// Shows the article under review and the ones after it, Y approving, N dismissing and S
+    /// skipping it. Returns the article approved this frame, if any.
+    pub fn show(&mut self, ui: &mut Ui) -> Option<NewsCardData> {
+        let current = match self.items.front() {
+            Some(current) => current,
+            None => {
+                ui.vertical_centered(|ui| ui.label("Nothing to review"));
+                return None;
+            }
+        };
+
+        ui.weak(format!("1 of {}", self.items.len()));
+        ui.add(Label::new(RichText::new(&current.title).heading()).wrap(true));
+        ui.weak(&current.source);
+        ui.label(&current.desc);
+        ui.hyperlink_to("read more ⤴", &current.url);
+        ui.add_space(8.);
+
+        let (mut approve, mut dismiss, mut skip) = (false, false, false);
+        // the keys are left alone while typing in the search field
+        if ui.memory().focus().is_none() {
+            let mut input = ui.input_mut();
+            approve = input.consume_key(Modifiers::NONE, Key::Y);
+            dismiss = input.consume_key(Modifiers::NONE, Key::N);
+            skip = input.consume_key(Modifiers::NONE, Key::S);
+        }
+        ui.horizontal(|ui| {
+            approve |= ui
+                .add(Button::new("✔ Approve (Y)"))
+                .on_hover_text("Bookmark it, and forward it if a forwarding url is set")
+                .clicked();
+            dismiss |= ui.button("✖ Dismiss (N)").clicked();
+            skip |= ui.button("⏭ Skip (S)").clicked();
+        });
+
+        ui.separator();
+        ScrollArea::vertical().show(ui, |ui| {
+            for item in self.items.iter().skip(1) {
+                ui.add(Label::new(
+                    RichText::new(&item.title).text_style(TextStyle::Button),
+                ));
+            }
+        });
+
+        if approve {
+            self.triage()
+        } else {
+            if dismiss {
+                self.triage();
+            } else if skip {
+                self.skip();
+            }
+            None
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use chrono::{DateTime, Datelike, Local, Timelike, Utc};
+use eframe::egui::{DragValue, Ui};
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+const MINUTES_PER_DAY: f64 = 24. * 60.;
+/// Zenith of the sun's center at sunrise and sunset, accounting for refraction and its radius
+const SUNRISE_ZENITH: f64 = 90.833;
+
+/// When the scheduled theme mode turns dark
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum NightSchedule {
+    /// Dark from `dark_from` to `light_from`, in local hours
+    Hours { dark_from: u32, light_from: u32 },
+    /// Dark between sunset and sunrise at the location, in degrees
+    Sun { latitude: f64, longitude: f64 },
+}
+
+impl Default for NightSchedule {
+    fn default() -> Self {
+        Self::Hours {
+            dark_from: 20,
+            light_from: 7,
+        }
+    }
+}
+
+impl NightSchedule {
+    pub fn is_night(&self, now: DateTime<Utc>) -> bool {
+        match *self {
+            Self::Hours {
+                dark_from,
+                light_from,
+            } => {
+                let hour = now.with_timezone(&Local).hour();
+                if dark_from <= light_from {
+                    (dark_from..light_from).contains(&hour)
+                } else {
+                    hour >= dark_from || hour < light_from
+                }
+            }
+            Self::Sun {
+                latitude,
+                longitude,
+            } => {
+                let minute = f64::from(now.hour() * 60 + now.minute());
+                match sun_times(now.ordinal(), latitude, longitude) {
+                    SunTimes::Rises { sunrise, sunset } => {
+                        if sunrise <= sunset {
+                            !(sunrise..sunset).contains(&minute)
+                        } else {
+                            // the day spans midnight UTC
+                            (sunset..sunrise).contains(&minute)
+                        }
+                    }
+                    SunTimes::PolarDay => false,
+                    SunTimes::PolarNight => true,
+                }
+            }
+        }
+    }
+
+    pub fn editor(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            let hours = matches!(self, Self::Hours { .. });
+            if ui.radio(hours, "Fixed hours").clicked() && !hours {
+                *self = Self::default();
+            }
+            if ui.radio(!hours, "Sunset to sunrise").clicked() && hours {
+                *self = Self::Sun {
+                    latitude: 48.85,
+                    longitude: 2.35,
+                };
+            }
+        });
+        match self {
+            Self::Hours {
+                dark_from,
+                light_from,
+            } => {
+                ui.horizontal(|ui| {
+                    ui.label("Dark from");
+                    ui.add(DragValue::new(dark_from).clamp_range(0..=23).suffix(" h"));
+                    ui.label("to");
+                    ui.add(DragValue::new(light_from).clamp_range(0..=23).suffix(" h"));
+                });
+            }
+            Self::Sun {
+                latitude,
+                longitude,
+            } => {
+                ui.horizontal(|ui| {
+                    ui.label("Latitude");
+                    ui.add(
+                        DragValue::new(latitude)
+                            .clamp_range(-90.0..=90.0)
+                            .speed(0.1)
+                            .suffix("°"),
+                    );
+                    ui.label("Longitude");
+                    ui.add(
+                        DragValue::new(longitude)
+                            .clamp_range(-180.0..=180.0)
+                            .speed(0.1)
+                            .suffix("°"),
+                    );
+                });
+            }
+        }
+    }
+}
+
+enum SunTimes {
+    /// Minutes after midnight UTC
+    Rises {
+        sunrise: f64,
+        sunset: f64,
+    },
+    PolarDay,
+    PolarNight,
+}
+
+/// Sunrise and sunset on the `day` of the year, with NOAA's approximate solar equations
+fn sun_times(day: u32, latitude: f64, longitude: f64) -> SunTimes {
+    // fractional year, in radians
+    let gamma = 2. * PI / 365. * (f64::from(day) - 0.5);
+    let equation_of_time = 229.18
+        * (0.000075 + 0.001868 * gamma.cos()
+            - 0.032077 * gamma.sin()
+            - 0.014615 * (2. * gamma).cos()
+            - 0.040849 * (2. * gamma).sin());
+    let declination = 0.006918 - 0.399912 * gamma.cos() + 0.070257 * gamma.sin()
+        - 0.006758 * (2. * gamma).cos()
+        + 0.000907 * (2. * gamma).sin()
+        - 0.002697 * (3. * gamma).cos()
+        + 0.00148 * (3. * gamma).sin();
+
+    let latitude = latitude.to_radians();
+    let cos_hour_angle = SUNRISE_ZENITH.to_radians().cos() / (latitude.cos() * declination.cos())
+        - latitude.tan() * declination.tan();
+    if cos_hour_angle > 1. {
+        return SunTimes::PolarNight;
+    }
+    if cos_hour_angle < -1. {
+        return SunTimes::PolarDay;
+    }
+    let hour_angle = cos_hour_angle.acos().to_degrees();
+
+    let noon = 720. - 4. * longitude - equation_of_time;
+    SunTimes::Rises {
+        sunrise: (noon - 4. * hour_angle).rem_euclid(MINUTES_PER_DAY),
+        sunset: (noon + 4. * hour_angle).rem_euclid(MINUTES_PER_DAY),
+    }
+}
@@ -0,0 +1,105 @@
+use crate::entities::Entity;
+use serde::{Deserialize, Serialize};
+
+/// What fills the window
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum Screen {
+    /// There's no API key yet, the config window asks for one
+    Onboarding,
+    Feed,
+    Settings,
+    Bookmarks,
+    /// The article of the open reader, those it was opened from are under it in the reader
+    Reader,
+    /// Articles naming a person, an organization or a place
+    Entity(Entity),
+    Review,
+    Comparison,
+    /// The error log
+    Errors,
+}
+
+/// Screens opened one over the other, the last one is shown and going back closes it. The first
+/// is the feed, or the onboarding until there's an API key.
+pub struct Navigation {
+    stack: Vec<Screen>,
+}
+
+impl Default for Navigation {
+    fn default() -> Self {
+        Self {
+            stack: vec![Screen::Onboarding],
+        }
+    }
+}
+
+impl Navigation {
+    pub fn current(&self) -> &Screen {
+        self.stack.last().unwrap_or(&Screen::Feed)
+    }
+
+    pub fn is(&self, screen: &Screen) -> bool {
+        self.current() == screen
+    }
+
+    /// Whether the API key was entered, the feed is then under every other screen
+    pub fn onboarded(&self) -> bool {
+        self.stack.first() != Some(&Screen::Onboarding)
+    }
+
+    pub fn can_go_back(&self) -> bool {
+        self.stack.len() > 1
+    }
+
+    /// Opens the screen over the current one, or goes back to it when it's already open
+    pub fn open(&mut self, screen: Screen) {
+        match self.stack.iter().position(|open| *open == screen) {
+            Some(i) => self.stack.truncate(i + 1),
+            None => self.stack.push(screen),
+        }
+    }
+
+    /// Closes the screen when it's the current one, opens it otherwise
+    pub fn toggle(&mut self, screen: Screen) {
+        if self.is(&screen) {
+            self.back();
+        } else {
+            self.open(screen);
+        }
+    }
+
+    /// Closes the current screen, the first one stays
+    pub fn back(&mut self) {
+        if self.can_go_back() {
+            self.stack.pop();
+        }
+    }
+
+    /// Closes every screen but the first, which becomes the feed
+    pub fn home(&mut self) {
+        self.stack = vec![Screen::Feed];
+    }
+
+    /// Back to the onboarding alone, for a config without an API key
+    pub fn restart(&mut self) {
+        self.stack = vec![Screen::Onboarding];
+    }
+
+    /// The screens of a past session, over the feed, or the onboarding when there's no API key
+    pub fn restore(&mut self, stack: Vec<Screen>, onboarded: bool) {
+        if !onboarded {
+            return self.restart();
+        }
+        self.home();
+        // the reader is reopened with its article
+        for screen in stack {
+            if screen != Screen::Onboarding && screen != Screen::Reader {
+                self.open(screen);
+            }
+        }
+    }
+
+    pub fn stack(&self) -> &[Screen] {
+        &self.stack
+    }
+}
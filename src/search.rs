@@ -0,0 +1,138 @@
+use crate::bookmarks::Bookmarks;
+use crate::history::History;
+use chrono::{DateTime, Local, Utc};
+use eframe::egui::{Hyperlink, ScrollArea, TextEdit, Ui};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+const MAX_RESULTS: usize = 50;
+const LIST_HEIGHT: f32 = 300.;
+
+enum Origin {
+    Bookmark,
+    /// Refresh the headline was last seen in
+    History(DateTime<Utc>),
+}
+
+struct Entry {
+    title: String,
+    url: String,
+    origin: Origin,
+}
+
+/// Searches the bookmarks and the past refreshes offline, through an index of their words
+#[derive(Default)]
+pub struct LocalSearch {
+    query: String,
+    /// Bookmarks first, then the headlines of the history from the newest
+    entries: Vec<Entry>,
+    /// Entries containing each word, sorted so that the words starting with a prefix are together
+    index: BTreeMap<String, BTreeSet<usize>>,
+    /// Counts and newest dates of the bookmarks and history indexed, rebuilt when they change
+    indexed: Option<(usize, Option<DateTime<Utc>>, usize, Option<DateTime<Utc>>)>,
+}
+
+impl LocalSearch {
+    pub fn show(&mut self, ui: &mut Ui, bookmarks: &Bookmarks, history: &History) {
+        self.reindex(bookmarks, history);
+        ui.add(
+            TextEdit::singleline(&mut self.query)
+                .hint_text("🔍 Search bookmarks and past headlines")
+                .desired_width(f32::INFINITY),
+        );
+        if self.query.trim().is_empty() {
+            ui.weak(format!("{} articles indexed", self.entries.len()));
+            return;
+        }
+
+        let results = self.search(&self.query);
+        ui.weak(match results.len() {
+            0 => "Nothing found".to_string(),
+            1 => "1 article".to_string(),
+            n if n > MAX_RESULTS => format!("{} articles, showing the first {}", n, MAX_RESULTS),
+            n => format!("{} articles", n),
+        });
+        ScrollArea::vertical()
+            .max_height(LIST_HEIGHT)
+            .show(ui, |ui| {
+                for &i in results.iter().take(MAX_RESULTS) {
+                    let entry = &self.entries[i];
+                    ui.horizontal(|ui| {
+                        match entry.origin {
+                            Origin::Bookmark => ui.weak("★"),
+                            Origin::History(seen_at) => {
+                                ui.weak(seen_at.with_timezone(&Local).format("%d %b").to_string())
+                            }
+                        };
+                        ui.add(Hyperlink::from_label_and_url(&entry.title, &entry.url));
+                    });
+                }
+            });
+    }
+
+    /// Entries with a word starting with each word of the query, in the order they're kept
+    fn search(&self, query: &str) -> Vec<usize> {
+        let mut results: Option<BTreeSet<usize>> = None;
+        for prefix in words(query) {
+            let matching: BTreeSet<usize> = self
+                .index
+                .range(prefix.clone()..)
+                .take_while(|(word, _)| word.starts_with(&prefix))
+                .flat_map(|(_, entries)| entries.iter().copied())
+                .collect();
+            results = Some(match results {
+                Some(results) => results.intersection(&matching).copied().collect(),
+                None => matching,
+            });
+        }
+        results.unwrap_or_default().into_iter().collect()
+    }
+
+    fn reindex(&mut self, bookmarks: &Bookmarks, history: &History) {
+        let indexed = (
+            bookmarks.iter().count(),
+            bookmarks.iter().next().map(|b| b.bookmarked_at),
+            history.headlines().count(),
+            history.headlines().next().map(|(taken_at, _, _)| taken_at),
+        );
+        if self.indexed == Some(indexed) {
+            return;
+        }
+        self.indexed = Some(indexed);
+        self.entries.clear();
+        self.index.clear();
+
+        let mut seen = HashSet::new();
+        for bookmark in bookmarks.iter() {
+            let article = &bookmark.article;
+            seen.insert(article.url.clone());
+            let text = format!("{} {}", article.title, article.desc);
+            self.add(
+                &text,
+                article.title.clone(),
+                article.url.clone(),
+                Origin::Bookmark,
+            );
+        }
+        // a headline stays in many refreshes, the newest one it's in is kept
+        for (taken_at, title, url) in history.headlines() {
+            if seen.insert(url.to_string()) {
+                let origin = Origin::History(taken_at);
+                self.add(title, title.to_string(), url.to_string(), origin);
+            }
+        }
+    }
+
+    fn add(&mut self, text: &str, title: String, url: String, origin: Origin) {
+        let i = self.entries.len();
+        for word in words(text) {
+            self.index.entry(word).or_default().insert(i);
+        }
+        self.entries.push(Entry { title, url, origin });
+    }
+}
+
+fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+}
@@ -0,0 +1,182 @@
+use eframe::egui::{Color32, Stroke};
+use serde::{Deserialize, Serialize};
+
+/// Width of the border tinting a card
+const TINT_WIDTH: f32 = 2.;
+const POSITIVE_TINT: Color32 = Color32::from_rgb(40, 170, 70);
+const NEGATIVE_TINT: Color32 = Color32::from_rgb(200, 40, 40);
+
+/// Words that lean a headline positive, as stems their inflections count with
+const POSITIVE_WORDS: &[&str] = &[
+    "achiev",
+    "agree",
+    "award",
+    "benefit",
+    "best",
+    "boost",
+    "breakthrough",
+    "celebrat",
+    "cure",
+    "deal",
+    "gain",
+    "good",
+    "great",
+    "grow",
+    "hero",
+    "hope",
+    "improv",
+    "innovat",
+    "joy",
+    "launch",
+    "love",
+    "peace",
+    "praise",
+    "progress",
+    "record",
+    "recover",
+    "relief",
+    "rescue",
+    "rise",
+    "rising",
+    "safe",
+    "save",
+    "success",
+    "support",
+    "surge",
+    "thriv",
+    "triumph",
+    "win",
+    "won",
+];
+
+/// Words that lean a headline negative, as stems
+const NEGATIVE_WORDS: &[&str] = &[
+    "accident", "arrest", "attack", "ban", "bomb", "collaps", "conflict", "crash", "crime",
+    "crisis", "dead", "death", "decline", "disaster", "drop", "fail", "fall", "fell", "fear",
+    "fire", "flood", "fraud", "injur", "kill", "lose", "losing", "loss", "murder", "plung",
+    "protest", "scandal", "shoot", "shot", "slump", "strike", "suspect", "threat", "violence",
+    "violent", "war", "warn", "worst",
+];
+
+/// Endings a stem of the word lists can take
+const SUFFIXES: &[&str] = &[
+    "", "s", "es", "d", "ed", "e", "ing", "er", "ers", "y", "ies", "ion", "ions", "ement", "ly",
+    "th",
+];
+
+/// Words flipping the leaning of the word right after them
+const NEGATIONS: &[&str] = &["no", "not", "never", "without", "nor"];
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Scoring {
+    /// A built-in list of positive and negative words
+    Lexicon,
+    /// Only the user's keywords
+    Keywords,
+}
+
+impl Scoring {
+    pub const ALL: [Scoring; 2] = [Self::Lexicon, Self::Keywords];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Lexicon => "Built-in word list",
+            Self::Keywords => "My keywords",
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SentimentSettings {
+    /// Whether cards get a border tinted by the leaning of their headline
+    pub enabled: bool,
+    pub scoring: Scoring,
+    /// Comma separated words leaning a headline positive, on top of the word list if it's used
+    pub positive: String,
+    /// Comma separated words leaning a headline negative
+    pub negative: String,
+}
+
+impl Default for SentimentSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            scoring: Scoring::Lexicon,
+            positive: String::new(),
+            negative: String::new(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Sentiment {
+    Positive,
+    Negative,
+    Neutral,
+}
+
+impl Sentiment {
+    /// Border of a card with this leaning, none for a neutral one
+    pub fn stroke(self) -> Stroke {
+        match self {
+            Self::Positive => Stroke::new(TINT_WIDTH, POSITIVE_TINT),
+            Self::Negative => Stroke::new(TINT_WIDTH, NEGATIVE_TINT),
+            Self::Neutral => Stroke::default(),
+        }
+    }
+}
+
+impl SentimentSettings {
+    /// Leaning of `text`, scored by the word list and the user's keywords
+    pub fn score(&self, text: &str) -> Sentiment {
+        let positive = keywords(&self.positive);
+        let negative = keywords(&self.negative);
+        let text = text.to_lowercase();
+
+        // keywords can span several words, they're matched in the whole text
+        let mut score = positive
+            .iter()
+            .filter(|k| text.contains(k.as_str()))
+            .count() as i32
+            - negative
+                .iter()
+                .filter(|k| text.contains(k.as_str()))
+                .count() as i32;
+
+        if self.scoring == Scoring::Lexicon {
+            let mut negated = false;
+            for word in text.split(|c: char| !c.is_alphanumeric() && c != '\'') {
+                if word.is_empty() {
+                    continue;
+                }
+                let sign = if negated { -1 } else { 1 };
+                if POSITIVE_WORDS.iter().any(|stem| inflects(word, stem)) {
+                    score += sign;
+                } else if NEGATIVE_WORDS.iter().any(|stem| inflects(word, stem)) {
+                    score -= sign;
+                }
+                negated = NEGATIONS.contains(&word) || word.ends_with("n't");
+            }
+        }
+
+        match score {
+            s if s > 0 => Sentiment::Positive,
+            s if s < 0 => Sentiment::Negative,
+            _ => Sentiment::Neutral,
+        }
+    }
+}
+
+/// Whether `word` is `stem` with one of the known endings
+fn inflects(word: &str, stem: &str) -> bool {
+    word.strip_prefix(stem)
+        .map_or(false, |ending| SUFFIXES.contains(&ending))
+}
+
+fn keywords(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(|k| k.trim().to_lowercase())
+        .filter(|k| !k.is_empty())
+        .collect()
+}
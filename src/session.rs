@@ -0,0 +1,17 @@
+use crate::news::NewsCardData;
+use crate::screens::Screen;
+use serde::{Deserialize, Serialize};
+
+/// What the app was showing when it was closed, to open where it was left off
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    /// Search the feed was filtered with
+    pub query: String,
+    /// Url of the first visible card and how far the feed was scrolled into it
+    pub anchor: Option<(String, f32)>,
+    /// Article open in the reader
+    pub reader: Option<NewsCardData>,
+    /// Screens opened over the feed
+    pub screens: Vec<Screen>,
+}
@@ -0,0 +1,51 @@
+/// Whether the platform has a system share sheet, the Web Share API on the web
+#[cfg(feature = "gui")]
+pub const SUPPORTED: bool = cfg!(target_arch = "wasm32");
+
+/// `[title](url)` with the characters that would end the link escaped
+pub fn markdown_link(title: &str, url: &str) -> String {
+    let title = title
+        .replace('\\', "\\\\")
+        .replace('[', "\\[")
+        .replace(']', "\\]");
+    let url = url
+        .replace(' ', "%20")
+        .replace('(', "%28")
+        .replace(')', "%29");
+    format!("[{}]({})", title, url)
+}
+
+/// Opens the browser's share sheet for the link
+#[cfg(all(feature = "gui", target_arch = "wasm32"))]
+pub fn share(title: &str, url: &str) -> Result<(), String> {
+    use eframe::wasm_bindgen::{JsCast, JsValue};
+    use js_sys::{Function, Object, Reflect};
+
+    let js_error = |e: JsValue| format!("{:?}", e);
+
+    let navigator = web_sys::window()
+        .ok_or("No window to share from")?
+        .navigator();
+    // not in web-sys without its unstable APIs
+    let share: Function = Reflect::get(&navigator, &JsValue::from_str("share"))
+        .map_err(js_error)?
+        .dyn_into()
+        .map_err(|_| "This browser can't share links")?;
+
+    let data = Object::new();
+    Reflect::set(
+        &data,
+        &JsValue::from_str("title"),
+        &JsValue::from_str(title),
+    )
+    .map_err(js_error)?;
+    Reflect::set(&data, &JsValue::from_str("url"), &JsValue::from_str(url)).map_err(js_error)?;
+    // the returned promise only rejects when the user dismisses the sheet
+    share.call1(&navigator, &data).map_err(js_error)?;
+    Ok(())
+}
+
+#[cfg(all(feature = "gui", not(target_arch = "wasm32")))]
+pub fn share(_title: &str, _url: &str) -> Result<(), String> {
+    Err("Sharing isn't supported on this platform".to_string())
+}
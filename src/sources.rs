@@ -0,0 +1,502 @@
+use crate::aggregate::{self, SourceCaps};
+use crate::errors::HeadlinesError;
+use crate::feeds::{self, FeedItem};
+use crate::http;
+use crate::language;
+use crate::news::{self, Discussion, NewsCardData};
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+pub const HACKER_NEWS: &str = "news.ycombinator.com";
+pub const REDDIT: &str = "reddit.com";
+pub const LOBSTERS: &str = "lobste.rs";
+pub const DEV_TO: &str = "dev.to";
+pub const YOUTUBE: &str = "youtube.com";
+/// RSS and Atom feeds, their articles are credited to their sites
+pub const FEEDS: &str = "feeds";
+
+const HACKER_NEWS_API: &str = "https://hacker-news.firebaseio.com/v0";
+const HACKER_NEWS_ITEM: &str = "https://news.ycombinator.com/item";
+const REDDIT_URL: &str = "https://www.reddit.com";
+const LOBSTERS_HOTTEST: &str = "https://lobste.rs/hottest.json";
+const DEV_TO_ARTICLES: &str = "https://dev.to/api/articles";
+const YOUTUBE_FEEDS: &str = "https://www.youtube.com/feeds/videos.xml";
+
+/// Sources fetched alongside newsapi.org, none of them needs a key
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SourceSettings {
+    pub hacker_news: bool,
+    pub reddit: bool,
+    /// Comma separated subreddits whose hot posts are fetched
+    pub subreddits: String,
+    pub lobsters: bool,
+    pub dev_to: bool,
+    pub youtube: bool,
+    /// Comma separated channel ids, playlist ids or feed urls whose new videos are fetched
+    pub youtube_channels: String,
+    pub feeds: bool,
+    /// Comma separated urls of RSS or Atom feeds, like those of an imported OPML file
+    pub feed_urls: String,
+}
+
+impl Default for SourceSettings {
+    fn default() -> Self {
+        Self {
+            hacker_news: false,
+            reddit: false,
+            subreddits: "worldnews, technology".to_string(),
+            lobsters: false,
+            dev_to: false,
+            youtube: false,
+            youtube_channels: String::new(),
+            feeds: false,
+            feed_urls: String::new(),
+        }
+    }
+}
+
+impl SourceSettings {
+    /// Names of the sources to fetch besides newsapi.org
+    pub fn enabled(&self) -> Vec<&'static str> {
+        let mut enabled = Vec::new();
+        if self.hacker_news {
+            enabled.push(HACKER_NEWS);
+        }
+        if self.reddit && self.subreddits().next().is_some() {
+            enabled.push(REDDIT);
+        }
+        if self.lobsters {
+            enabled.push(LOBSTERS);
+        }
+        if self.dev_to {
+            enabled.push(DEV_TO);
+        }
+        if self.youtube && self.youtube_feeds().next().is_some() {
+            enabled.push(YOUTUBE);
+        }
+        if self.feeds && self.feed_urls().next().is_some() {
+            enabled.push(FEEDS);
+        }
+        enabled
+    }
+
+    /// Adds the feed urls that aren't there yet, returns how many were added
+    pub fn add_feeds(&mut self, urls: &[String]) -> usize {
+        let mut known: Vec<String> = self.feed_urls().map(str::to_string).collect();
+        let mut added = 0;
+        for url in urls {
+            if known.contains(url) {
+                continue;
+            }
+            if !self.feed_urls.trim().is_empty() {
+                self.feed_urls.push_str(", ");
+            }
+            self.feed_urls.push_str(url);
+            known.push(url.clone());
+            added += 1;
+        }
+        added
+    }
+
+    fn feed_urls(&self) -> impl Iterator<Item = &str> {
+        self.feed_urls
+            .split(',')
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+    }
+
+    fn subreddits(&self) -> impl Iterator<Item = &str> {
+        self.subreddits
+            .split(',')
+            .map(|name| name.trim().trim_start_matches("r/"))
+            .filter(|name| !name.is_empty())
+    }
+
+    /// Feed urls of the channels and playlists, told apart by the prefix of their ids
+    fn youtube_feeds(&self) -> impl Iterator<Item = String> + '_ {
+        self.youtube_channels
+            .split(',')
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .map(|id| {
+                if id.starts_with("http://") || id.starts_with("https://") {
+                    id.to_string()
+                } else if id.starts_with("UC") {
+                    format!("{}?channel_id={}", YOUTUBE_FEEDS, id)
+                } else {
+                    format!("{}?playlist_id={}", YOUTUBE_FEEDS, id)
+                }
+            })
+    }
+}
+
+/// Fetches up to `limit` articles of the source. The native requests block, the future is only
+/// ever pending on the web.
+pub async fn fetch(
+    source: &str,
+    settings: &SourceSettings,
+    limit: usize,
+    timeout: Duration,
+) -> Result<Vec<NewsCardData>, HeadlinesError> {
+    match source {
+        HACKER_NEWS => fetch_hacker_news(limit, timeout).await,
+        REDDIT => fetch_reddit(settings, limit, timeout).await,
+        LOBSTERS => fetch_lobsters(limit, timeout).await,
+        DEV_TO => fetch_dev_to(limit, timeout).await,
+        YOUTUBE => fetch_youtube(settings, limit, timeout).await,
+        FEEDS => fetch_feeds(settings, limit, timeout).await,
+        _ => Ok(Vec::new()),
+    }
+}
+
+#[derive(Deserialize)]
+struct HackerNewsItem {
+    id: u64,
+    title: Option<String>,
+    /// None for Ask HN posts, which are their own discussion
+    url: Option<String>,
+    #[serde(default)]
+    score: u32,
+    /// Number of comments
+    #[serde(default)]
+    descendants: u32,
+}
+
+/// The top stories, an item request each as the Firebase API has no listing with their titles
+async fn fetch_hacker_news(
+    limit: usize,
+    timeout: Duration,
+) -> Result<Vec<NewsCardData>, HeadlinesError> {
+    let ids: Vec<u64> = get(&format!("{}/topstories.json", HACKER_NEWS_API), timeout).await?;
+    let fetched_at = Utc::now();
+    let mut articles = Vec::new();
+    for id in ids.into_iter().take(limit) {
+        let url = format!("{}/item/{}.json", HACKER_NEWS_API, id);
+        // deleted items come as null
+        let item = match get::<Option<HackerNewsItem>>(&url, timeout).await? {
+            Some(item) => item,
+            None => continue,
+        };
+        let title = match item.title {
+            Some(title) => title,
+            None => continue,
+        };
+        let discussion = format!("{}?id={}", HACKER_NEWS_ITEM, item.id);
+        articles.push(article(
+            title,
+            String::new(),
+            item.url.unwrap_or_else(|| discussion.clone()),
+            HACKER_NEWS,
+            fetched_at,
+            Some(Discussion {
+                url: discussion,
+                score: item.score,
+                comments: item.descendants,
+            }),
+        ));
+    }
+    Ok(articles)
+}
+
+#[derive(Deserialize)]
+struct RedditListing {
+    data: RedditListingData,
+}
+
+#[derive(Deserialize)]
+struct RedditListingData {
+    children: Vec<RedditChild>,
+}
+
+#[derive(Deserialize)]
+struct RedditChild {
+    data: RedditPost,
+}
+
+#[derive(Deserialize)]
+struct RedditPost {
+    title: String,
+    url: String,
+    permalink: String,
+    subreddit: String,
+    score: i64,
+    num_comments: u32,
+    /// Pinned by the moderators, usually rules and announcements
+    #[serde(default)]
+    stickied: bool,
+}
+
+/// The hot posts of the subreddits together, as a multireddit
+async fn fetch_reddit(
+    settings: &SourceSettings,
+    limit: usize,
+    timeout: Duration,
+) -> Result<Vec<NewsCardData>, HeadlinesError> {
+    let subreddits: Vec<_> = settings.subreddits().collect();
+    // raw_json keeps reddit from escaping the titles as HTML
+    let url = format!(
+        "{}/r/{}/hot.json?limit={}&raw_json=1",
+        REDDIT_URL,
+        subreddits.join("+"),
+        limit
+    );
+    let listing: RedditListing = get(&url, timeout).await?;
+    let fetched_at = Utc::now();
+    let articles = listing
+        .data
+        .children
+        .into_iter()
+        .map(|child| child.data)
+        .filter(|post| !post.stickied)
+        .map(|post| {
+            article(
+                post.title,
+                format!("r/{}", post.subreddit),
+                post.url,
+                REDDIT,
+                fetched_at,
+                Some(Discussion {
+                    url: format!("{}{}", REDDIT_URL, post.permalink),
+                    score: post.score.max(0) as u32,
+                    comments: post.num_comments,
+                }),
+            )
+        })
+        .collect();
+    Ok(articles)
+}
+
+#[derive(Deserialize)]
+struct LobstersStory {
+    title: String,
+    /// Empty for text posts, which are their own discussion
+    url: String,
+    #[serde(default)]
+    description_plain: String,
+    score: i64,
+    comment_count: u32,
+    comments_url: String,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+async fn fetch_lobsters(
+    limit: usize,
+    timeout: Duration,
+) -> Result<Vec<NewsCardData>, HeadlinesError> {
+    let stories: Vec<LobstersStory> = get(LOBSTERS_HOTTEST, timeout).await?;
+    let fetched_at = Utc::now();
+    let articles = stories
+        .into_iter()
+        .take(limit)
+        .map(|story| {
+            let desc = if story.description_plain.is_empty() {
+                story.tags.join(", ")
+            } else {
+                story.description_plain
+            };
+            let url = if story.url.is_empty() {
+                story.comments_url.clone()
+            } else {
+                story.url
+            };
+            article(
+                story.title,
+                desc,
+                url,
+                LOBSTERS,
+                fetched_at,
+                Some(Discussion {
+                    url: story.comments_url,
+                    score: story.score.max(0) as u32,
+                    comments: story.comment_count,
+                }),
+            )
+        })
+        .collect();
+    Ok(articles)
+}
+
+#[derive(Deserialize)]
+struct DevToArticle {
+    title: String,
+    #[serde(default)]
+    description: String,
+    url: String,
+    public_reactions_count: u32,
+    comments_count: u32,
+}
+
+/// The articles of the day most reacted to
+async fn fetch_dev_to(
+    limit: usize,
+    timeout: Duration,
+) -> Result<Vec<NewsCardData>, HeadlinesError> {
+    let url = format!("{}?top=1&per_page={}", DEV_TO_ARTICLES, limit);
+    let posts: Vec<DevToArticle> = get(&url, timeout).await?;
+    let fetched_at = Utc::now();
+    let articles = posts
+        .into_iter()
+        .map(|post| {
+            // the comments are at the bottom of the article
+            let comments = format!("{}#comments", post.url);
+            article(
+                post.title,
+                post.description,
+                post.url,
+                DEV_TO,
+                fetched_at,
+                Some(Discussion {
+                    url: comments,
+                    score: post.public_reactions_count,
+                    comments: post.comments_count,
+                }),
+            )
+        })
+        .collect();
+    Ok(articles)
+}
+
+/// The latest videos of every channel and playlist, interleaved so one of them can't take all
+/// the cards
+async fn fetch_youtube(
+    settings: &SourceSettings,
+    limit: usize,
+    timeout: Duration,
+) -> Result<Vec<NewsCardData>, HeadlinesError> {
+    let urls: Vec<String> = settings.youtube_feeds().collect();
+    let feeds = fetch_feed_items(&urls, timeout).await?;
+    Ok(feed_articles(&feeds, limit, |_| YOUTUBE))
+}
+
+/// The latest articles of every feed, interleaved, each credited to the site it comes from
+async fn fetch_feeds(
+    settings: &SourceSettings,
+    limit: usize,
+    timeout: Duration,
+) -> Result<Vec<NewsCardData>, HeadlinesError> {
+    let urls: Vec<&str> = settings.feed_urls().collect();
+    let feeds = fetch_feed_items(&urls, timeout).await?;
+    Ok(feed_articles(&feeds, limit, |item| {
+        news::domain(&item.link)
+    }))
+}
+
+/// Items of the feeds at `urls`, a feed failing is skipped unless they all do
+async fn fetch_feed_items<U: AsRef<str>>(
+    urls: &[U],
+    timeout: Duration,
+) -> Result<Vec<(&str, Vec<FeedItem>)>, HeadlinesError> {
+    let mut feeds = Vec::new();
+    let mut failure = None;
+    for url in urls {
+        let url = url.as_ref();
+        let fetched = match get_bytes(url, timeout).await {
+            Ok(bytes) => feeds::parse(&bytes).map_err(HeadlinesError::Parse),
+            Err(e) => Err(e),
+        };
+        match fetched {
+            Ok(items) => feeds.push((url, items)),
+            Err(e) => {
+                tracing::error!("Failed fetching the feed {}: {}", url, e);
+                failure = Some(e);
+            }
+        }
+    }
+    match failure {
+        Some(e) if feeds.is_empty() => Err(e),
+        _ => Ok(feeds),
+    }
+}
+
+/// Up to `limit` items of the feeds taken in turns, so one of them can't take all the cards
+fn feed_articles<'a>(
+    feeds: &'a [(&str, Vec<FeedItem>)],
+    limit: usize,
+    source: impl Fn(&'a FeedItem) -> &'a str,
+) -> Vec<NewsCardData> {
+    let fetched_at = Utc::now();
+    let batches = feeds
+        .iter()
+        .map(|(url, items)| (*url, items.iter().collect()))
+        .collect();
+    let caps = SourceCaps {
+        default: limit,
+        ..Default::default()
+    };
+    aggregate::merge(batches, &caps)
+        .into_iter()
+        .take(limit)
+        .map(|item| NewsCardData {
+            thumbnail: item.thumbnail.clone(),
+            ..article(
+                item.title.clone(),
+                item.summary.clone(),
+                item.link.clone(),
+                source(item),
+                fetched_at,
+                None,
+            )
+        })
+        .collect()
+}
+
+fn article(
+    title: String,
+    desc: String,
+    url: String,
+    source: &str,
+    fetched_at: DateTime<Utc>,
+    discussion: Option<Discussion>,
+) -> NewsCardData {
+    let language = language::detect(&format!("{} {}", title, desc)).unwrap_or_default();
+    NewsCardData {
+        title,
+        desc,
+        url,
+        source: source.to_string(),
+        fetched_at,
+        country: String::new(),
+        language: language.to_string(),
+        discussion,
+        thumbnail: None,
+        author: None,
+        published_at: None,
+    }
+}
+
+async fn get<T: DeserializeOwned>(url: &str, timeout: Duration) -> Result<T, HeadlinesError> {
+    let bytes = get_bytes(url, timeout).await?;
+    serde_json::from_slice(&bytes).map_err(|e| HeadlinesError::Parse(e.to_string()))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn get_bytes(url: &str, timeout: Duration) -> Result<Vec<u8>, HeadlinesError> {
+    let mut request = ehttp::Request::get(url);
+    // reddit throttles the generic user agents
+    request
+        .headers
+        .insert("User-Agent".to_string(), "headlines".to_string());
+    read(http::fetch_blocking(request, timeout))
+}
+
+/// The browser bounds how long a request may hang, `timeout` isn't applied on the web
+#[cfg(target_arch = "wasm32")]
+async fn get_bytes(url: &str, _timeout: Duration) -> Result<Vec<u8>, HeadlinesError> {
+    read(http::fetch_async(ehttp::Request::get(url)).await)
+}
+
+fn read(response: ehttp::Result<ehttp::Response>) -> Result<Vec<u8>, HeadlinesError> {
+    let response = response.map_err(HeadlinesError::from_fetch)?;
+    if !response.ok {
+        let error = format!(
+            "{} {} from {}",
+            response.status, response.status_text, response.url
+        );
+        return Err(HeadlinesError::from_fetch(error));
+    }
+    Ok(response.bytes)
+}
@@ -0,0 +1,133 @@
+use tts::Tts;
+
+/// Seconds a text is given to start being spoken before the engine counts as done with it
+const START_GRACE: f64 = 0.5;
+/// Seconds between checks of whether the engine is done speaking
+const POLL_INTERVAL: f64 = 0.2;
+
+/// Reads texts aloud one after the other, through the platform's speech engine or the Web Speech
+/// API on the web
+#[derive(Default)]
+pub struct Speech {
+    /// None when the platform has no speech engine
+    tts: Option<Tts>,
+    queue: Vec<String>,
+    /// Index in `queue` of the text being read
+    current: usize,
+    /// Whether the current text was handed to the engine, it's done once the engine is silent
+    spoken: bool,
+    playing: bool,
+    /// When the text being spoken was started
+    started_at: f64,
+}
+
+impl Speech {
+    pub fn new() -> Self {
+        let tts = match Tts::default() {
+            Ok(tts) => Some(tts),
+            Err(e) => {
+                tracing::error!("No text to speech: {}", e);
+                None
+            }
+        };
+        Self {
+            tts,
+            ..Self::default()
+        }
+    }
+
+    pub fn is_available(&self) -> bool {
+        self.tts.is_some()
+    }
+
+    /// Whether texts are queued, playing or paused
+    pub fn is_active(&self) -> bool {
+        !self.queue.is_empty()
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// Position of the text being read and the number of texts
+    pub fn position(&self) -> (usize, usize) {
+        ((self.current + 1).min(self.queue.len()), self.queue.len())
+    }
+
+    /// Replaces what's being read with `texts`, read from the first
+    pub fn read(&mut self, texts: Vec<String>) {
+        self.stop();
+        self.queue = texts;
+        self.playing = true;
+    }
+
+    pub fn toggle_pause(&mut self) {
+        if self.playing {
+            self.silence();
+            // the interrupted text starts over on resuming
+            self.spoken = false;
+            self.playing = false;
+        } else {
+            self.playing = true;
+        }
+    }
+
+    pub fn skip(&mut self) {
+        self.silence();
+        self.current += 1;
+        self.spoken = false;
+        self.started_at = f64::NEG_INFINITY;
+    }
+
+    pub fn stop(&mut self) {
+        self.silence();
+        self.queue.clear();
+        self.current = 0;
+        self.spoken = false;
+        self.playing = false;
+    }
+
+    /// Moves on to the next text once the current one is spoken, `rate` being a multiple of the
+    /// engine's normal speed. Returns the seconds until it wants to be polled again.
+    pub fn poll(&mut self, now: f64, rate: f32) -> Option<f64> {
+        let tts = self.tts.as_mut()?;
+        if !self.playing {
+            return None;
+        }
+        let speaking = now - self.started_at < START_GRACE || tts.is_speaking().unwrap_or(false);
+        if speaking {
+            return Some(POLL_INTERVAL);
+        }
+        if std::mem::take(&mut self.spoken) {
+            self.current += 1;
+        }
+        let text = match self.queue.get(self.current) {
+            Some(text) => text,
+            None => {
+                self.stop();
+                return None;
+            }
+        };
+
+        let rate = (tts.normal_rate() * rate).clamp(tts.min_rate(), tts.max_rate());
+        if let Err(e) = tts.set_rate(rate) {
+            tracing::error!("Failed setting the speech rate: {}", e);
+        }
+        if let Err(e) = tts.speak(text, true) {
+            tracing::error!("Failed speaking: {}", e);
+            self.stop();
+            return None;
+        }
+        self.spoken = true;
+        self.started_at = now;
+        Some(POLL_INTERVAL)
+    }
+
+    fn silence(&mut self) {
+        if let Some(tts) = &mut self.tts {
+            if let Err(e) = tts.stop() {
+                tracing::error!("Failed stopping speech: {}", e);
+            }
+        }
+    }
+}
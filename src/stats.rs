@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Articles fetched from a source without any being opened before muting it is suggested
+const MUTE_SUGGESTION_THRESHOLD: u32 = 50;
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct SourceStats {
+    pub fetched: u32,
+    pub opened: u32,
+}
+
+impl SourceStats {
+    /// Share of the fetched articles that were opened
+    pub fn read_through(&self) -> f32 {
+        if self.fetched == 0 {
+            0.
+        } else {
+            self.opened as f32 / self.fetched as f32
+        }
+    }
+}
+
+/// Per source reading statistics, persisted across sessions
+#[derive(Default, Serialize, Deserialize)]
+pub struct ReadingStats {
+    sources: BTreeMap<String, SourceStats>,
+}
+
+impl ReadingStats {
+    pub fn record_fetched(&mut self, source: &str) {
+        self.sources.entry(source.to_string()).or_default().fetched += 1;
+    }
+
+    pub fn record_opened(&mut self, source: &str) {
+        self.sources.entry(source.to_string()).or_default().opened += 1;
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &SourceStats)> {
+        self.sources
+            .iter()
+            .map(|(source, stats)| (source.as_str(), stats))
+    }
+
+    /// Sources that were never read despite plenty of articles, and aren't muted yet
+    pub fn mute_suggestions<'a>(
+        &'a self,
+        muted: &'a BTreeSet<String>,
+    ) -> impl Iterator<Item = &'a str> {
+        self.iter()
+            .filter(|(source, stats)| {
+                stats.opened == 0
+                    && stats.fetched >= MUTE_SUGGESTION_THRESHOLD
+                    && !muted.contains(*source)
+            })
+            .map(|(source, _)| source)
+    }
+}
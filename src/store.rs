@@ -0,0 +1,369 @@
+use eframe::egui::Context;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+/// Whether this build can keep its data in a SQLite database
+pub const SQLITE: bool = cfg!(all(feature = "sqlite", not(target_arch = "wasm32")));
+
+/// Where the data growing with use is kept: the bookmarks, read articles, history and cached
+/// responses. eframe's storage rewrites everything it holds on every save, the databases only the
+/// values that changed.
+#[derive(Default)]
+pub struct Store {
+    #[cfg(all(feature = "sqlite", not(target_arch = "wasm32")))]
+    database: Option<sqlite::Database>,
+    /// Keys whose last saved value is in the database, the others' is in eframe's storage
+    #[cfg(all(feature = "sqlite", not(target_arch = "wasm32")))]
+    in_database: BTreeSet<String>,
+    /// Whether the saves go to the database, turning it off hands the values back to eframe's
+    /// storage as they're saved
+    #[cfg(all(feature = "sqlite", not(target_arch = "wasm32")))]
+    saves_database: bool,
+    /// Opened in the background, eframe's storage is used until it's read
+    #[cfg(target_arch = "wasm32")]
+    database: indexed_db::Database,
+}
+
+impl Store {
+    /// Saves to the database when it's `enabled`, to eframe's storage otherwise. The keys
+    /// `in_database` are read from the database whatever the setting, none meaning every key it
+    /// has for the data saved before they were tracked.
+    #[cfg(all(feature = "sqlite", not(target_arch = "wasm32")))]
+    pub fn open(
+        enabled: bool,
+        in_database: Option<BTreeSet<String>>,
+        _ctx: &Context,
+    ) -> Result<Self, String> {
+        let database = match &in_database {
+            Some(keys) if !enabled && keys.is_empty() => None,
+            None if !enabled => None,
+            _ => Some(sqlite::Database::open()?),
+        };
+        let in_database = match (in_database, &database) {
+            (Some(keys), _) => keys,
+            (None, Some(database)) => database.keys().map_err(|e| e.to_string())?,
+            (None, None) => BTreeSet::new(),
+        };
+        Ok(Self {
+            database,
+            in_database,
+            saves_database: enabled,
+        })
+    }
+
+    /// Keys whose last saved value is in the database, for the next launch to read them there
+    #[cfg(all(feature = "sqlite", not(target_arch = "wasm32")))]
+    pub fn in_database(&self) -> Option<&BTreeSet<String>> {
+        Some(&self.in_database)
+    }
+
+    /// The value kept under `key`, none when it's eframe's storage that keeps it or the database
+    /// doesn't have it yet
+    #[cfg(all(feature = "sqlite", not(target_arch = "wasm32")))]
+    pub fn load<T: DeserializeOwned>(&self, key: &str) -> Option<Result<T, String>> {
+        if !self.in_database.contains(key) {
+            return None;
+        }
+        let database = self.database.as_ref()?;
+        match database.get(key) {
+            Ok(Some(value)) => Some(serde_json::from_str(&value).map_err(|e| e.to_string())),
+            Ok(None) => None,
+            Err(e) => Some(Err(e.to_string())),
+        }
+    }
+
+    /// Saves the value under `key`, returns false when it's for eframe's storage to keep
+    #[cfg(all(feature = "sqlite", not(target_arch = "wasm32")))]
+    pub fn save<T: Serialize>(&mut self, key: &str, value: &T) -> Result<bool, String> {
+        let database = match &mut self.database {
+            Some(database) if self.saves_database => database,
+            _ => {
+                self.in_database.remove(key);
+                return Ok(false);
+            }
+        };
+        let value = serde_json::to_string(value).map_err(|e| e.to_string())?;
+        database.set(key, &value).map_err(|e| e.to_string())?;
+        self.in_database.insert(key.to_string());
+        Ok(true)
+    }
+
+    /// IndexedDB, whatever the setting, the browser's local storage is too small for the data
+    #[cfg(target_arch = "wasm32")]
+    pub fn open(
+        _enabled: bool,
+        _in_database: Option<BTreeSet<String>>,
+        ctx: &Context,
+    ) -> Result<Self, String> {
+        Ok(Self {
+            database: indexed_db::Database::open(ctx.clone()),
+        })
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn load<T: DeserializeOwned>(&self, key: &str) -> Option<Result<T, String>> {
+        let value = self.database.get(key)?;
+        Some(serde_json::from_str(&value).map_err(|e| e.to_string()))
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn save<T: Serialize>(&mut self, key: &str, value: &T) -> Result<bool, String> {
+        if !self.database.is_read() {
+            return Ok(false);
+        }
+        let value = serde_json::to_string(value).map_err(|e| e.to_string())?;
+        self.database.set(key, value);
+        Ok(true)
+    }
+
+    /// Some once the database opened in the background is read, its values replace those
+    /// loaded from eframe's storage
+    #[cfg(target_arch = "wasm32")]
+    pub fn poll(&mut self) -> Option<Result<(), String>> {
+        self.database.poll()
+    }
+
+    /// eframe's storage, the build has no database
+    #[cfg(not(any(feature = "sqlite", target_arch = "wasm32")))]
+    pub fn open(
+        _enabled: bool,
+        _in_database: Option<BTreeSet<String>>,
+        _ctx: &Context,
+    ) -> Result<Self, String> {
+        Ok(Self::default())
+    }
+
+    #[cfg(not(any(feature = "sqlite", target_arch = "wasm32")))]
+    pub fn load<T: DeserializeOwned>(&self, _key: &str) -> Option<Result<T, String>> {
+        None
+    }
+
+    #[cfg(not(all(feature = "sqlite", not(target_arch = "wasm32"))))]
+    pub fn in_database(&self) -> Option<&BTreeSet<String>> {
+        None
+    }
+
+    #[cfg(not(any(feature = "sqlite", target_arch = "wasm32")))]
+    pub fn save<T: Serialize>(&mut self, _key: &str, _value: &T) -> Result<bool, String> {
+        Ok(false)
+    }
+
+    /// The database is opened before the first frame
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn poll(&mut self) -> Option<Result<(), String>> {
+        None
+    }
+}
+
+#[cfg(all(feature = "sqlite", not(target_arch = "wasm32")))]
+mod sqlite {
+    use rusqlite::{params, Connection, OptionalExtension};
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::{BTreeSet, HashMap};
+    use std::fs;
+    use std::hash::{Hash, Hasher};
+
+    /// Changes to the schema in order, the database's user_version counts those applied
+    const MIGRATIONS: &[&str] = &["CREATE TABLE documents (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL,
+            saved_at INTEGER NOT NULL
+        );"];
+
+    pub struct Database {
+        connection: Connection,
+        /// Hashes of the values last written, an unchanged value isn't written again
+        written: HashMap<String, u64>,
+    }
+
+    impl Database {
+        /// Opens the database next to eframe's storage, creating or migrating it as needed
+        pub fn open() -> Result<Self, String> {
+            let dir = directories_next::ProjectDirs::from("", "", "headlines")
+                .map(|dirs| dirs.data_dir().to_path_buf())
+                .ok_or("no data directory on this system")?;
+            fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+            let mut connection =
+                Connection::open(dir.join("headlines.sqlite")).map_err(|e| e.to_string())?;
+            migrate(&mut connection).map_err(|e| format!("Failed migrating: {}", e))?;
+            Ok(Self {
+                connection,
+                written: HashMap::new(),
+            })
+        }
+
+        pub fn get(&self, key: &str) -> rusqlite::Result<Option<String>> {
+            self.connection
+                .query_row(
+                    "SELECT value FROM documents WHERE key = ?1",
+                    params![key],
+                    |row| row.get(0),
+                )
+                .optional()
+        }
+
+        pub fn keys(&self) -> rusqlite::Result<BTreeSet<String>> {
+            let mut statement = self.connection.prepare("SELECT key FROM documents")?;
+            let keys = statement.query_map([], |row| row.get(0))?.collect();
+            keys
+        }
+
+        pub fn set(&mut self, key: &str, value: &str) -> rusqlite::Result<()> {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            let hash = hasher.finish();
+            if self.written.get(key) == Some(&hash) {
+                return Ok(());
+            }
+            self.connection.execute(
+                "INSERT INTO documents (key, value, saved_at) VALUES (?1, ?2, ?3)
+                ON CONFLICT (key) DO UPDATE SET value = excluded.value, saved_at = excluded.saved_at",
+                params![key, value, chrono::Utc::now().timestamp()],
+            )?;
+            self.written.insert(key.to_string(), hash);
+            Ok(())
+        }
+    }
+
+    fn migrate(connection: &mut Connection) -> rusqlite::Result<()> {
+        let version: i64 = connection.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+        // a database from a newer version is left as it is
+        if version >= MIGRATIONS.len() as i64 {
+            return Ok(());
+        }
+        let transaction = connection.transaction()?;
+        for migration in &MIGRATIONS[version as usize..] {
+            transaction.execute_batch(migration)?;
+        }
+        transaction.pragma_update(None, "user_version", MIGRATIONS.len() as i64)?;
+        transaction.commit()
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod indexed_db {
+    use eframe::egui::Context;
+    use eframe::wasm_bindgen::JsValue;
+    use rexie::{ObjectStore, Rexie, TransactionMode};
+    use std::cell::RefCell;
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashMap;
+    use std::hash::{Hash, Hasher};
+    use std::rc::Rc;
+
+    const NAME: &str = "headlines";
+    /// Raised with every change to the object stores, rexie creates the missing ones
+    const VERSION: u32 = 1;
+    const DOCUMENTS: &str = "documents";
+
+    enum State {
+        Opening,
+        /// The values it held when opened
+        Read(Rc<Rexie>, HashMap<String, String>),
+        Failed(String),
+    }
+
+    /// IndexedDB, read whole when it's opened
+    #[derive(Default)]
+    pub struct Database {
+        state: Rc<RefCell<Option<State>>>,
+        /// Whether the app was told it's read or failed
+        announced: bool,
+        /// Hashes of the values last written, an unchanged value isn't written again
+        written: Rc<RefCell<HashMap<String, u64>>>,
+    }
+
+    impl Database {
+        pub fn open(ctx: Context) -> Self {
+            let state = Rc::new(RefCell::new(Some(State::Opening)));
+            let opened = state.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let result = match read().await {
+                    Ok((rexie, documents)) => State::Read(Rc::new(rexie), documents),
+                    Err(e) => State::Failed(e.to_string()),
+                };
+                *opened.borrow_mut() = Some(result);
+                ctx.request_repaint();
+            });
+            Self {
+                state,
+                ..Self::default()
+            }
+        }
+
+        pub fn is_read(&self) -> bool {
+            matches!(*self.state.borrow(), Some(State::Read(..)))
+        }
+
+        pub fn poll(&mut self) -> Option<Result<(), String>> {
+            if self.announced {
+                return None;
+            }
+            let result = match &*self.state.borrow() {
+                Some(State::Read(..)) => Ok(()),
+                Some(State::Failed(e)) => Err(e.clone()),
+                Some(State::Opening) | None => return None,
+            };
+            self.announced = true;
+            Some(result)
+        }
+
+        pub fn get(&self, key: &str) -> Option<String> {
+            match &*self.state.borrow() {
+                Some(State::Read(_, documents)) => documents.get(key).cloned(),
+                _ => None,
+            }
+        }
+
+        /// Writes the value in the background, a failed write is tried again with the next save
+        pub fn set(&mut self, key: &str, value: String) {
+            let rexie = match &*self.state.borrow() {
+                Some(State::Read(rexie, _)) => rexie.clone(),
+                _ => return,
+            };
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            let hash = hasher.finish();
+            if self.written.borrow().get(key) == Some(&hash) {
+                return;
+            }
+            self.written.borrow_mut().insert(key.to_string(), hash);
+            let written = self.written.clone();
+            let key = key.to_string();
+            wasm_bindgen_futures::spawn_local(async move {
+                if let Err(e) = write(&rexie, &key, &value).await {
+                    tracing::error!("Failed storing the {}: {}", key, e);
+                    written.borrow_mut().remove(&key);
+                }
+            });
+        }
+    }
+
+    async fn read() -> rexie::Result<(Rexie, HashMap<String, String>)> {
+        let rexie = Rexie::builder(NAME)
+            .version(VERSION)
+            .add_object_store(ObjectStore::new(DOCUMENTS))
+            .build()
+            .await?;
+        let transaction = rexie.transaction(&[DOCUMENTS], TransactionMode::ReadOnly)?;
+        let documents = transaction
+            .store(DOCUMENTS)?
+            .get_all(None, None, None, None)
+            .await?
+            .into_iter()
+            .filter_map(|(key, value)| Some((key.as_string()?, value.as_string()?)))
+            .collect();
+        transaction.done().await?;
+        Ok((rexie, documents))
+    }
+
+    async fn write(rexie: &Rexie, key: &str, value: &str) -> rexie::Result<()> {
+        let transaction = rexie.transaction(&[DOCUMENTS], TransactionMode::ReadWrite)?;
+        transaction
+            .store(DOCUMENTS)?
+            .put(&JsValue::from_str(value), Some(&JsValue::from_str(key)))
+            .await?;
+        transaction.done().await
+    }
+}
@@ -0,0 +1,65 @@
+use crate::aggregate::SourceCaps;
+use crate::worker::RetryPolicy;
+use newsapi::Country;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Part of the app's stored config the tools without a window use
+#[derive(Default, Deserialize)]
+#[serde(default)]
+pub struct StoredConfig {
+    pub api_key: String,
+    pub country: Option<Country>,
+    pub more_countries: Vec<Country>,
+    pub source_caps: SourceCaps,
+    pub retry: RetryPolicy,
+    pub ticker: StoredTicker,
+}
+
+/// Part of the ticker's settings the app's window is created with
+#[derive(Default, Deserialize)]
+#[serde(default)]
+pub struct StoredTicker {
+    pub enabled: bool,
+}
+
+impl StoredConfig {
+    /// Config the app saved with eframe's persistence, the default one when there's none
+    pub fn load() -> Self {
+        let path = match storage_path() {
+            Some(path) => path,
+            None => return Self::default(),
+        };
+        // eframe stores every key as a RON string in a RON map
+        let config = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|ron| ron::from_str::<HashMap<String, String>>(&ron).ok())
+            .and_then(|values| values.get("headlines").cloned());
+        match config.map(|config| ron::from_str(&config)) {
+            Some(Ok(config)) => config,
+            Some(Err(e)) => {
+                eprintln!(
+                    "Ignoring the unreadable config in {}: {}",
+                    path.display(),
+                    e
+                );
+                Self::default()
+            }
+            None => Self::default(),
+        }
+    }
+
+    /// Countries of the app's feed, its main one first
+    pub fn countries(&self) -> Vec<Country> {
+        let mut countries = vec![self.country.unwrap_or(Country::FR)];
+        countries.extend(self.more_countries.iter().copied());
+        countries
+    }
+}
+
+/// Where eframe keeps the app's storage on this platform
+fn storage_path() -> Option<PathBuf> {
+    directories_next::ProjectDirs::from("", "", "headlines")
+        .map(|dirs| dirs.data_dir().join("app.ron"))
+}
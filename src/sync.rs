@@ -0,0 +1,363 @@
+use crate::http;
+use chrono::{DateTime, Utc};
+use eframe::egui::{ComboBox, Context, DragValue, Grid, TextEdit, Ui};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Name of the gist's file holding the settings
+const GIST_FILE: &str = "headlines-sync.json";
+const GITHUB_API: &str = "https://api.github.com/gists";
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SyncBackend {
+    /// A GitHub gist, the token needs the gist scope
+    Gist,
+    /// A file on a WebDAV server, Nextcloud's for one
+    WebDav,
+}
+
+impl SyncBackend {
+    pub const ALL: [SyncBackend; 2] = [Self::Gist, Self::WebDav];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Gist => "GitHub gist",
+            Self::WebDav => "WebDAV",
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SyncSettings {
+    pub enabled: bool,
+    pub backend: SyncBackend,
+    /// The gist's url or id, or the url of the WebDAV file
+    pub url: String,
+    /// WebDAV only, the token is sent as a bearer token without it
+    pub username: String,
+    pub token: String,
+    /// Minutes between two syncs
+    pub interval: u32,
+}
+
+impl Default for SyncSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            backend: SyncBackend::Gist,
+            url: String::new(),
+            username: String::new(),
+            token: String::new(),
+            interval: 15,
+        }
+    }
+}
+
+/// What's stored remotely, the settings file of "Export settings" stamped with when it changed
+#[derive(Serialize, Deserialize)]
+struct Remote {
+    modified: DateTime<Utc>,
+    settings: serde_json::Value,
+}
+
+#[derive(Deserialize)]
+struct Gist {
+    files: BTreeMap<String, GistFile>,
+}
+
+#[derive(Deserialize)]
+struct GistFile {
+    content: String,
+}
+
+enum Outcome {
+    /// The remote settings, none if nothing was pushed yet
+    Pulled(Option<Remote>),
+    /// The settings pushed and when they changed
+    Pushed(String, DateTime<Utc>),
+    Failed(String),
+}
+
+/// Keeps the settings and bookmarks the same across the app's instances through a remote file.
+/// The latest change wins, a local one counting from the sync that found it.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SettingsSync {
+    pub settings: SyncSettings,
+    /// The settings as they were last pushed or pulled, and when they changed then
+    synced: Option<String>,
+    synced_at: Option<DateTime<Utc>>,
+    /// When the settings were found to differ from the synced ones
+    changed_at: Option<DateTime<Utc>>,
+    #[serde(skip)]
+    next_sync: f64,
+    #[serde(skip)]
+    in_flight: bool,
+    /// Result of the last sync, shown in the settings
+    #[serde(skip)]
+    status: String,
+    #[serde(skip)]
+    outcome: Arc<Mutex<Option<Outcome>>>,
+}
+
+impl SettingsSync {
+    /// Applies the outcome of the sync under way and starts one when it's due. `local` gives the
+    /// settings as they are now. Returns remote settings that are newer, to apply.
+    pub fn poll(
+        &mut self,
+        ctx: &Context,
+        local: impl FnOnce() -> Result<String, String>,
+    ) -> Result<Option<String>, String> {
+        let now = ctx.input().time;
+        let outcome = self.outcome.lock().unwrap().take();
+        match outcome {
+            Some(Outcome::Pulled(remote)) => {
+                self.in_flight = false;
+                return self.merge(remote, local()?, ctx);
+            }
+            Some(Outcome::Pushed(settings, modified)) => {
+                self.in_flight = false;
+                if self.changed_at.map_or(true, |changed| changed <= modified) {
+                    self.changed_at = None;
+                }
+                self.synced = Some(settings);
+                self.synced_at = Some(modified);
+                self.status = format!("Pushed at {}", chrono::Local::now().format("%H:%M"));
+            }
+            Some(Outcome::Failed(e)) => {
+                self.in_flight = false;
+                self.status = format!("Failed: {}", e);
+                return Err(e);
+            }
+            None => {}
+        }
+
+        if !self.settings.enabled || self.settings.url.trim().is_empty() || self.in_flight {
+            return Ok(None);
+        }
+        if now >= self.next_sync {
+            self.next_sync = now + f64::from(self.settings.interval.max(1)) * 60.;
+            self.in_flight = true;
+            pull(&self.settings, self.outcome.clone(), ctx.clone());
+        }
+        ctx.request_repaint_after(Duration::from_secs_f64((self.next_sync - now).max(0.)));
+        Ok(None)
+    }
+
+    /// Records the settings as applied from the remote ones, they may serialize a bit differently
+    pub fn applied(&mut self, local: String) {
+        self.synced = Some(local);
+    }
+
+    pub fn show_settings(&mut self, ui: &mut Ui) {
+        let settings = &mut self.settings;
+        ui.checkbox(&mut settings.enabled, "Sync settings and bookmarks");
+        Grid::new("sync").num_columns(2).show(ui, |ui| {
+            ui.label("Through");
+            ComboBox::from_id_source("sync_backend")
+                .selected_text(settings.backend.name())
+                .show_ui(ui, |ui| {
+                    for backend in SyncBackend::ALL {
+                        ui.selectable_value(&mut settings.backend, backend, backend.name());
+                    }
+                });
+            ui.end_row();
+            ui.label("Url");
+            let hint = match settings.backend {
+                SyncBackend::Gist => "https://gist.github.com/you/<id>",
+                SyncBackend::WebDav => "https://dav.example.com/headlines.json",
+            };
+            ui.add(TextEdit::singleline(&mut settings.url).hint_text(hint));
+            ui.end_row();
+            if settings.backend == SyncBackend::WebDav {
+                ui.label("Username");
+                ui.add(TextEdit::singleline(&mut settings.username).hint_text("none"));
+                ui.end_row();
+            }
+            ui.label("Token");
+            ui.add(TextEdit::singleline(&mut settings.token).password(true));
+            ui.end_row();
+            ui.label("Every");
+            ui.add(
+                DragValue::new(&mut settings.interval)
+                    .clamp_range(1..=24 * 60)
+                    .suffix(" min"),
+            );
+            ui.end_row();
+        });
+        ui.weak("The token is stored unencrypted, apart from the synced settings");
+        ui.horizontal(|ui| {
+            if ui.button("Sync now").clicked() {
+                self.next_sync = 0.;
+            }
+            ui.weak(&self.status);
+        });
+    }
+
+    /// Pushes the local settings if they changed after the remote ones, returns the remote ones
+    /// if they changed last
+    fn merge(
+        &mut self,
+        remote: Option<Remote>,
+        local: String,
+        ctx: &Context,
+    ) -> Result<Option<String>, String> {
+        let now = Utc::now();
+        if self.synced.as_ref() != Some(&local) && self.changed_at.is_none() {
+            self.changed_at = Some(now);
+        }
+        if let Some(remote) = &remote {
+            // a first sync takes the settings of the instances already syncing
+            let newer_than_synced = self.synced_at.map_or(true, |at| remote.modified > at);
+            let newer_than_local = self.changed_at.map_or(true, |at| remote.modified > at);
+            if self.synced_at.is_none() || (newer_than_synced && newer_than_local) {
+                self.synced_at = Some(remote.modified);
+                self.changed_at = None;
+                self.status = format!("Pulled at {}", chrono::Local::now().format("%H:%M"));
+                // nothing to apply when the other instance changed nothing of ours
+                if serde_json::from_str::<serde_json::Value>(&local)
+                    .ok()
+                    .as_ref()
+                    == Some(&remote.settings)
+                {
+                    self.synced = Some(local);
+                    return Ok(None);
+                }
+                let settings = remote.settings.to_string();
+                self.synced = Some(settings.clone());
+                return Ok(Some(settings));
+            }
+        }
+
+        match self.changed_at {
+            Some(modified) => {
+                let settings = serde_json::from_str(&local).map_err(|e| e.to_string())?;
+                self.in_flight = true;
+                push(
+                    &self.settings,
+                    Remote { modified, settings },
+                    local,
+                    self.outcome.clone(),
+                    ctx.clone(),
+                );
+            }
+            None => self.status = format!("Up to date at {}", chrono::Local::now().format("%H:%M")),
+        }
+        Ok(None)
+    }
+}
+
+fn pull(settings: &SyncSettings, outcome: Arc<Mutex<Option<Outcome>>>, ctx: Context) {
+    let backend = settings.backend;
+    http::fetch(request(settings, "GET", Vec::new()), move |response| {
+        let pulled = match response {
+            Ok(response) if response.ok => {
+                read_remote(backend, &response.bytes).map(Outcome::Pulled)
+            }
+            // nothing was pushed to the WebDAV file yet
+            Ok(response) if response.status == 404 && backend == SyncBackend::WebDav => {
+                Ok(Outcome::Pulled(None))
+            }
+            Ok(response) => Err(format!("{} {}", response.status, response.status_text)),
+            Err(e) => Err(e),
+        };
+        *outcome.lock().unwrap() = Some(pulled.unwrap_or_else(Outcome::Failed));
+        ctx.request_repaint();
+    });
+}
+
+fn read_remote(backend: SyncBackend, bytes: &[u8]) -> Result<Option<Remote>, String> {
+    let unreadable = |e: serde_json::Error| format!("Unreadable remote settings: {}", e);
+    match backend {
+        SyncBackend::Gist => {
+            let gist: Gist = serde_json::from_slice(bytes).map_err(unreadable)?;
+            gist.files
+                .get(GIST_FILE)
+                .map(|file| serde_json::from_str(&file.content).map_err(unreadable))
+                .transpose()
+        }
+        SyncBackend::WebDav => serde_json::from_slice(bytes).map(Some).map_err(unreadable),
+    }
+}
+
+fn push(
+    settings: &SyncSettings,
+    remote: Remote,
+    local: String,
+    outcome: Arc<Mutex<Option<Outcome>>>,
+    ctx: Context,
+) {
+    let modified = remote.modified;
+    let body = match settings.backend {
+        SyncBackend::Gist => serde_json::to_string(&remote).and_then(|content| {
+            serde_json::to_vec(&serde_json::json!({
+                "files": { GIST_FILE: { "content": content } }
+            }))
+        }),
+        SyncBackend::WebDav => serde_json::to_vec(&remote),
+    };
+    let body = match body {
+        Ok(body) => body,
+        Err(e) => {
+            *outcome.lock().unwrap() = Some(Outcome::Failed(e.to_string()));
+            return;
+        }
+    };
+    let method = match settings.backend {
+        SyncBackend::Gist => "PATCH",
+        SyncBackend::WebDav => "PUT",
+    };
+    http::fetch(request(settings, method, body), move |response| {
+        let pushed = match response {
+            Ok(response) if response.ok => Outcome::Pushed(local, modified),
+            Ok(response) => {
+                Outcome::Failed(format!("{} {}", response.status, response.status_text))
+            }
+            Err(e) => Outcome::Failed(e),
+        };
+        *outcome.lock().unwrap() = Some(pushed);
+        ctx.request_repaint();
+    });
+}
+
+fn request(settings: &SyncSettings, method: &str, body: Vec<u8>) -> ehttp::Request {
+    let url = match settings.backend {
+        SyncBackend::Gist => {
+            // the id is the last part of the gist's url
+            let id = settings.url.trim().trim_end_matches('/').rsplit('/').next();
+            format!("{}/{}", GITHUB_API, id.unwrap_or_default())
+        }
+        SyncBackend::WebDav => settings.url.trim().to_string(),
+    };
+    let mut request = ehttp::Request::get(url);
+    request.method = method.to_string();
+    request.body = body;
+    let token = settings.token.trim();
+    let authorization = if settings.backend == SyncBackend::WebDav && !settings.username.is_empty()
+    {
+        let credentials = format!("{}:{}", settings.username, token);
+        format!("Basic {}", base64::encode(credentials))
+    } else {
+        format!("Bearer {}", token)
+    };
+    request
+        .headers
+        .insert("Authorization".to_string(), authorization);
+    request
+        .headers
+        .insert("Content-Type".to_string(), "application/json".to_string());
+    if settings.backend == SyncBackend::Gist {
+        request.headers.insert(
+            "Accept".to_string(),
+            "application/vnd.github+json".to_string(),
+        );
+        // GitHub refuses requests without one, browsers set their own
+        request
+            .headers
+            .insert("User-Agent".to_string(), "headlines".to_string());
+    }
+    request
+}
@@ -0,0 +1,259 @@
+use crate::schedule::NightSchedule;
+use chrono::Utc;
+use eframe::egui::{Color32, ComboBox, Grid, Separator, Stroke, Ui, Visuals};
+use serde::{Deserialize, Serialize};
+
+pub const WHITE: Color32 = Color32::from_rgb(255, 255, 255);
+pub const BLACK: Color32 = Color32::from_rgb(0, 0, 0);
+pub const CYAN: Color32 = Color32::from_rgb(0, 255, 255);
+pub const RED: Color32 = Color32::from_rgb(255, 0, 0);
+/// Links of the light high-contrast theme, RED being too light on a light background
+pub const DARK_RED: Color32 = Color32::from_rgb(170, 0, 0);
+
+/// WCAG's AAA contrast ratio for body text, kept to by the high-contrast theme
+const HIGH_CONTRAST_RATIO: f32 = 7.;
+
+/// Share of their brightness images lose in dark mode at full dimming strength
+const MAX_IMAGE_DIMMING: f32 = 0.5;
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ThemeMode {
+    Dark,
+    Light,
+    /// Follows the OS preference, or `prefers-color-scheme` on the web
+    System,
+    /// Dark at night, as set by the night schedule
+    Scheduled,
+}
+
+impl ThemeMode {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Dark => Self::Light,
+            Self::Light => Self::System,
+            Self::System => Self::Scheduled,
+            Self::Scheduled => Self::Dark,
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Self::Dark => "🌙",
+            Self::Light => "🌞",
+            Self::System => "🖥",
+            Self::Scheduled => "⏰",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Dark => "dark",
+            Self::Light => "light",
+            Self::System => "system",
+            Self::Scheduled => "scheduled",
+        }
+    }
+
+    /// Whether dark visuals should be used, `system_theme` being unknown on some platforms
+    pub fn is_dark(&self, system_theme: Option<eframe::Theme>, schedule: &NightSchedule) -> bool {
+        match self {
+            Self::Dark => true,
+            Self::Light => false,
+            Self::System => system_theme != Some(eframe::Theme::Light),
+            Self::Scheduled => schedule.is_night(Utc::now()),
+        }
+    }
+}
+
+/// WCAG contrast ratio between two colors, from 1 for the same luminance to 21 for black on white
+pub fn contrast_ratio(a: Color32, b: Color32) -> f32 {
+    let (a, b) = (relative_luminance(a), relative_luminance(b));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+/// Luminance of an sRGB color as WCAG defines it, from 0 to 1
+fn relative_luminance(color: Color32) -> f32 {
+    let linear = |channel: u8| {
+        let c = channel as f32 / 255.;
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * linear(color.r()) + 0.7152 * linear(color.g()) + 0.0722 * linear(color.b())
+}
+
+/// `color` when it stands out enough from `background`, `fallback` otherwise
+fn legible(color: Color32, background: Color32, fallback: Color32) -> Color32 {
+    if contrast_ratio(color, background) >= HIGH_CONTRAST_RATIO {
+        color
+    } else {
+        fallback
+    }
+}
+
+/// Tint multiplying the colors of images, dims them in dark mode by `strength` from 0 to 1
+pub fn image_tint(dark_mode: bool, strength: f32) -> Color32 {
+    if !dark_mode {
+        return Color32::WHITE;
+    }
+    let brightness = 1. - strength.clamp(0., 1.) * MAX_IMAGE_DIMMING;
+    Color32::from_gray((255. * brightness).round() as u8)
+}
+
+fn base_visuals(dark_mode: bool) -> Visuals {
+    if dark_mode {
+        Visuals::dark()
+    } else {
+        Visuals::light()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SeparatorStyle {
+    Line,
+    Thick,
+    Space,
+}
+
+impl SeparatorStyle {
+    const ALL: [SeparatorStyle; 3] = [Self::Line, Self::Thick, Self::Space];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Line => "Line",
+            Self::Thick => "Thick line",
+            Self::Space => "Blank space",
+        }
+    }
+}
+
+/// User adjustable colors layered on top of egui's dark or light visuals
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct Theme {
+    pub title: Color32,
+    pub accent: Color32,
+    pub hyperlink: Color32,
+    pub card_background: Color32,
+    pub separator: SeparatorStyle,
+}
+
+impl Theme {
+    pub fn dark() -> Self {
+        Self {
+            title: WHITE,
+            accent: Visuals::dark().selection.bg_fill,
+            hyperlink: CYAN,
+            card_background: Color32::TRANSPARENT,
+            separator: SeparatorStyle::Line,
+        }
+    }
+
+    pub fn light() -> Self {
+        Self {
+            title: BLACK,
+            accent: Visuals::light().selection.bg_fill,
+            hyperlink: RED,
+            card_background: Color32::TRANSPARENT,
+            separator: SeparatorStyle::Line,
+        }
+    }
+
+    /// Copy whose titles and links fall back to WHITE and CYAN, or BLACK and DARK_RED, where
+    /// they don't meet the high contrast ratio against the background
+    pub fn high_contrast(&self, dark_mode: bool) -> Self {
+        let background = base_visuals(dark_mode).widgets.noninteractive.bg_fill;
+        let (text, link) = if dark_mode {
+            (WHITE, CYAN)
+        } else {
+            (BLACK, DARK_RED)
+        };
+        Self {
+            title: legible(self.title, background, text),
+            accent: self.accent,
+            hyperlink: legible(self.hyperlink, background, link),
+            card_background: Color32::TRANSPARENT,
+            separator: self.separator,
+        }
+    }
+
+    pub fn visuals(&self, dark_mode: bool, high_contrast: bool) -> Visuals {
+        let mut visuals = base_visuals(dark_mode);
+        visuals.hyperlink_color = self.hyperlink;
+        visuals.selection.bg_fill = self.accent;
+        visuals.widgets.active.bg_fill = self.accent;
+        if high_contrast {
+            let (text, field) = if dark_mode {
+                (WHITE, BLACK)
+            } else {
+                (BLACK, WHITE)
+            };
+            visuals.override_text_color = Some(text);
+            visuals.extreme_bg_color = field;
+            let widgets = &mut visuals.widgets;
+            for widget in [
+                &mut widgets.noninteractive,
+                &mut widgets.inactive,
+                &mut widgets.hovered,
+                &mut widgets.active,
+                &mut widgets.open,
+            ] {
+                widget.fg_stroke.color = text;
+                widget.bg_stroke = Stroke::new(widget.bg_stroke.width.max(1.), text);
+            }
+        }
+        visuals
+    }
+
+    pub fn add_separator(&self, ui: &mut Ui) {
+        match self.separator {
+            SeparatorStyle::Line => {
+                ui.add(Separator::default());
+            }
+            SeparatorStyle::Thick => {
+                ui.scope(|ui| {
+                    ui.visuals_mut().widgets.noninteractive.bg_stroke.width = 3.;
+                    ui.add(Separator::default());
+                });
+            }
+            SeparatorStyle::Space => {
+                ui.add_space(12.);
+            }
+        }
+    }
+
+    /// Editor for every field, `default` being what the reset button restores
+    pub fn editor(&mut self, ui: &mut Ui, default: Theme) {
+        Grid::new("theme_editor").num_columns(2).show(ui, |ui| {
+            ui.label("Titles");
+            ui.color_edit_button_srgba(&mut self.title);
+            ui.end_row();
+
+            ui.label("Accent");
+            ui.color_edit_button_srgba(&mut self.accent);
+            ui.end_row();
+
+            ui.label("Links");
+            ui.color_edit_button_srgba(&mut self.hyperlink);
+            ui.end_row();
+
+            ui.label("Card background");
+            ui.color_edit_button_srgba(&mut self.card_background);
+            ui.end_row();
+
+            ui.label("Separator");
+            ComboBox::from_id_source("separator_style")
+                .selected_text(self.separator.name())
+                .show_ui(ui, |ui| {
+                    for style in SeparatorStyle::ALL {
+                        ui.selectable_value(&mut self.separator, style, style.name());
+                    }
+                });
+            ui.end_row();
+        });
+        if ui.button("Reset").clicked() {
+            *self = default;
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use crate::http;
+use eframe::egui::{
+    vec2, Color32, ColorImage, Context, Image, Rect, Sense, TextureHandle, TextureOptions, Ui,
+};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Height of the placeholder shown while an image loads, as a share of its width
+const PLACEHOLDER_RATIO: f32 = 9. / 16.;
+/// Seconds the shimmer takes to sweep across the placeholder
+const SHIMMER_PERIOD: f64 = 1.2;
+
+enum Thumbnail {
+    Loading,
+    Loaded(TextureHandle),
+    /// Not retried until the next launch
+    Failed,
+}
+
+type ThumbnailCache = Arc<Mutex<HashMap<String, Thumbnail>>>;
+
+/// Images of the cards, fetched the first time they're shown and kept for the session, and
+/// between sessions on disk on native or in the browser's Cache Storage on the web
+#[derive(Default)]
+pub struct Thumbnails {
+    cache: ThumbnailCache,
+}
+
+impl Thumbnails {
+    /// Shows the image at `url` at most `width` wide, a placeholder while it loads and nothing if
+    /// it couldn't be
+    pub fn show(&self, ui: &mut Ui, url: &str, width: f32, tint: Color32) {
+        self.prefetch(ui.ctx(), url);
+        match self.cache.lock().unwrap().get(url) {
+            Some(Thumbnail::Loaded(texture)) => {
+                let size = texture.size_vec2();
+                let size = size * (width / size.x).min(1.);
+                ui.add(Image::new(texture, size).tint(tint));
+            }
+            Some(Thumbnail::Loading) => placeholder(ui, width),
+            Some(Thumbnail::Failed) | None => {}
+        }
+    }
+
+    /// Starts loading the image at `url`, unless it already was
+    pub fn prefetch(&self, ctx: &Context, url: &str) {
+        let mut cache = self.cache.lock().unwrap();
+        if cache.contains_key(url) {
+            return;
+        }
+        cache.insert(url.to_string(), Thumbnail::Loading);
+        drop(cache);
+        load(url.to_string(), self.cache.clone(), ctx.clone());
+    }
+}
+
+/// A box the size of a typical image, with a band of light sweeping across it unless reduced
+/// motion turned the animations off
+fn placeholder(ui: &mut Ui, width: f32) {
+    let width = width.min(ui.available_width());
+    let (rect, _) = ui.allocate_exact_size(vec2(width, width * PLACEHOLDER_RATIO), Sense::hover());
+    let painter = ui.painter_at(rect);
+    let visuals = ui.visuals();
+    painter.rect_filled(
+        rect,
+        visuals.widgets.noninteractive.rounding,
+        visuals.faint_bg_color,
+    );
+    if ui.style().animation_time == 0. {
+        return;
+    }
+    let phase = (ui.input().time % SHIMMER_PERIOD / SHIMMER_PERIOD) as f32;
+    let band = rect.width() / 3.;
+    let left = rect.left() - band + phase * (rect.width() + band);
+    let shimmer = Rect::from_x_y_ranges(left..=left + band, rect.y_range());
+    painter.rect_filled(
+        shimmer,
+        0.,
+        visuals.widgets.inactive.bg_fill.linear_multiply(0.5),
+    );
+    ui.ctx().request_repaint();
+}
+
+/// Reads the image from the cache on disk or in the browser, fetches it when it isn't there
+fn load(url: String, cache: ThumbnailCache, ctx: Context) {
+    #[cfg(not(target_arch = "wasm32"))]
+    match disk::read(&url) {
+        Some(bytes) => finish(url, Ok(bytes), cache, ctx),
+        None => fetch(url, cache, ctx),
+    }
+    #[cfg(target_arch = "wasm32")]
+    wasm_bindgen_futures::spawn_local(async move {
+        match web::read(&url).await {
+            Some(bytes) => finish(url, Ok(bytes), cache, ctx),
+            None => fetch(url, cache, ctx),
+        }
+    });
+}
+
+fn fetch(url: String, cache: ThumbnailCache, ctx: Context) {
+    http::fetch(ehttp::Request::get(&url), move |response| {
+        let bytes = match response {
+            Ok(response) if response.ok => {
+                #[cfg(not(target_arch = "wasm32"))]
+                disk::write(&url, &response.bytes);
+                #[cfg(target_arch = "wasm32")]
+                wasm_bindgen_futures::spawn_local(web::write(url.clone(), response.bytes.clone()));
+                Ok(response.bytes)
+            }
+            Ok(response) => Err(format!("{} {}", response.status, response.status_text)),
+            Err(e) => Err(e),
+        };
+        finish(url, bytes, cache, ctx);
+    });
+}
+
+fn finish(url: String, bytes: Result<Vec<u8>, String>, cache: ThumbnailCache, ctx: Context) {
+    let thumbnail = match bytes.and_then(|bytes| decode(&bytes)) {
+        Ok(image) => Thumbnail::Loaded(ctx.load_texture(&url, image, TextureOptions::LINEAR)),
+        Err(e) => {
+            tracing::error!("Failed loading the thumbnail {}: {}", url, e);
+            Thumbnail::Failed
+        }
+    };
+    cache.lock().unwrap().insert(url, thumbnail);
+    ctx.request_repaint();
+}
+
+pub fn decode(bytes: &[u8]) -> Result<ColorImage, String> {
+    let image = image::load_from_memory(bytes)
+        .map_err(|e| e.to_string())?
+        .to_rgba8();
+    let size = [image.width() as usize, image.height() as usize];
+    Ok(ColorImage::from_rgba_unmultiplied(
+        size,
+        image.as_flat_samples().as_slice(),
+    ))
+}
+
+/// Images kept under the cache directory, a read marks the file as used so that the cleanup
+/// evicts the least recently used ones first
+#[cfg(not(target_arch = "wasm32"))]
+mod disk {
+    use filetime::FileTime;
+    use std::collections::hash_map::DefaultHasher;
+    use std::fs;
+    use std::hash::{Hash, Hasher};
+    use std::path::PathBuf;
+
+    fn path(url: &str) -> Option<PathBuf> {
+        let mut hasher = DefaultHasher::new();
+        url.hash(&mut hasher);
+        directories_next::ProjectDirs::from("", "", "headlines").map(|dirs| {
+            dirs.cache_dir()
+                .join("thumbnails")
+                .join(format!("{:016x}", hasher.finish()))
+        })
+    }
+
+    pub fn read(url: &str) -> Option<Vec<u8>> {
+        let path = path(url)?;
+        let bytes = fs::read(&path).ok()?;
+        if let Err(e) = filetime::set_file_mtime(&path, FileTime::now()) {
+            tracing::error!("Failed marking the thumbnail {} as used: {}", url, e);
+        }
+        Some(bytes)
+    }
+
+    pub fn write(url: &str, bytes: &[u8]) {
+        let path = match path(url) {
+            Some(path) => path,
+            None => return,
+        };
+        let result = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&path, bytes));
+        if let Err(e) = result {
+            tracing::error!("Failed saving the thumbnail {}: {}", url, e);
+        }
+    }
+}
+
+/// Images kept in the browser's Cache Storage, which the browser evicts when it runs short of
+/// space
+#[cfg(target_arch = "wasm32")]
+mod web {
+    use eframe::wasm_bindgen::{JsCast, JsValue};
+    use wasm_bindgen_futures::JsFuture;
+    use web_sys::{Cache, Response};
+
+    const CACHE: &str = "thumbnails";
+
+    async fn open() -> Result<Cache, JsValue> {
+        let window = web_sys::window().ok_or_else(|| JsValue::from_str("No window"))?;
+        let cache = JsFuture::from(window.caches()?.open(CACHE)).await?;
+        cache.dyn_into()
+    }
+
+    pub async fn read(url: &str) -> Option<Vec<u8>> {
+        let bytes = async {
+            let response = JsFuture::from(open().await?.match_with_str(url)).await?;
+            if response.is_undefined() {
+                return Ok(None);
+            }
+            let response: Response = response.dyn_into()?;
+            let buffer = JsFuture::from(response.array_buffer()?).await?;
+            Ok::<_, JsValue>(Some(js_sys::Uint8Array::new(&buffer).to_vec()))
+        };
+        bytes.await.unwrap_or_else(|e| {
+            tracing::error!("Failed reading the cached thumbnail {}: {:?}", url, e);
+            None
+        })
+    }
+
+    pub async fn write(url: String, mut bytes: Vec<u8>) {
+        let written = async {
+            let response = Response::new_with_opt_u8_array(Some(&mut bytes))?;
+            JsFuture::from(open().await?.put_with_str(&url, &response)).await?;
+            Ok::<_, JsValue>(())
+        };
+        if let Err(e) = written.await {
+            tracing::error!("Failed caching the thumbnail {}: {:?}", url, e);
+        }
+    }
+}
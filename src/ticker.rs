@@ -0,0 +1,151 @@
+use crate::accessibility;
+use crate::news::NewsCardData;
+use eframe::egui::{
+    self, vec2, Button, CentralPanel, Color32, Context, Hyperlink, Layout, Sense, Vec2,
+};
+use eframe::Frame;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Size of the window in ticker mode
+const TICKER_SIZE: Vec2 = vec2(540., 72.);
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TickerSettings {
+    /// Whether the window is created for the ticker, which takes a restart
+    pub enabled: bool,
+    /// Seconds each headline stays in the ticker
+    pub interval: f32,
+    /// Opacity of the ticker's background, from 0.2 to 1
+    pub opacity: f32,
+}
+
+impl Default for TickerSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval: 6.,
+            opacity: 1.,
+        }
+    }
+}
+
+/// Compact always-on-top mode cycling through the headlines one at a time, native only as it
+/// resizes the window
+#[derive(Default)]
+pub struct Ticker {
+    /// Whether the window was created transparent and small enough for the ticker
+    available: bool,
+    active: bool,
+    /// Index of the headline shown
+    index: usize,
+    shown_at: f64,
+    /// Size of the window before entering the ticker mode, it's given back on leaving it
+    restore_size: Option<Vec2>,
+}
+
+impl Ticker {
+    /// Ticker of a window created for it
+    pub fn available() -> Self {
+        Self {
+            available: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn is_available(&self) -> bool {
+        self.available
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    pub fn enter(&mut self, frame: &mut Frame, now: f64) {
+        self.active = true;
+        self.index = 0;
+        self.shown_at = now;
+        self.restore_size = Some(frame.info().window_info.size);
+        frame.set_decorations(false);
+        frame.set_always_on_top(true);
+        frame.set_window_size(TICKER_SIZE);
+    }
+
+    pub fn leave(&mut self, frame: &mut Frame) {
+        self.active = false;
+        frame.set_always_on_top(false);
+        frame.set_decorations(true);
+        if let Some(size) = self.restore_size.take() {
+            frame.set_window_size(size);
+        }
+    }
+
+    /// Fills the window with the ticker, the headline changing every `settings.interval` seconds
+    /// unless the pointer is over it
+    pub fn show(
+        &mut self,
+        ctx: &Context,
+        frame: &mut Frame,
+        articles: &[NewsCardData],
+        settings: &TickerSettings,
+    ) {
+        let now = ctx.input().time;
+        let interval = f64::from(settings.interval);
+        if now - self.shown_at >= interval {
+            self.index += 1;
+            self.shown_at = now;
+        }
+        let remaining = interval - (now - self.shown_at);
+        ctx.request_repaint_after(Duration::from_secs_f64(remaining.max(0.)));
+
+        let background = ctx.style().visuals.widgets.noninteractive.bg_fill;
+        let alpha = (settings.opacity.clamp(0.2, 1.) * 255.) as u8;
+        let [r, g, b, _] = background.to_array();
+        let panel = egui::Frame::none()
+            .fill(Color32::from_rgba_unmultiplied(r, g, b, alpha))
+            .inner_margin(8.);
+
+        CentralPanel::default().frame(panel).show(ctx, |ui| {
+            // the window has no title bar to be moved by
+            let background = ui.interact(ui.max_rect(), ui.id().with("ticker"), Sense::drag());
+            if background.drag_started() {
+                frame.drag_window();
+            }
+            if ui.rect_contains_pointer(ui.max_rect()) {
+                self.shown_at = now;
+            }
+
+            ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                let leave_btn =
+                    accessibility::label(ui.add(Button::new("⤢").frame(false)), "Leave the ticker");
+                if leave_btn.clicked() {
+                    self.leave(frame);
+                }
+                if articles.is_empty() {
+                    ui.weak("No headlines yet");
+                    return;
+                }
+                self.index %= articles.len();
+                let next_btn =
+                    accessibility::label(ui.add(Button::new("▶").frame(false)), "Next headline");
+                if next_btn.clicked() {
+                    self.index = (self.index + 1) % articles.len();
+                    self.shown_at = now;
+                }
+                let previous_btn = accessibility::label(
+                    ui.add(Button::new("◀").frame(false)),
+                    "Previous headline",
+                );
+                if previous_btn.clicked() {
+                    self.index = self.index.checked_sub(1).unwrap_or(articles.len() - 1);
+                    self.shown_at = now;
+                }
+                let article = &articles[self.index];
+                ui.with_layout(Layout::left_to_right(egui::Align::Center), |ui| {
+                    ui.add(Hyperlink::from_label_and_url(&article.title, &article.url));
+                });
+            });
+        });
+    }
+}
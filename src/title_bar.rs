@@ -0,0 +1,70 @@
+use crate::accessibility;
+use eframe::egui::{Align, Button, Context, Id, Layout, RichText, Sense, TopBottomPanel};
+use eframe::Frame;
+
+const HEIGHT: f32 = 28.;
+
+/// Title bar drawn by the app in place of the system's, the same on every platform: dragging it
+/// moves the window and double-clicking it maximizes it
+#[derive(Default)]
+pub struct TitleBar {
+    maximized: bool,
+    /// Whether the system's decorations were last swapped for the title bar, none when they're
+    /// someone else's
+    applied: Option<bool>,
+}
+
+impl TitleBar {
+    /// Turns the system's decorations off while `enabled`, `None` leaves them alone until it's
+    /// `Some` again
+    pub fn update(&mut self, frame: &mut Frame, enabled: Option<bool>) {
+        if enabled != self.applied {
+            if let Some(enabled) = enabled {
+                frame.set_decorations(!enabled);
+            }
+            self.applied = enabled;
+        }
+    }
+
+    /// Draws the title bar, returns true when its close button was clicked
+    pub fn show(&mut self, ctx: &Context, frame: &mut Frame) -> bool {
+        let mut close = false;
+        TopBottomPanel::top("title_bar")
+            .exact_height(HEIGHT)
+            .show(ctx, |ui| {
+                // added first so that the buttons above it get their clicks
+                let bar = ui.interact(ui.max_rect(), Id::new("title_bar"), Sense::click());
+                ui.with_layout(Layout::left_to_right(Align::Center), |ui| {
+                    ui.label(RichText::new("📓 Headlines").strong());
+                    ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                        let close_btn = ui.add(Button::new("❌").frame(false));
+                        close = accessibility::label(close_btn, "Close").clicked();
+                        let (icon, label) = if self.maximized {
+                            ("🗗", "Restore")
+                        } else {
+                            ("🗖", "Maximize")
+                        };
+                        let maximize_btn = ui.add(Button::new(icon).frame(false));
+                        if accessibility::label(maximize_btn, label).clicked() {
+                            self.toggle_maximized(frame);
+                        }
+                        let minimize_btn = ui.add(Button::new("🗕").frame(false));
+                        if accessibility::label(minimize_btn, "Minimize").clicked() {
+                            frame.set_minimized(true);
+                        }
+                    });
+                });
+                if bar.double_clicked() {
+                    self.toggle_maximized(frame);
+                } else if bar.is_pointer_button_down_on() {
+                    frame.drag_window();
+                }
+            });
+        close
+    }
+
+    fn toggle_maximized(&mut self, frame: &mut Frame) {
+        self.maximized = !self.maximized;
+        frame.set_maximized(self.maximized);
+    }
+}
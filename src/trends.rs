@@ -0,0 +1,138 @@
+use crate::news::NewsCardData;
+use chrono::{DateTime, Duration, Utc};
+use eframe::egui::{Grid, ProgressBar, Ui};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// How far back the report looks
+const REPORT_DAYS: i64 = 7;
+
+const BAR_WIDTH: f32 = 160.;
+
+/// An article fetched in the last week, kept with the text its topics are looked for in
+#[derive(Serialize, Deserialize)]
+struct Sighting {
+    url: String,
+    source: String,
+    /// Lowercase title and description
+    text: String,
+    seen_at: DateTime<Utc>,
+}
+
+/// Articles fetched over the last week, persisted so the report spans sessions
+#[derive(Default, Serialize, Deserialize)]
+pub struct TopicTrends {
+    sightings: Vec<Sighting>,
+}
+
+/// How many of a source's articles of the week mention a topic
+pub struct SourceCoverage {
+    pub source: String,
+    pub mentions: usize,
+    pub articles: usize,
+}
+
+impl SourceCoverage {
+    /// Share of the source's articles mentioning the topic
+    pub fn share(&self) -> f32 {
+        if self.articles == 0 {
+            0.
+        } else {
+            self.mentions as f32 / self.articles as f32
+        }
+    }
+}
+
+pub struct TopicCoverage {
+    pub topic: String,
+    /// Every source of the week, the ones covering the topic most first
+    pub sources: Vec<SourceCoverage>,
+}
+
+impl TopicTrends {
+    /// Counts the article once, however many refreshes fetch it, and forgets those older than a
+    /// week
+    pub fn record(&mut self, article: &NewsCardData) {
+        let since = article.fetched_at - Duration::days(REPORT_DAYS);
+        self.sightings.retain(|s| s.seen_at >= since);
+        if self.sightings.iter().any(|s| s.url == article.url) {
+            return;
+        }
+        self.sightings.push(Sighting {
+            url: article.url.clone(),
+            source: article.source.clone(),
+            text: format!("{} {}", article.title, article.desc).to_lowercase(),
+            seen_at: article.fetched_at,
+        });
+    }
+
+    /// Articles first fetched on each of the last days before `now`, the oldest day first
+    pub fn daily_counts(&self, now: DateTime<Utc>) -> Vec<usize> {
+        let mut counts = vec![0; REPORT_DAYS as usize];
+        for sighting in &self.sightings {
+            let days_ago = (now - sighting.seen_at).num_days();
+            if (0..REPORT_DAYS).contains(&days_ago) {
+                counts[(REPORT_DAYS - 1 - days_ago) as usize] += 1;
+            }
+        }
+        counts
+    }
+
+    /// Coverage of each of the comma separated `topics` over the week before `now`
+    pub fn report(&self, topics: &str, now: DateTime<Utc>) -> Vec<TopicCoverage> {
+        let since = now - Duration::days(REPORT_DAYS);
+        let week: Vec<_> = self
+            .sightings
+            .iter()
+            .filter(|s| s.seen_at >= since)
+            .collect();
+        let mut articles = BTreeMap::new();
+        for sighting in &week {
+            *articles.entry(sighting.source.as_str()).or_insert(0) += 1;
+        }
+
+        topics
+            .split(',')
+            .map(str::trim)
+            .filter(|topic| !topic.is_empty())
+            .map(|topic| {
+                let lower = topic.to_lowercase();
+                let mut sources: Vec<_> = articles
+                    .iter()
+                    .map(|(source, articles)| SourceCoverage {
+                        source: source.to_string(),
+                        mentions: week
+                            .iter()
+                            .filter(|s| s.source == *source && s.text.contains(&lower))
+                            .count(),
+                        articles: *articles,
+                    })
+                    .collect();
+                sources.sort_by(|a, b| b.mentions.cmp(&a.mentions));
+                TopicCoverage {
+                    topic: topic.to_string(),
+                    sources,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Bars of each topic's share of every source's articles, with the counts
+pub fn show_report(ui: &mut Ui, report: &[TopicCoverage]) {
+    for coverage in report {
+        ui.strong(&coverage.topic);
+        Grid::new(("topic_trend", &coverage.topic)).show(ui, |ui| {
+            for source in &coverage.sources {
+                ui.label(&source.source);
+                ui.add(
+                    ProgressBar::new(source.share())
+                        .desired_width(BAR_WIDTH)
+                        .text(format!("{:.0} %", source.share() * 100.)),
+                );
+                ui.label(format!("{} of {}", source.mentions, source.articles));
+                ui.end_row();
+            }
+        });
+    }
+}
@@ -0,0 +1,61 @@
+use crate::news::NewsCardData;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Days an article is remembered after it was first fetched
+const MAX_AGE_DAYS: i64 = 7;
+
+/// When each article was first fetched, and the newest article seen in each view of the feed, to
+/// tell the articles that came since the last visit. newsapi gives no publication date.
+#[derive(Default, Serialize, Deserialize)]
+pub struct Visits {
+    first_seen: BTreeMap<String, DateTime<Utc>>,
+    /// By the view's key, the country codes of the feed
+    last_seen: BTreeMap<String, DateTime<Utc>>,
+}
+
+impl Visits {
+    /// Moves the articles that came since the view's last visit to the top of the feed, and
+    /// returns how many there are. On a first visit everything counts as seen.
+    pub fn sort_unseen(
+        &mut self,
+        view: &str,
+        articles: &mut Vec<NewsCardData>,
+        now: DateTime<Utc>,
+    ) -> usize {
+        let since = now - Duration::days(MAX_AGE_DAYS);
+        self.first_seen.retain(|_, seen| *seen >= since);
+        for a in articles.iter() {
+            self.first_seen.entry(a.url.clone()).or_insert(now);
+        }
+
+        let last_seen = match self.last_seen.get(view) {
+            Some(last_seen) => *last_seen,
+            None => {
+                self.visit(view, articles);
+                return 0;
+            }
+        };
+        let (unseen, seen): (Vec<_>, Vec<_>) = std::mem::take(articles)
+            .into_iter()
+            .partition(|a| self.first_seen[&a.url] > last_seen);
+        let count = unseen.len();
+        *articles = unseen;
+        articles.extend(seen);
+        count
+    }
+
+    /// Marks the articles of the view as seen
+    pub fn visit(&mut self, view: &str, articles: &[NewsCardData]) {
+        let newest = articles
+            .iter()
+            .filter_map(|a| self.first_seen.get(&a.url))
+            .max()
+            .copied();
+        if let Some(newest) = newest {
+            let last_seen = self.last_seen.entry(view.to_string()).or_insert(newest);
+            *last_seen = newest.max(*last_seen);
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use crate::http;
+use crate::news::NewsCardData;
+use crate::outbox::OutboundRequest;
+use eframe::egui::{Context, Grid, TextEdit, Ui};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// Seconds before its expiry a token is renewed, so that a queued request doesn't outlive it
+const TOKEN_MARGIN: f64 = 60.;
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WallabagSettings {
+    /// Url of the instance, like https://wallabag.example.com
+    pub server: String,
+    /// Of an API client created under "API clients management" on the instance
+    pub client_id: String,
+    pub client_secret: String,
+    pub username: String,
+    pub password: String,
+}
+
+impl WallabagSettings {
+    pub fn is_configured(&self) -> bool {
+        [
+            &self.server,
+            &self.client_id,
+            &self.client_secret,
+            &self.username,
+        ]
+        .iter()
+        .all(|field| !field.trim().is_empty())
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/{}", self.server.trim().trim_end_matches('/'), path)
+    }
+
+    pub fn show(&mut self, ui: &mut Ui) {
+        Grid::new("wallabag").num_columns(2).show(ui, |ui| {
+            ui.label("Server");
+            ui.add(
+                TextEdit::singleline(&mut self.server).hint_text("https://wallabag.example.com"),
+            );
+            ui.end_row();
+            ui.label("Client ID");
+            ui.text_edit_singleline(&mut self.client_id);
+            ui.end_row();
+            ui.label("Client secret");
+            ui.add(TextEdit::singleline(&mut self.client_secret).password(true));
+            ui.end_row();
+            ui.label("Username");
+            ui.text_edit_singleline(&mut self.username);
+            ui.end_row();
+            ui.label("Password");
+            ui.add(TextEdit::singleline(&mut self.password).password(true));
+            ui.end_row();
+        });
+        ui.weak("The secret and password are stored unencrypted with the settings");
+    }
+}
+
+#[derive(Deserialize)]
+struct Token {
+    access_token: String,
+    /// Seconds the token is valid for
+    expires_in: f64,
+}
+
+/// Saves articles to a wallabag instance through the outbox, once an access token is got for them
+#[derive(Default)]
+pub struct Wallabag {
+    /// Access token and when it expires
+    token: Option<(String, f64)>,
+    /// Articles waiting for a token
+    waiting: Vec<NewsCardData>,
+    in_flight: bool,
+    answer: Arc<Mutex<Option<Result<Token, String>>>>,
+}
+
+impl Wallabag {
+    pub fn save(&mut self, article: NewsCardData) {
+        self.waiting.push(article);
+    }
+
+    /// Gets a token when articles wait for one, and returns the requests of those that can be
+    /// saved
+    pub fn poll(
+        &mut self,
+        settings: &WallabagSettings,
+        ctx: &Context,
+    ) -> Result<Vec<OutboundRequest>, String> {
+        let now = ctx.input().time;
+        let answer = self.answer.lock().unwrap().take();
+        if let Some(answer) = answer {
+            self.in_flight = false;
+            match answer {
+                Ok(token) => self.token = Some((token.access_token, now + token.expires_in)),
+                Err(e) => {
+                    // the articles would wait for a token that isn't coming
+                    self.waiting.clear();
+                    return Err(e);
+                }
+            }
+        }
+        if self.waiting.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        match &self.token {
+            Some((token, expires)) if now + TOKEN_MARGIN < *expires => Ok(self
+                .waiting
+                .drain(..)
+                .map(|article| save_request(settings, token, &article))
+                .collect()),
+            _ => {
+                if !self.in_flight {
+                    self.in_flight = true;
+                    request_token(settings, self.answer.clone(), ctx.clone());
+                }
+                Ok(Vec::new())
+            }
+        }
+    }
+}
+
+fn request_token(
+    settings: &WallabagSettings,
+    answer: Arc<Mutex<Option<Result<Token, String>>>>,
+    ctx: Context,
+) {
+    let body = serde_json::json!({
+        "grant_type": "password",
+        "client_id": settings.client_id.trim(),
+        "client_secret": settings.client_secret.trim(),
+        "username": settings.username.trim(),
+        "password": settings.password,
+    });
+    let mut request = ehttp::Request::post(
+        settings.url("oauth/v2/token"),
+        body.to_string().into_bytes(),
+    );
+    request
+        .headers
+        .insert("Content-Type".to_string(), "application/json".to_string());
+    http::fetch(request, move |response| {
+        let token = match response {
+            Ok(response) if response.ok => {
+                serde_json::from_slice(&response.bytes).map_err(|e| e.to_string())
+            }
+            Ok(response) => Err(format!("{} {}", response.status, response.status_text)),
+            Err(e) => Err(e),
+        };
+        *answer.lock().unwrap() = Some(token);
+        ctx.request_repaint();
+    });
+}
+
+fn save_request(
+    settings: &WallabagSettings,
+    token: &str,
+    article: &NewsCardData,
+) -> OutboundRequest {
+    let body = serde_json::json!({
+        "url": article.url,
+        "title": article.title,
+    });
+    OutboundRequest {
+        label: format!("Saving \"{}\" to wallabag", article.title),
+        url: settings.url("api/entries.json"),
+        headers: vec![
+            ("Content-Type".to_string(), "application/json".to_string()),
+            ("Authorization".to_string(), format!("Bearer {}", token)),
+        ],
+        body: body.to_string(),
+    }
+}
@@ -0,0 +1,204 @@
+use crate::http;
+use eframe::egui::{Context, RichText, TextEdit, Ui};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+/// Seconds before the weather of a place is fetched again
+pub const WEATHER_REFRESH: f64 = 60. * 60.;
+const GEOCODING_API: &str = "https://geocoding-api.open-meteo.com/v1/search";
+const FORECAST_API: &str = "https://api.open-meteo.com/v1/forecast";
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WeatherSettings {
+    /// Whether the weather of the city is shown in the top panel
+    pub enabled: bool,
+    pub city: String,
+}
+
+impl WeatherSettings {
+    pub fn show(&mut self, ui: &mut Ui) {
+        ui.checkbox(&mut self.enabled, "Show the weather in the top panel");
+        ui.horizontal(|ui| {
+            ui.label("City");
+            ui.add_enabled(
+                self.enabled,
+                TextEdit::singleline(&mut self.city).hint_text("Paris"),
+            );
+        });
+        ui.weak("Forecasts by Open-Meteo, which needs no key");
+    }
+}
+
+#[derive(Clone)]
+pub struct CurrentWeather {
+    pub temperature: f32,
+    pub windspeed: f32,
+    pub code: u8,
+}
+
+#[derive(Deserialize)]
+struct Forecast {
+    current_weather: ForecastWeather,
+}
+
+#[derive(Deserialize)]
+struct ForecastWeather {
+    temperature: f32,
+    windspeed: f32,
+    weathercode: u8,
+}
+
+#[derive(Deserialize)]
+struct Places {
+    /// Missing when no place has the name
+    #[serde(default)]
+    results: Vec<Place>,
+}
+
+#[derive(Deserialize)]
+struct Place {
+    latitude: f64,
+    longitude: f64,
+}
+
+pub enum WeatherState {
+    Loading,
+    Loaded(CurrentWeather),
+    Failed(String),
+}
+
+/// Weather of the places shown and when it was fetched, by a key of the place
+pub type WeatherCache = Arc<Mutex<BTreeMap<String, (f64, WeatherState)>>>;
+
+/// Current weather of the configured city, shown in the top panel
+#[derive(Default)]
+pub struct CityWeather {
+    cache: WeatherCache,
+}
+
+impl CityWeather {
+    /// Draws the temperature and an icon, details on hover
+    pub fn show(&self, ui: &mut Ui, settings: &WeatherSettings) {
+        let city = settings.city.trim();
+        if !settings.enabled || city.is_empty() {
+            return;
+        }
+        let key = city.to_lowercase();
+        let now = ui.input().time;
+        let mut cache = self.cache.lock().unwrap();
+        let stale = cache
+            .get(&key)
+            .map_or(true, |(fetched_at, _)| now - fetched_at > WEATHER_REFRESH);
+        if stale {
+            cache.insert(key.clone(), (now, WeatherState::Loading));
+            fetch_city_weather(&key, self.cache.clone(), ui.ctx().clone());
+        }
+        match cache.get(&key).map(|(_, state)| state) {
+            Some(WeatherState::Loaded(weather)) => {
+                let (icon, description) = describe(weather.code);
+                ui.label(format!("{} {:.0} °C", icon, weather.temperature))
+                    .on_hover_text(format!(
+                        "{}: {}, wind {:.0} km/h",
+                        city, description, weather.windspeed
+                    ));
+            }
+            Some(WeatherState::Failed(e)) => {
+                ui.label(RichText::new("🌡 ?").weak())
+                    .on_hover_text(format!("No weather for {}: {}", city, e));
+            }
+            _ => {
+                ui.weak("🌡 …");
+            }
+        }
+    }
+}
+
+/// Locates the city with Open-Meteo's geocoding, then fetches its weather
+fn fetch_city_weather(key: &str, cache: WeatherCache, ctx: Context) {
+    let url = format!("{}?name={}&count=1", GEOCODING_API, encode(key));
+    let key = key.to_string();
+    http::fetch(ehttp::Request::get(url), move |response| {
+        let place = match response {
+            Ok(response) if response.ok => serde_json::from_slice::<Places>(&response.bytes)
+                .map_err(|e| format!("Unreadable places: {}", e))
+                .and_then(|places| {
+                    places
+                        .results
+                        .into_iter()
+                        .next()
+                        .ok_or_else(|| "Unknown city".to_string())
+                }),
+            Ok(response) => Err(format!("{} {}", response.status, response.status_text)),
+            Err(e) => Err(e),
+        };
+        match place {
+            Ok(place) => fetch_weather(&key, place.latitude, place.longitude, cache, ctx),
+            Err(e) => {
+                if let Some((_, entry)) = cache.lock().unwrap().get_mut(&key) {
+                    *entry = WeatherState::Failed(e);
+                }
+                ctx.request_repaint();
+            }
+        }
+    });
+}
+
+/// Fetches the current weather at the location from Open-Meteo, which needs no key
+pub fn fetch_weather(key: &str, latitude: f64, longitude: f64, cache: WeatherCache, ctx: Context) {
+    let url = format!(
+        "{}?latitude={:.2}&longitude={:.2}&current_weather=true",
+        FORECAST_API, latitude, longitude
+    );
+    let key = key.to_string();
+    http::fetch(ehttp::Request::get(url), move |response| {
+        let state = match response {
+            Ok(response) if response.ok => {
+                match serde_json::from_slice::<Forecast>(&response.bytes) {
+                    Ok(forecast) => WeatherState::Loaded(CurrentWeather {
+                        temperature: forecast.current_weather.temperature,
+                        windspeed: forecast.current_weather.windspeed,
+                        code: forecast.current_weather.weathercode,
+                    }),
+                    Err(e) => WeatherState::Failed(format!("Unreadable forecast: {}", e)),
+                }
+            }
+            Ok(response) => {
+                WeatherState::Failed(format!("{} {}", response.status, response.status_text))
+            }
+            Err(e) => WeatherState::Failed(e),
+        };
+        if let Some((_, entry)) = cache.lock().unwrap().get_mut(&key) {
+            *entry = state;
+        }
+        ctx.request_repaint();
+    });
+}
+
+/// Percent-encodes the city's name for the query string
+fn encode(text: &str) -> String {
+    text.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Icon and description of a WMO weather code
+pub fn describe(code: u8) -> (&'static str, &'static str) {
+    match code {
+        0 => ("☀", "clear"),
+        1..=3 => ("⛅", "partly cloudy"),
+        45 | 48 => ("🌫", "fog"),
+        51..=67 => ("🌧", "rain"),
+        71..=77 => ("❄", "snow"),
+        80..=82 => ("🌦", "showers"),
+        85 | 86 => ("🌨", "snow showers"),
+        95..=99 => ("⛈", "thunderstorm"),
+        _ => ("🌡", "unknown"),
+    }
+}
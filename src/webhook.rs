@@ -0,0 +1,151 @@
+use crate::news::NewsCardData;
+use crate::outbox::OutboundRequest;
+use crate::review;
+use eframe::egui::{ComboBox, Grid, TextEdit, Ui};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Urls of notified articles remembered so that they aren't sent again, the oldest are forgotten
+const MAX_NOTIFIED: usize = 1000;
+/// Characters a Slack or Discord message is kept to, Discord allows 2000
+const MAX_MESSAGE_CHARS: usize = 1900;
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum WebhookFormat {
+    /// The articles as a JSON array
+    Json,
+    Slack,
+    Discord,
+}
+
+impl WebhookFormat {
+    pub const ALL: [WebhookFormat; 3] = [Self::Json, Self::Slack, Self::Discord];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Json => "JSON",
+            Self::Slack => "Slack",
+            Self::Discord => "Discord",
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebhookSettings {
+    /// Url matching headlines are POSTed to, none when empty
+    pub url: String,
+    /// Comma separated words whose headlines are sent
+    pub keywords: String,
+    pub format: WebhookFormat,
+}
+
+impl Default for WebhookSettings {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            keywords: String::new(),
+            format: WebhookFormat::Json,
+        }
+    }
+}
+
+impl WebhookSettings {
+    pub fn show(&mut self, ui: &mut Ui) {
+        Grid::new("webhook").num_columns(2).show(ui, |ui| {
+            ui.label("Url");
+            ui.add(TextEdit::singleline(&mut self.url).hint_text("https://hooks.slack.com/…"));
+            ui.end_row();
+            ui.label("Keywords");
+            ui.add(TextEdit::singleline(&mut self.keywords).hint_text("outage, recall"));
+            ui.end_row();
+            ui.label("Format");
+            ComboBox::from_id_source("webhook_format")
+                .selected_text(self.format.name())
+                .show_ui(ui, |ui| {
+                    for format in WebhookFormat::ALL {
+                        ui.selectable_value(&mut self.format, format, format.name());
+                    }
+                });
+            ui.end_row();
+        });
+        ui.weak("New matching headlines are sent once per refresh");
+    }
+}
+
+/// Headlines matching the webhook's keywords, sent together at the end of a refresh
+#[derive(Default, Serialize, Deserialize)]
+pub struct Webhook {
+    notified: VecDeque<String>,
+    #[serde(skip)]
+    matching: Vec<NewsCardData>,
+}
+
+impl Webhook {
+    /// Keeps `article` for the next notification if it matches and wasn't sent before
+    pub fn offer(&mut self, article: &NewsCardData, settings: &WebhookSettings) {
+        let known = self.notified.contains(&article.url)
+            || self.matching.iter().any(|a| a.url == article.url);
+        if !settings.url.trim().is_empty() && review::matches(article, &settings.keywords) && !known
+        {
+            self.matching.push(article.clone());
+        }
+    }
+
+    /// Request sending the headlines kept since the last one, if any
+    pub fn notification(&mut self, settings: &WebhookSettings) -> Option<OutboundRequest> {
+        if self.matching.is_empty() {
+            return None;
+        }
+        let articles = std::mem::take(&mut self.matching);
+        for article in &articles {
+            self.notified.push_back(article.url.clone());
+        }
+        while self.notified.len() > MAX_NOTIFIED {
+            self.notified.pop_front();
+        }
+
+        let body = match settings.format {
+            WebhookFormat::Json => {
+                let articles: Vec<_> = articles
+                    .iter()
+                    .map(|a| {
+                        serde_json::json!({
+                            "title": a.title,
+                            "description": a.desc,
+                            "url": a.url,
+                            "source": a.source,
+                        })
+                    })
+                    .collect();
+                serde_json::json!({ "articles": articles })
+            }
+            WebhookFormat::Slack => serde_json::json!({ "text": message(&articles) }),
+            WebhookFormat::Discord => serde_json::json!({ "content": message(&articles) }),
+        };
+        let label = match articles.len() {
+            1 => "Sending 1 headline to the webhook".to_string(),
+            n => format!("Sending {} headlines to the webhook", n),
+        };
+        Some(OutboundRequest {
+            label,
+            url: settings.url.trim().to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.to_string(),
+        })
+    }
+}
+
+/// One line per headline, as many as fit in a message
+fn message(articles: &[NewsCardData]) -> String {
+    let mut message = String::new();
+    for (i, article) in articles.iter().enumerate() {
+        let line = format!("• {} {}\n", article.title, article.url);
+        if message.chars().count() + line.chars().count() > MAX_MESSAGE_CHARS {
+            message.push_str(&format!("and {} more", articles.len() - i));
+            break;
+        }
+        message.push_str(&line);
+    }
+    message
+}
@@ -0,0 +1,235 @@
+use crate::aggregate::SourceCaps;
+use crate::categories::Category;
+#[cfg(feature = "gui")]
+use crate::errors::HeadlinesError;
+use crate::http_cache::SharedHttpCache;
+#[cfg(feature = "gui")]
+use crate::news::NewsCardData;
+use crate::news_source::{NewsApi, NewsSource};
+use crate::sources::SourceSettings;
+use newsapi::Country;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "gui")]
+use std::collections::VecDeque;
+#[cfg(feature = "gui")]
+use std::mem;
+#[cfg(feature = "gui")]
+use std::sync::mpsc::{SyncSender, TrySendError};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Seconds before the first retry of a failed request, doubled for every retry after it
+const RETRY_DELAY: f64 = 1.;
+const MAX_RETRY_DELAY: f64 = 30.;
+/// Seconds the watchdog allows a request on top of its attempts and retry delays
+const STALL_MARGIN: f64 = 5.;
+
+/// Messages from the app to the fetch worker
+pub enum Msg {
+    APIKeySet(String),
+    Refresh(Vec<Country>),
+    Search(String),
+    SetSourceCaps(SourceCaps),
+    SetSources(SourceSettings),
+    SetRetryPolicy(RetryPolicy),
+    Shutdown,
+}
+
+/// Messages waiting for room in the fetch worker's channel, so that the UI never blocks on a
+/// worker busy fetching
+#[cfg(feature = "gui")]
+#[derive(Default)]
+pub struct WorkerQueue {
+    queue: VecDeque<Msg>,
+}
+
+#[cfg(feature = "gui")]
+impl WorkerQueue {
+    /// Queues a message, in place of a queued one of the same kind it supersedes
+    pub fn push(&mut self, msg: Msg) {
+        let kind = mem::discriminant(&msg);
+        match self
+            .queue
+            .iter_mut()
+            .find(|queued| mem::discriminant(*queued) == kind)
+        {
+            Some(queued) => *queued = msg,
+            None => self.queue.push_back(msg),
+        }
+    }
+
+    /// Hands the worker as many messages as its channel takes, returns whether some are left.
+    /// They're dropped when the worker is gone.
+    pub fn flush(&mut self, tx: &SyncSender<Msg>) -> Result<bool, String> {
+        while let Some(msg) = self.queue.pop_front() {
+            match tx.try_send(msg) {
+                Ok(()) => {}
+                Err(TrySendError::Full(msg)) => {
+                    self.queue.push_front(msg);
+                    return Ok(true);
+                }
+                Err(TrySendError::Disconnected(_)) => {
+                    self.queue.clear();
+                    return Err("stopped taking messages".to_string());
+                }
+            }
+        }
+        Ok(false)
+    }
+}
+
+/// Messages from the fetch worker to the app, every fetch ends with `Done`
+#[cfg(feature = "gui")]
+pub enum Fetched {
+    /// A country's or a source's request is over, successful or not, the watchdog's sign of life
+    Progress,
+    /// Every article of the fetch, merged, sent at once before it's done
+    Articles(Vec<NewsCardData>),
+    Failed(HeadlinesError),
+    Done,
+}
+
+/// What the worker does once a message is handled
+#[derive(PartialEq, Eq)]
+pub enum Step {
+    Fetch,
+    Wait,
+    Stop,
+}
+
+/// How long a request may take and how often a failed one is tried again
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetryPolicy {
+    /// Seconds an attempt may take before it counts as failed
+    pub timeout: u64,
+    /// Attempts after the first one, only network errors and timeouts are retried
+    pub retries: u32,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            timeout: 15,
+            retries: 2,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout.max(1))
+    }
+
+    /// Seconds a fetch may go without progress before the watchdog deems it stalled, the longest a
+    /// request can take with all its retries
+    pub fn stall_timeout(&self) -> f64 {
+        let attempts = f64::from(self.retries + 1);
+        let delays = MAX_RETRY_DELAY * 1.5 * f64::from(self.retries);
+        self.timeout().as_secs_f64() * attempts + delays + STALL_MARGIN
+    }
+
+    /// Wait before the retry numbered `attempt` from 0, doubling every time, scaled by 0.5 to 1.5
+    /// with `jitter` from 0 to 1 so that clients don't all retry at once
+    pub fn backoff(&self, attempt: u32, jitter: f64) -> Duration {
+        let delay = (RETRY_DELAY * 2f64.powi(attempt as i32)).min(MAX_RETRY_DELAY);
+        Duration::from_secs_f64(delay * (0.5 + jitter.clamp(0., 1.)))
+    }
+}
+
+/// Everything a fetch depends on, messages update it before the worker fetches again
+#[derive(Clone)]
+pub struct FetchState {
+    pub api_key: String,
+    /// Countries whose headlines are merged in the feed, one request each
+    pub countries: Vec<Country>,
+    pub query: String,
+    pub source_caps: SourceCaps,
+    pub retry: RetryPolicy,
+    /// Sources fetched besides newsapi.org
+    pub sources: SourceSettings,
+    /// Category the newsapi.org headlines are narrowed to, none for the top headlines
+    pub category: Option<Category>,
+    /// Page of the newsapi.org results, from 1, the other sources only have the first
+    pub page: u32,
+    /// Where the countries' headlines come from
+    pub source: Arc<dyn NewsSource>,
+    /// Responses of earlier fetches, so that fetching them again is a conditional request
+    pub http_cache: SharedHttpCache,
+}
+
+impl FetchState {
+    pub fn new(
+        api_key: String,
+        countries: Vec<Country>,
+        source_caps: SourceCaps,
+        retry: RetryPolicy,
+    ) -> Self {
+        Self {
+            api_key,
+            countries,
+            query: String::new(),
+            source_caps,
+            retry,
+            sources: SourceSettings::default(),
+            category: None,
+            page: 1,
+            source: Arc::new(NewsApi),
+            http_cache: SharedHttpCache::default(),
+        }
+    }
+
+    /// Step to take when the worker starts
+    pub fn start(&self) -> Step {
+        self.fetch_or_wait()
+    }
+
+    pub fn handle(&mut self, msg: Msg) -> Step {
+        match msg {
+            Msg::APIKeySet(api_key) => {
+                self.api_key = api_key;
+                self.fetch_or_wait()
+            }
+            Msg::Refresh(countries) => {
+                self.countries = countries;
+                self.fetch_or_wait()
+            }
+            Msg::Search(query) => {
+                self.query = query.trim().to_string();
+                self.fetch_or_wait()
+            }
+            Msg::SetSourceCaps(source_caps) => {
+                // applies to the next fetch, refetching would only shuffle the feed
+                self.source_caps = source_caps;
+                Step::Wait
+            }
+            Msg::SetSources(sources) => {
+                self.sources = sources;
+                self.fetch_or_wait()
+            }
+            Msg::SetRetryPolicy(retry) => {
+                self.retry = retry;
+                Step::Wait
+            }
+            Msg::Shutdown => Step::Stop,
+        }
+    }
+
+    /// Whether an article belongs in the feed for the current query
+    pub fn matches(&self, title: &str, desc: &str) -> bool {
+        if self.query.is_empty() {
+            return true;
+        }
+        let query = self.query.to_lowercase();
+        title.to_lowercase().contains(&query) || desc.to_lowercase().contains(&query)
+    }
+
+    fn fetch_or_wait(&self) -> Step {
+        // newsapi.org rejects every request without a key
+        if self.api_key.is_empty() || self.countries.is_empty() {
+            Step::Wait
+        } else {
+            Step::Fetch
+        }
+    }
+}
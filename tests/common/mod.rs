@@ -0,0 +1,65 @@
+//! Runs the app's frames through egui without a window
+#![allow(dead_code)]
+
+use eframe::egui::{vec2, Context, Event, FullOutput, Pos2, RawInput, Rect, Shape, Vec2};
+use headlines::Headlines;
+use std::thread;
+use std::time::Duration;
+
+/// Size of the app's window when it opens
+pub const SCREEN: Vec2 = vec2(540., 960.);
+/// Frames waited for a fetch to come through before giving up
+const MAX_FRAMES: usize = 300;
+const FRAME_WAIT: Duration = Duration::from_millis(10);
+
+/// Runs a frame with the events on a screen of `SCREEN` at one pixel per point
+pub fn frame(app: &mut Headlines, ctx: &Context, events: Vec<Event>) -> FullOutput {
+    let input = RawInput {
+        screen_rect: Some(Rect::from_min_size(Pos2::ZERO, SCREEN)),
+        pixels_per_point: Some(1.),
+        events,
+        ..Default::default()
+    };
+    ctx.run(input, |ctx| app.show(ctx))
+}
+
+/// Calls `f` with every shape the frame drew, those in groups included
+pub fn visit(output: &FullOutput, f: &mut impl FnMut(&Shape)) {
+    for clipped in &output.shapes {
+        visit_shape(&clipped.1, f);
+    }
+}
+
+fn visit_shape(shape: &Shape, f: &mut impl FnMut(&Shape)) {
+    match shape {
+        Shape::Vec(group) => {
+            for shape in group {
+                visit_shape(shape, f);
+            }
+        }
+        shape => f(shape),
+    }
+}
+
+/// Texts drawn by the frame
+pub fn texts(output: &FullOutput) -> Vec<String> {
+    let mut texts = Vec::new();
+    visit(output, &mut |shape| {
+        if let Shape::Text(text) = shape {
+            texts.push(text.galley.job.text.clone());
+        }
+    });
+    texts
+}
+
+/// Runs frames until one draws a text containing `needle`, returns whether one did
+pub fn wait_for(app: &mut Headlines, ctx: &Context, needle: &str) -> bool {
+    for _ in 0..MAX_FRAMES {
+        let output = frame(app, ctx, Vec::new());
+        if texts(&output).iter().any(|text| text.contains(needle)) {
+            return true;
+        }
+        thread::sleep(FRAME_WAIT);
+    }
+    false
+}
@@ -0,0 +1,39 @@
+//! Drives the app through egui without a window, its headlines coming from the fake source
+
+mod common;
+
+use common::{frame, texts, wait_for};
+use eframe::egui::Context;
+use headlines::{FakeSource, Headlines};
+use newsapi::Country;
+
+#[test]
+fn first_run_shows_the_onboarding() {
+    let ctx = Context::default();
+    let mut app = Headlines::with_source(Box::new(FakeSource::new(3))).start(&ctx);
+    let output = frame(&mut app, &ctx, Vec::new());
+    assert!(texts(&output)
+        .iter()
+        .any(|text| text == "Welcome to Headlines"));
+}
+
+#[test]
+fn the_feed_shows_the_source_headlines() {
+    let ctx = Context::default();
+    let mut app = Headlines::with_source(Box::new(FakeSource::new(3)))
+        .with_api_key("key")
+        .start(&ctx);
+    for n in 1..=3 {
+        let title = FakeSource::title(Country::FR, 1, n);
+        assert!(wait_for(&mut app, &ctx, &title), "{} never shown", title);
+    }
+}
+
+#[test]
+fn a_failing_source_is_reported() {
+    let ctx = Context::default();
+    let mut app = Headlines::with_source(Box::new(FakeSource::failing("no network here")))
+        .with_api_key("key")
+        .start(&ctx);
+    assert!(wait_for(&mut app, &ctx, "no network here"));
+}
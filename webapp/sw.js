@@ -0,0 +1,47 @@
+// Keeps the app's shell so that it starts offline, the headlines it shows then are those it stored
+// itself. The shell is fetched from the network first so that updates show up when online.
+const CACHE_NAME = 'headlines-shell-v1';
+// the fonts are compiled into the wasm
+const SHELL = [
+    './',
+    './index.html',
+    './headlines.js',
+    './headlines_bg.wasm',
+    './manifest.json',
+    './icon.svg',
+];
+
+self.addEventListener('install', (event) => {
+    event.waitUntil(caches.open(CACHE_NAME).then((cache) => cache.addAll(SHELL)));
+    self.skipWaiting();
+});
+
+self.addEventListener('activate', (event) => {
+    // drops the shells of older versions of this worker
+    event.waitUntil(
+        caches.keys()
+            .then((names) => Promise.all(names
+                .filter((name) => name.startsWith('headlines-shell-') && name !== CACHE_NAME)
+                .map((name) => caches.delete(name))))
+            .then(() => self.clients.claim())
+    );
+});
+
+self.addEventListener('fetch', (event) => {
+    const url = new URL(event.request.url);
+    // news, images and the rest go to the network as usual, the app caches them itself
+    if (event.request.method !== 'GET' || url.origin !== self.location.origin) {
+        return;
+    }
+    event.respondWith(
+        fetch(event.request)
+            .then((response) => {
+                if (response.ok) {
+                    const copy = response.clone();
+                    caches.open(CACHE_NAME).then((cache) => cache.put(event.request, copy));
+                }
+                return response;
+            })
+            .catch(() => caches.match(event.request, { ignoreSearch: true }))
+    );
+});